@@ -0,0 +1,100 @@
+/*
+    基准测试：并行单词计数，ShardedMap与Mutex<HashMap>的对比
+
+        cargo bench --bench sharded_map
+
+    WORDS个单词（词表大小VOCABULARY）平均分给THREADS个线程，每个线程逐个单词更新同一个表：
+
+        - mutex-hashmap：所有线程共用一把锁；
+        - sharded-map：ShardedMap::upsert，只锁单词所在的分片。
+*/
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rust_exercise::thread::sharded_map::ShardedMap;
+
+const WORDS: usize = 400_000;
+const VOCABULARY: u64 = 5_000;
+const THREADS: usize = 4;
+const RUNS: usize = 5;
+
+type Counts = HashMap<String, usize>;
+
+// 用线性同余生成器产生可重现的单词序列
+fn corpus() -> Vec<String> {
+    let mut state = 42u64;
+    (0..WORDS)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            format!("word{}", (state >> 33) % VOCABULARY)
+        })
+        .collect()
+}
+
+fn mutex_hashmap(words: &[String]) -> Counts {
+    let map = Mutex::new(HashMap::new());
+    thread::scope(|scope| {
+        for part in words.chunks(words.len().div_ceil(THREADS)) {
+            let map = &map;
+            scope.spawn(move || {
+                for word in part {
+                    *map.lock().unwrap().entry(word.clone()).or_insert(0) += 1;
+                }
+            });
+        }
+    });
+    map.into_inner().unwrap()
+}
+
+fn sharded_map(words: &[String]) -> Counts {
+    let map = ShardedMap::new();
+    thread::scope(|scope| {
+        for part in words.chunks(words.len().div_ceil(THREADS)) {
+            let map = &map;
+            scope.spawn(move || {
+                for word in part {
+                    map.upsert(word.clone(), |entry| *entry.or_insert(0) += 1);
+                }
+            });
+        }
+    });
+    map.into_inner()
+}
+
+// 运行RUNS次，取中位数
+fn measure(f: &dyn Fn() -> Counts) -> (Duration, Counts) {
+    let mut result = HashMap::new();
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            result = f();
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    (times[RUNS / 2], result)
+}
+
+fn main() {
+    let words = corpus();
+    let mut expected: Counts = HashMap::new();
+    for word in &words {
+        *expected.entry(word.clone()).or_insert(0) += 1;
+    }
+
+    let cases: [(&str, &dyn Fn() -> Counts); 2] = [
+        ("mutex-hashmap", &|| mutex_hashmap(&words)),
+        ("sharded-map", &|| sharded_map(&words)),
+    ];
+
+    println!("{} words, {} distinct, {} threads, median of {} runs", WORDS, expected.len(), THREADS, RUNS);
+    // 只有一个CPU核时线程之间几乎没有锁竞争，两者的差别不明显
+    println!("available parallelism: {}", thread::available_parallelism().map_or(1, |n| n.get()));
+    for (name, case) in cases.iter() {
+        let (time, result) = measure(*case);
+        assert_eq!(result, expected, "{} computed a wrong result", name);
+        println!("{:<14} {:>10.2?}", name, time);
+    }
+}
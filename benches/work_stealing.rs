@@ -0,0 +1,96 @@
+/*
+    基准测试：倾斜负载下几种map-reduce调度方式的对比
+
+        cargo bench --bench work_stealing
+
+    一共CHUNKS个数据块，前1/8的数据块的计算量是其余的HEAVY倍，并且集中在输入的开头，
+    这正是“事先平均分配”最吃亏的情况：
+
+        - spawn-per-chunk：testcase_map_reduce最初的写法，每个数据块一个线程；
+        - static-partition：按顺序平均切成WORKERS段，每段一个线程；
+        - shared-queue：map_reduce默认的共享队列；
+        - work-stealing：map_reduce_with + Schedule::WorkStealing。
+*/
+use std::hint::black_box;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rust_exercise::thread::map_reduce::{map_reduce_with, Options, Schedule};
+
+const CHUNKS: usize = 256;
+const WORKERS: usize = 4;
+const LIGHT: u64 = 20_000;
+const HEAVY: u64 = 40;
+const RUNS: usize = 5;
+
+// 每个数据块要做的计算量
+fn workload() -> Vec<u64> {
+    (0..CHUNKS).map(|i| if i < CHUNKS / 8 { LIGHT * HEAVY } else { LIGHT }).collect()
+}
+
+// 纯计算，不会被编译器优化掉
+fn burn(iterations: u64) -> u64 {
+    let mut x = iterations;
+    for _ in 0..iterations {
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    }
+    black_box(x) % 7
+}
+
+fn spawn_per_chunk(work: &[u64]) -> u64 {
+    thread::scope(|scope| {
+        let handlers: Vec<_> = work.iter().map(|&n| scope.spawn(move || burn(n))).collect();
+        handlers.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+fn static_partition(work: &[u64]) -> u64 {
+    let size = work.len().div_ceil(WORKERS);
+    thread::scope(|scope| {
+        let handlers: Vec<_> = work.chunks(size)
+            .map(|part| scope.spawn(move || part.iter().map(|&n| burn(n)).sum::<u64>()))
+            .collect();
+        handlers.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+fn with_schedule(work: &[u64], schedule: Schedule) -> u64 {
+    let options = Options::new(WORKERS).schedule(schedule);
+    map_reduce_with(work, |work| work.iter().copied(), burn, |a, b| a + b, options).unwrap_or(0)
+}
+
+// 运行RUNS次，取中位数
+fn measure(f: &dyn Fn() -> u64) -> (Duration, u64) {
+    let mut result = 0;
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            result = f();
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    (times[RUNS / 2], result)
+}
+
+fn main() {
+    let work = workload();
+    let expected: u64 = work.iter().map(|&n| burn(n)).sum();
+
+    let cases: [(&str, &dyn Fn() -> u64); 4] = [
+        ("spawn-per-chunk", &|| spawn_per_chunk(&work)),
+        ("static-partition", &|| static_partition(&work)),
+        ("shared-queue", &|| with_schedule(&work, Schedule::SharedQueue)),
+        ("work-stealing", &|| with_schedule(&work, Schedule::WorkStealing)),
+    ];
+
+    println!("{} chunks, first {} are {}x heavier, {} workers, median of {} runs",
+             CHUNKS, CHUNKS / 8, HEAVY, WORKERS, RUNS);
+    // 只有一个CPU核时几种方式的差别主要来自线程创建的开销，看不出调度的效果
+    println!("available parallelism: {}", thread::available_parallelism().map_or(1, |n| n.get()));
+    for (name, case) in cases.iter() {
+        let (time, result) = measure(*case);
+        assert_eq!(result, expected, "{} computed a wrong result", name);
+        println!("{:<18} {:>10.2?}", name, time);
+    }
+}
@@ -5,6 +5,23 @@ The word size is the same as usize, determined by the processor architecture eg
 Slices can be used to borrow a section of an array, and have the type signature &[T].
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "array_and_slice", about: "数组与借用数组一部分的切片", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let arr = [1, 2, 3, 4, 5];
+    // 借用整个数组，或者数组的一部分
+    let whole: &[i32] = &arr;
+    let part = &arr[1..4];
+    writeln!(out, "whole: {:?}, {} elements", whole, whole.len())?;
+    writeln!(out, "part: {:?}, first element: {}", part, part[0])?;
+    writeln!(out, "{:?} {:?}", arr.get(4), arr.get(5))
+}
+
 #[cfg(test)]
 mod tests{
     fn analyze_slice(slice: &[i32]) {
@@ -7,16 +7,33 @@
         两种形式使用的参数语法都相同。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 // 下面函数只有当目标系统是linux的时候才会编译
 #[cfg(target_os = "linux")]
-fn are_you_on_linux() {
-    println!("I'm running linux!")
+fn are_you_on_linux(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "I'm running linux!")
 }
 
 // 下面函数只有当目标系统不是linux的时候才会编译
 #[cfg(not(target_os = "linux"))]
-fn are_you_on_linux() {
-    println!("I'm not running linux!")
+fn are_you_on_linux(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "I'm not running linux!")
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "attribute_cfg", about: "用cfg属性和cfg!宏进行条件编译", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    are_you_on_linux(out)?;
+    // cfg!宏在布尔表达式中判断条件
+    if cfg!(target_os = "linux") {
+        writeln!(out, "Yes. It's definitely linux!")
+    } else {
+        writeln!(out, "Yes. It's definitely not linux!")
+    }
 }
 
 #[cfg(test)]
@@ -26,7 +43,10 @@ mod tests {
     #[test]
     fn test_attribute_cfg() {
         // 调用上面设定条件编译的函数
-        are_you_on_linux();
+        let mut out = Vec::new();
+        are_you_on_linux(&mut out).unwrap();
+        let expected = if cfg!(target_os = "linux") { "I'm running linux!\n" } else { "I'm not running linux!\n" };
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
 
         // 通过宏将条件编译逻辑加入代码
         if cfg!(target_os="linux") {
@@ -4,7 +4,24 @@
        简直是强迫症爱好者的福音呀！
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
 
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "attribute_dead_code", about: "用#[allow(dead_code)]关闭未使用代码的警告", run: demo },
+];
+
+fn used_function(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "called `used_function`")
+}
+
+// 没有被调用的函数会产生dead_code警告，加上#[allow(dead_code)]就不会了
+#[allow(dead_code)]
+fn unused_function() {}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    used_function(out)
+}
 
 #[cfg(test)]
 mod tests {
@@ -13,6 +30,7 @@ mod tests {
     }
 
     // 该函数未被调用，编译会报waring
+    #[allow(dead_code)]
     fn unused_function() {
         println!("called `unused_function`")
     }
@@ -30,4 +48,4 @@ mod tests {
     fn test_attribute_dead_code() {
         used_function()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,179 @@
+/*
+    编译失败测试compile_fail
+
+    object_safe.rs、lifetimes、static_and_dynamic_dispatch.rs 等文件里有不少
+    “这段代码编译不过”的例子，原先只写在注释里，升级工具链后这些说明可能悄悄失效。
+
+    现在把这些片段放到仓库根目录的 ui/ 下，每个文件开头用注释写明期望的错误：
+
+        // error-code: E0038
+        // error-message: the `foo2` method cannot be invoked on a trait object
+
+    测试会用本机的rustc（可用环境变量RUSTC指定）把每个文件编译成lib，
+    断言编译失败，并且输出中包含所有期望的错误码/错误信息。
+*/
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// ui片段编译时使用的edition，与本crate保持一致
+pub const EDITION: &str = "2018";
+
+/// 文件头部声明的期望
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Expectation {
+    /// 形如`E0038`的错误码
+    pub codes: Vec<String>,
+    /// 错误信息中应包含的文本
+    pub messages: Vec<String>,
+}
+
+impl Expectation {
+    /// 解析文件开头连续的`//`注释中的`error-code:`和`error-message:`
+    pub fn parse(source: &str) -> Expectation {
+        let mut expectation = Expectation::default();
+        for line in source.lines().map(str::trim).take_while(|line| line.starts_with("//")) {
+            let line = line.trim_start_matches('/').trim();
+            if let Some(code) = line.strip_prefix("error-code:") {
+                expectation.codes.push(code.trim().to_string());
+            } else if let Some(message) = line.strip_prefix("error-message:") {
+                expectation.messages.push(message.trim().to_string());
+            }
+        }
+        expectation
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty() && self.messages.is_empty()
+    }
+}
+
+/// 一个ui片段没有按预期失败的原因
+#[derive(Debug)]
+pub enum UiError {
+    /// 文件头部没有任何期望
+    NoExpectation,
+    /// 片段竟然编译通过了
+    Compiled,
+    /// 编译失败了，但缺少期望的错误码或错误信息
+    Unexpected { missing: Vec<String>, stderr: String },
+    Io(io::Error),
+}
+
+impl Display for UiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            UiError::NoExpectation => write!(f, "no `// error-code:` or `// error-message:` header"),
+            UiError::Compiled => write!(f, "expected compilation to fail, but it succeeded"),
+            UiError::Unexpected { missing, stderr } => {
+                write!(f, "missing expected errors {:?}, rustc said:\n{}", missing, stderr)
+            }
+            UiError::Io(err) => write!(f, "failed to run rustc: {}", err),
+        }
+    }
+}
+
+impl From<io::Error> for UiError {
+    fn from(err: io::Error) -> Self {
+        UiError::Io(err)
+    }
+}
+
+/// 仓库中存放ui片段的目录
+pub fn ui_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("ui")
+}
+
+/// dir下所有的`.rs`文件，按文件名排序
+pub fn ui_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn rustc() -> String {
+    std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string())
+}
+
+/// 编译一个ui片段，检查它是否按头部声明的方式失败
+///
+/// 只做到类型检查和借用检查（--emit=metadata），产物写到out_dir中
+pub fn check_file(path: &Path, out_dir: &Path) -> Result<(), UiError> {
+    let expectation = Expectation::parse(&fs::read_to_string(path)?);
+    if expectation.is_empty() {
+        return Err(UiError::NoExpectation);
+    }
+
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("ui");
+    let output = Command::new(rustc())
+        .arg("--edition").arg(EDITION)
+        .arg("--crate-type").arg("lib")
+        .arg("--crate-name").arg(stem)
+        .arg("--emit=metadata")
+        .arg("--error-format=short")
+        .arg("--out-dir").arg(out_dir)
+        .arg(path)
+        .output()?;
+    if output.status.success() {
+        return Err(UiError::Compiled);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    let missing: Vec<String> = expectation.codes.iter()
+        .filter(|code| !stderr.contains(&format!("error[{}]", code)))
+        .chain(expectation.messages.iter().filter(|message| !stderr.contains(message.as_str())))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(UiError::Unexpected { missing, stderr })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn test_parse_expectation() {
+        let source = "// 说明\n// error-code: E0038\n//   error-message: cannot be invoked\n\n// error-code: E0000\nfn f() {}";
+        let expectation = Expectation::parse(source);
+        assert_eq!(expectation.codes, vec!["E0038"]);
+        assert_eq!(expectation.messages, vec!["cannot be invoked"]);
+        assert!(Expectation::parse("fn f() {}").is_empty());
+    }
+
+    #[test]
+    fn test_check_file_that_compiles() {
+        let dir = TempDir::new("ui").unwrap();
+        let path = dir.write("fine.rs", "// error-code: E0038\npub fn fine() {}\n").unwrap();
+
+        match check_file(&path, dir.path()) {
+            Err(UiError::Compiled) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ui() {
+        let out_dir = TempDir::new("ui").unwrap();
+
+        let files = ui_files(&ui_dir()).unwrap();
+        assert!(!files.is_empty());
+        let failures: Vec<String> = files.iter()
+            .filter_map(|path| check_file(path, out_dir.path()).err().map(|err| format!("{}: {}", path.display(), err)))
+            .collect();
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+}
@@ -3,6 +3,23 @@
 // const: An unchangeable value (the common case).
 // static: A possibly mutable variable with 'static lifetime. The static lifetime is inferred and does not have to be specified. Accessing or modifying a mutable static variable is unsafe.
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "constants", about: "const与static", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    static LANGUAGE: &str = "Rust";
+    const THRESHOLD: i32 = 10;
+
+    let n = 16;
+    writeln!(out, "This is {}", LANGUAGE)?;
+    writeln!(out, "The threshold is {}", THRESHOLD)?;
+    writeln!(out, "{} is {}", n, if n > THRESHOLD { "big" } else { "small" })
+}
+
 #[cfg(test)]
 mod tests {
     // An unchangeable value
@@ -19,6 +36,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(static_mut_refs)]
     fn test_constants() {
         let n = 1025;
 
@@ -36,4 +54,4 @@ mod tests {
         // compile error
 //    THRESHOLD = 1;
     }
-}
\ No newline at end of file
+}
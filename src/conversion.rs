@@ -1,3 +1,31 @@
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "conversion", about: "From/Into与parse", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    struct Number {
+        value: i32,
+    }
+
+    impl From<i32> for Number {
+        fn from(item: i32) -> Self {
+            Number { value: item }
+        }
+    }
+
+    let n = Number::from(1024);
+    // 实现了From，也就免费获得了Into
+    let m: Number = 2048.into();
+    writeln!(out, "from: {}, into: {}", n.value, m.value)?;
+
+    let parsed: i32 = "1024".parse().map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    writeln!(out, "parsed: {}", parsed)?;
+    writeln!(out, "to_string: {:?}", 1025.to_string())
+}
+
 #[cfg(test)]
 mod tests {
 //    use super::*;
@@ -24,6 +52,7 @@ mod tests {
         use std::convert::From;
 
         #[derive(Debug)]
+        #[allow(dead_code)]
         struct Number {
             value: i32,
         }
@@ -49,12 +78,14 @@ mod tests {
 
     // ToString和FromStr
     #[test]
+    #[allow(non_snake_case)]
     fn test_ToString_and_FromStr() {
         // 要把任何类型转换成 String，只需要实现那个类型的 ToString trait。
         struct Circle {
             radius: i32
         }
 
+        #[allow(clippy::to_string_trait_impl)]
         impl std::string::ToString for Circle {
             fn to_string(&self) -> String {
                 format!("Circle of radius {:?}", self.radius)
@@ -87,4 +118,4 @@ mod tests {
         let parsed_circle: Circle = "Circle of radius 1025".parse().unwrap();
         println!("{}", parsed_circle.radius);
     }
-}
\ No newline at end of file
+}
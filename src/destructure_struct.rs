@@ -1,8 +1,31 @@
 //解构 struct
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "destructure_struct", about: "解构结构体的字段", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    struct Point {
+        x: (u32, u32),
+        y: u32,
+    }
+
+    let point = Point { x: (1, 2), y: 3 };
+    // 字段与变量同名
+    let Point { x: (a, b), y } = point;
+    writeln!(out, "a = {}, b = {}, y = {}", a, b, y)?;
+    // 字段与变量不同名，并忽略其余字段
+    let Point { y: i, .. } = point;
+    writeln!(out, "i = {}", i)
+}
+
 #[cfg(test)]
 mod tests {
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct S1 {
         field1: i32,
         field2: String,
@@ -35,4 +58,4 @@ mod tests {
         // let S1 { .. , filed4 } = s1; 会报错
 
     }
-}
\ No newline at end of file
+}
@@ -4,26 +4,42 @@
 //Display: 3.3 + 7.2i
 //Debug: Complex { real: 3.3, imag: 7.2 }
 
-#[cfg(test)]
-mod tests {
-    use std::fmt::{Display, Formatter, Error};
+use std::fmt::{Display, Formatter, Error};
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    #[derive(Debug)]
-    struct Complex {
-        real: f32,
-        imag: f32,
-    }
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "display", about: "为Complex实现Display，并与Debug比较", run: demo },
+];
 
-    impl Display for Complex {
-        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-            write!(f, "{} + {}i", self.real, self.imag)
-        }
+/// 复数
+#[derive(Debug)]
+pub struct Complex {
+    pub real: f32,
+    pub imag: f32,
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{} + {}i", self.real, self.imag)
     }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let c = Complex { real: 3.3, imag: 7.2 };
+    writeln!(out, "Display: {}", c)?;
+    writeln!(out, "Debug: {:?}", c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Complex;
+    use crate::snapshot::assert_snapshot;
 
     #[test]
     fn test_display() {
         let c = Complex { real: 3.3, imag: 7.2 };
-        println!("{}", c);
-        println!("{:?}", c);
+        let output = format!("Display: {}\nDebug: {:?}\n", c, c);
+        assert_snapshot("display", &output);
     }
 }
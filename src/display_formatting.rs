@@ -8,57 +8,73 @@
 //You may need to list each color more than once,
 //You can pad with zeros to a width of 2 with :02.
 
-#[cfg(test)]
-mod tests {
-    use std::fmt::{Display, Formatter, Error};
+use std::fmt::{Display, Formatter, Error};
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+/// 城市名及其经纬度
+pub struct City {
+    pub name: &'static str,
+    pub lat: f32,
+    pub lon: f32,
+}
 
-    struct City {
-        name: &'static str,
-        lat: f32,
-        lon: f32,
+impl Display for City {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
+        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+
+        write!(f, "{}:{:.3}°{} {:.3}°{}",
+               self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
     }
+}
 
-    impl Display for City {
-        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-            let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
-            let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+/// RGB颜色
+#[derive(Debug)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
 
-            write!(f, "{}:{:.3}°{} {:.3}°{}",
-                   self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
-        }
+// attention!!!!!
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "RGB ({0}, {1}, {2}) 0x{0:02X}{1:02X}{2:02X}", self.red, self.green, self.blue)
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "display_formatting", about: "为City和Color实现Display", run: demo },
+];
 
-    #[derive(Debug)]
-    struct Color {
-        red: u8,
-        green: u8,
-        blue: u8,
+#[allow(clippy::excessive_precision)]
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    for city in [
+        City { name: "Dublin", lat: 53.347778, lon: -6.259722 },
+        City { name: "Oslo", lat: 59.95, lon: 10.75 },
+        City { name: "Vancouver", lat: 49.25, lon: -123.1 },
+    ].iter() {
+        writeln!(out, "{}", *city)?;
     }
 
-    // attention!!!!!
-    impl Display for Color {
-        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-            write!(f, "RGB ({0}, {1}, {2}) 0x{0:02X}{1:02X}{2:02X}", self.red, self.green, self.blue)
-        }
+    for color in [
+        Color { red: 128, green: 255, blue: 90 },
+        Color { red: 0, green: 3, blue: 254 },
+        Color { red: 0, green: 0, blue: 0 },
+    ].iter() {
+//        writeln!(out, "{:?}", *color)?;
+        writeln!(out, "{}", *color)?;
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::snapshot::assert_demo_snapshot;
 
     #[test]
     fn test_display_formatting() {
-        for city in [
-            City { name: "Dublin", lat: 53.347778, lon: -6.259722 },
-            City { name: "Oslo", lat: 59.95, lon: 10.75 },
-            City { name: "Vancouver", lat: 49.25, lon: -123.1 },
-        ].iter() {
-            println!("{}", *city);
-        }
-
-        for color in [
-            Color { red: 128, green: 255, blue: 90 },
-            Color { red: 0, green: 3, blue: 254 },
-            Color { red: 0, green: 0, blue: 0 },
-        ].iter() {
-//        println!("{:?}", *color);
-            println!("{}", *color);
-        }
+        assert_demo_snapshot("display_formatting", super::demo);
     }
 }
@@ -2,29 +2,42 @@
 //
 //[0: 1, 1: 2, 2: 3]
 
-#[cfg(test)]
-mod tests{
-    use std::fmt::{Display, Formatter, Error};
+use std::fmt::{Display, Formatter, Error};
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    struct List(Vec<i32>);
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "display_vec", about: "打印Vec时带上每个元素的下标", run: demo },
+];
 
-    impl Display for List {
-        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-            write!(f, "[")?;
+/// 打印时带上下标的Vec
+pub struct List(pub Vec<i32>);
 
-            for (count, v) in self.0.iter().enumerate() {
-                if count != 0 {
-                    write!(f, ",")?;
-                }
-                write!(f, "{}:{}", count, v)?;
+impl Display for List {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "[")?;
+        for (count, v) in self.0.iter().enumerate() {
+            if count != 0 {
+                write!(f, ",")?;
             }
-            write!(f, "]")
+            write!(f, "{}:{}", count, v)?;
         }
+        write!(f, "]")
     }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}", List(vec![1, 2, 3]))
+}
+
+#[cfg(test)]
+mod tests{
+    use super::List;
+    use crate::snapshot::assert_snapshot;
 
     #[test]
     fn test_display_vec(){
         let l = List(vec![10, 20, 30, 40, 50]);
-        println!("{}", l);
+        assert_snapshot("display_vec", &format!("{}\n", l));
     }
-}
\ No newline at end of file
+}
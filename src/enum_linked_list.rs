@@ -1,72 +1,400 @@
 //A common use for enums is to create a linked-list:
 
-#[cfg(test)]
-mod tests {
-    use List::*;
+use std::fmt::{self, Debug, Display, Write as _};
+use std::io::{self, Write};
+use std::iter::FromIterator;
+use std::mem;
+use crate::runner::Demo;
+use Link::*;
+
+// 链表的节点。原先的`List`本身就是这个enum，
+// 现在外面包了一层结构体，方便实现push_front/pop_front等需要`&mut self`修改表头的方法
+enum Link<T> {
+    // Cons: Tuple struct that wraps an element and a pointer to the next node
+    Cons(T, Box<Link<T>>),
+    // Nil: A node that signifies the end of the linked list
+    Nil,
+}
+
+/// 用enum实现的单向链表
+pub struct List<T> {
+    head: Link<T>,
+}
+
+// Methods can be attached to an enum (and to the struct wrapping it)
+impl<T> List<T> {
+    /// Create an empty list
+    pub fn new() -> List<T> {
+        // `Nil` has type `Link<T>`
+        List { head: Nil }
+    }
+
+    /// Consume a list, and return the same list with a new element at its front
+    pub fn prepend(mut self, elem: T) -> List<T> {
+        self.push_front(elem);
+        self
+    }
+
+    /// 在表头插入一个元素
+    pub fn push_front(&mut self, elem: T) {
+        // 先用Nil把旧的表头换出来，再把它接到新节点后面
+        let tail = mem::replace(&mut self.head, Nil);
+        // `Cons` also has type `Link<T>`
+        self.head = Cons(elem, Box::new(tail));
+    }
+
+    /// 取出表头的元素，空表返回None
+    pub fn pop_front(&mut self) -> Option<T> {
+        match mem::replace(&mut self.head, Nil) {
+            Cons(elem, tail) => {
+                self.head = *tail;
+                Some(elem)
+            }
+            Nil => None,
+        }
+    }
+
+    /// 表头元素的引用
+    pub fn peek(&self) -> Option<&T> {
+        match self.head {
+            Cons(ref elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    /// 表头元素的可变引用
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        match self.head {
+            Cons(ref mut elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    /// Return the length of the list
+    pub fn len(&self) -> usize {
+        // 递归的写法（`Cons(_, ref tail) => 1 + tail.len()`）每个节点占一层栈，
+        // 几十万个元素就会栈溢出，所以这里改为沿着链表循环计数
+        self.iter().count()
+    }
+
+    /// Return true if the list has no element
+    pub fn is_empty(&self) -> bool {
+        matches!(self.head, Nil)
+    }
+
+    /// 原地反转链表：逐个把节点摘下来接到新表头上，不分配新节点
+    pub fn reverse(&mut self) {
+        let mut reversed = Nil;
+        let mut current = mem::replace(&mut self.head, Nil);
+        while let Cons(elem, mut next) = current {
+            current = mem::replace(&mut *next, reversed);
+            reversed = Cons(elem, next);
+        }
+        self.head = reversed;
+    }
+
+    /// 从表头到表尾的迭代器
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: &self.head }
+    }
 
-    enum List {
-        // Cons: Tuple struct that wraps an element and a pointer to the next node
-        Cons(u32, Box<List>),
-        // Nil: A node that signifies the end of the linked list
-        Nil,
+    /// 从表头到表尾、可以修改元素的迭代器
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: Some(&mut self.head) }
     }
 
-    // Methods can be attached to an enum
-    impl List {
-        // Create an empty list
-        fn new() -> List {
-            // `Nil` has type `List`
-            Nil
+    // 表尾的Nil，在这里写入就是在表尾追加
+    fn tail_mut(&mut self) -> &mut Link<T> {
+        let mut link = &mut self.head;
+        while let Cons(_, next) = link {
+            link = next;
         }
+        link
+    }
+}
+
+impl<T: Display> List<T> {
+    /// Return representation of the list as a (heap allocated) string
+    pub fn stringify(&self) -> String {
+        // 与len一样用循环代替递归，结果形如"2,1,0,Nil"
+        let mut out = String::new();
+        for elem in self.iter() {
+            // 写入String不会失败
+            write!(out, "{},", elem).unwrap();
+        }
+        out.push_str("Nil");
+        out
+    }
+}
 
-        // Consume a list, and return the same list with a new element at its front
-        fn prepend(self, elem: u32) -> List {
-            // `Cons` also has type List
-            Cons(elem, Box::new(self))
+// 默认的drop会先drop表头节点，再由它drop下一个节点……同样每个节点占一层栈。
+// 这里把节点逐个摘下来，每次只drop一个不再有后继的节点
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut current = mem::replace(&mut self.head, Nil);
+        while let Cons(_, mut next) = current {
+            current = mem::replace(&mut *next, Nil);
         }
+    }
+}
 
-        // Return the length of the list
-        fn len(&self) -> u32 {
-            // `self` has to be matched, because the behavior of this method depends on the variant of `self`
-            // `self` has type `&List`, and `*self` has type `List`, matching on a
-            // concrete type `T` is preferred over a match on a reference `&T`
-            match *self {
-                // Can't take ownership of the tail, because `self` is borrowed;
-                // instead take a reference to the tail
-                Cons(_, ref tail) => 1 + tail.len(),
-                // Base Case: An empty list has zero length
-                Nil => 0
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+/// List::iter返回的迭代器
+pub struct Iter<'a, T> {
+    next: &'a Link<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            Cons(elem, tail) => {
+                self.next = tail;
+                Some(elem)
             }
+            Nil => None,
         }
+    }
+}
 
-        // Return representation of the list as a (heap allocated) string
-        fn stringify(&self) -> String {
-            match *self {
-                Cons(head, ref tail) => {
-                    // `format!` is similar to `print!`, but returns a heap allocated string instead of printing to the console
-                    format!("{},{}", head, tail.stringify())
-                }
-                Nil => {
-                    format!("Nil")
-                }
+/// List::iter_mut返回的迭代器
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Link<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 可变引用不能复制，先take出来，再把指向下一个节点的引用放回去
+        match self.next.take()? {
+            Cons(elem, tail) => {
+                self.next = Some(tail);
+                Some(elem)
             }
+            Nil => None,
+        }
+    }
+}
+
+/// List::into_iter返回的迭代器，按顺序取出元素
+pub struct IntoIter<T>(List<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// 在表尾依次追加元素，与Vec::extend的顺序一致
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        let mut tail = self.tail_mut();
+        for elem in iter {
+            *tail = Cons(elem, Box::new(Nil));
+            tail = match tail {
+                Cons(_, next) => next,
+                Nil => unreachable!(),
+            };
         }
     }
+}
+
+/// 收集得到的链表保持迭代器的顺序：第一个元素在表头
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// 从Vec批量构建：从最后一个元素开始push_front，每个元素只分配一次节点
+impl<T> From<Vec<T>> for List<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut list = List::new();
+        for elem in vec.into_iter().rev() {
+            list.push_front(elem);
+        }
+        list
+    }
+}
+
+impl<T: Debug> Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "enum_linked_list", about: "用enum实现链表", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // Create an empty linked list
+    let mut list = List::new();
+    // Prepend some elements
+    list = list.prepend(0);
+    list = list.prepend(1);
+    list = list.prepend(2);
+
+    // Show the final state of the list
+    writeln!(out, "{}", list.len())?;
+    writeln!(out, "{}", list.stringify())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::List;
 
     #[test]
     fn test_enum_linked_list() {
-        // Create an empty linked list
+        let mut out = Vec::new();
+        super::demo(&mut out).unwrap();
+        print!("{}", String::from_utf8(out).unwrap());
+
+        let list = List::new().prepend(0).prepend(1).prepend(2);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.stringify(), "2,1,0,Nil");
+    }
+
+    #[test]
+    fn test_push_pop_peek() {
         let mut list = List::new();
-        // Prepend some elements
-        list = list.prepend(0);
-        list = list.prepend(1);
-        list = list.prepend(2);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.peek(), None);
+        assert!(list.is_empty());
 
-        // Show the final state of the list
-        println!("{}", list.len());
-        println!("{}", list.stringify());
+        list.push_front(1);
+        list.push_front(2);
+        assert_eq!(list.peek(), Some(&2));
+        if let Some(head) = list.peek_mut() {
+            *head *= 10;
+        }
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(20));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
     }
-}
 
+    #[test]
+    fn test_iterators() {
+        let mut list: List<i32> = (1..=4).collect();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
 
+        for elem in list.iter_mut() {
+            *elem *= 2;
+        }
+        for elem in &mut list {
+            *elem += 1;
+        }
+        assert_eq!((&list).into_iter().sum::<i32>(), 3 + 5 + 7 + 9);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 5, 7, 9]);
+    }
 
+    #[test]
+    fn test_extend_and_traits() {
+        let mut list: List<String> = vec!["a".to_string()].into_iter().collect();
+        list.extend(vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(format!("{:?}", list), r#"["a", "b", "c"]"#);
+
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        list.push_front("z".to_string());
+        assert_ne!(copy, list);
+        assert_eq!(format!("{:?}", List::<u8>::new()), "[]");
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list: List<i32> = (1..=5).collect();
+        list.reverse();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+        assert_eq!(list.stringify(), "5,4,3,2,1,Nil");
+
+        let mut empty: List<i32> = List::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+        let mut one: List<i32> = Some(7).into_iter().collect();
+        one.reverse();
+        assert_eq!(one.peek(), Some(&7));
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let list = List::from(vec![1, 2, 3]);
+        assert_eq!(list.stringify(), "1,2,3,Nil");
+        assert_eq!(list, (1..=3).collect());
+        assert!(List::<u8>::from(vec![]).is_empty());
+    }
+
+    // 回归测试：len、stringify、clone、比较和drop都不能随长度递归
+    #[test]
+    fn test_million_elements_do_not_overflow_the_stack() {
+        const N: usize = 1_000_000;
+        let mut list: List<usize> = List::from((0..N).collect::<Vec<_>>());
+        assert_eq!(list.len(), N);
+        assert!(list.stringify().ends_with("999999,Nil"));
+
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        drop(copy);
+
+        list.reverse();
+        assert_eq!(list.peek(), Some(&(N - 1)));
+        let mut prepended = (0..N).fold(List::new(), |list, i| list.prepend(i));
+        assert_eq!(prepended.pop_front(), Some(N - 1));
+        drop(prepended);
+        drop(list);
+    }
+}
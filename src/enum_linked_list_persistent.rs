@@ -0,0 +1,234 @@
+/*
+    持久化链表PersistentList
+
+    enum_linked_list中的List用Box指向下一个节点，每个节点只有一个owner，
+    prepend会把整个旧链表move进新链表。
+
+    借助Rc（见std_library_types/rc.rs），节点可以有多个owner：
+    prepend不再消耗旧链表，而是返回一个新链表，新表头之后的部分与旧链表`共享`。
+
+        a = 3 -> 2 -> 1
+        b = a.prepend(4)    b = 4 -> (3 -> 2 -> 1)   括号中的节点与a共享
+        c = a.tail()        c =      (2 -> 1)        与a、b共享
+
+    节点一旦创建就不能修改，所以任何一个版本都不会被其他版本的操作影响。
+*/
+use std::fmt::{self, Debug};
+use std::io::{self, Write};
+use std::iter::FromIterator;
+use std::rc::Rc;
+use crate::runner::Demo;
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+/// 不可变的单向链表，不同版本之间通过Rc共享表尾
+pub struct PersistentList<T> {
+    head: Link<T>,
+}
+
+impl<T> PersistentList<T> {
+    /// 空链表
+    pub fn new() -> Self {
+        PersistentList { head: None }
+    }
+
+    /// 返回在表头加上elem的新链表，self保持不变，两者共享原来的节点
+    pub fn prepend(&self, elem: T) -> Self {
+        PersistentList { head: Some(Rc::new(Node { elem, next: self.head.clone() })) }
+    }
+
+    /// 表头元素
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// 去掉表头之后的链表（与self共享节点），空链表的tail仍是空链表
+    pub fn tail(&self) -> Self {
+        PersistentList { head: self.head.as_ref().and_then(|node| node.next.clone()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// 从表头到表尾的迭代器
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+}
+
+// 与List一样，默认的drop会沿着链表递归。
+// 这里逐个释放只被当前链表持有的节点；遇到与其他版本共享的节点就停下，
+// 剩下的部分交给仍持有它的版本去释放
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => head = node.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        PersistentList::new()
+    }
+}
+
+/// clone只复制表头的Rc，O(1)，新旧链表共享全部节点
+impl<T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        PersistentList { head: self.head.clone() }
+    }
+}
+
+/// PersistentList::iter返回的迭代器
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// 收集得到的链表保持迭代器的顺序：第一个元素在表头
+impl<T> FromIterator<T> for PersistentList<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let elems: Vec<T> = iter.into_iter().collect();
+        let mut head = None;
+        for elem in elems.into_iter().rev() {
+            head = Some(Rc::new(Node { elem, next: head }));
+        }
+        PersistentList { head }
+    }
+}
+
+/// 结构相等：逐个比较元素，而不是比较指针
+impl<T: PartialEq> PartialEq for PersistentList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for PersistentList<T> {}
+
+impl<T: Debug> Debug for PersistentList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "enum_linked_list_persistent", about: "prepend与tail返回共享表尾的新版本", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let a: PersistentList<i32> = PersistentList::new().prepend(1).prepend(2).prepend(3);
+    let b = a.prepend(4);
+    let c = a.tail();
+    // 每个版本都不受其他版本的影响
+    writeln!(out, "a = {:?}", a)?;
+    writeln!(out, "b = {:?}", b)?;
+    writeln!(out, "c = {:?}", c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 表头节点的强引用计数
+    fn head_count<T>(list: &PersistentList<T>) -> usize {
+        list.head.as_ref().map_or(0, Rc::strong_count)
+    }
+
+    #[test]
+    fn test_prepend_head_tail() {
+        let empty: PersistentList<i32> = PersistentList::new();
+        assert_eq!(empty.head(), None);
+        assert!(empty.tail().is_empty());
+
+        let a = empty.prepend(1).prepend(2).prepend(3);
+        let b = a.prepend(4);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(b.head(), Some(&4));
+        assert_eq!(b.tail(), a);
+        assert_eq!(a.tail().tail().tail(), empty);
+        assert_eq!(format!("{:?}", b), "[4, 3, 2, 1]");
+    }
+
+    #[test]
+    fn test_versions_share_nodes() {
+        let a: PersistentList<String> = vec!["x".to_string(), "y".to_string()].into_iter().collect();
+        assert_eq!(head_count(&a), 1);
+
+        // b和c的第二个节点就是a的表头
+        let b = a.prepend("b".to_string());
+        let c = a.prepend("c".to_string());
+        assert_eq!(head_count(&a), 3);
+        assert!(Rc::ptr_eq(b.tail().head.as_ref().unwrap(), a.head.as_ref().unwrap()));
+
+        // tail和clone只增加计数，不复制节点
+        let tail = a.tail();
+        assert_eq!(head_count(&tail), 2);
+        let copy = a.clone();
+        assert_eq!(head_count(&a), 4);
+
+        drop(b);
+        drop(copy);
+        assert_eq!(head_count(&a), 2);
+        drop(a);
+        // a释放后，c仍然完整
+        assert_eq!(c.iter().map(String::as_str).collect::<Vec<_>>(), vec!["c", "x", "y"]);
+        assert_eq!(head_count(&c.tail()), 2);
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        let a: PersistentList<i32> = (1..=3).collect();
+        let b = PersistentList::new().prepend(3).prepend(2).prepend(1);
+        // 节点不同，但元素相同
+        assert!(!Rc::ptr_eq(a.head.as_ref().unwrap(), b.head.as_ref().unwrap()));
+        assert_eq!(a, b);
+        assert_ne!(a, b.tail());
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn test_long_chain_drop_does_not_overflow_the_stack() {
+        let long: PersistentList<u32> = (0..1_000_000).collect();
+        let shared = long.tail();
+        // 只有表头被long独占，drop long时遇到共享节点就停止
+        drop(long);
+        assert_eq!(shared.len(), 999_999);
+        drop(shared);
+    }
+}
@@ -3,48 +3,62 @@
 // `PageLoad != PageUnload` and `KeyPress(char) != Paste(String)`.
 // Each is different and independent.
 
-#[cfg(test)]
-mod tests {
-    use std::borrow::ToOwned;
-
-    enum WebEvent {
-        // An `enum` may either be `unit-like`,
-        PageLoad,
-        PageUnload,
-        // like tuple structs,
-        KeyPress(char),
-        Paste(String),
-        // or c-like structures.
-        Click { x: i64, y: i64 },
-    }
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+/// 网页事件
+pub enum WebEvent {
+    // An `enum` may either be `unit-like`,
+    PageLoad,
+    PageUnload,
+    // like tuple structs,
+    KeyPress(char),
+    Paste(String),
+    // or c-like structures.
+    Click { x: i64, y: i64 },
+}
 
-    // A function which takes a `WebEvent` enum as an argument and returns nothing.
-    fn inspect(event: WebEvent) {
-        match event {
-            WebEvent::PageLoad => println!("page loaded"),
-            WebEvent::PageUnload => println!("page unloaded"),
-            // Destructure `c` from inside the `enum`.
-            WebEvent::KeyPress(c) => println!("pressed '{}'.", c),
-            WebEvent::Paste(s) => println!("pasted \"{}\".", s),
-            // Destructure `Click` into `x` and `y`.
-            WebEvent::Click { x, y } => {
-                println!("clicked at x={}, y={}.", x, y);
-            }
+// A function which takes a `WebEvent` enum as an argument and writes a description of it.
+/// 把事件的描述写进out
+pub fn inspect(event: WebEvent, out: &mut dyn Write) -> io::Result<()> {
+    match event {
+        WebEvent::PageLoad => writeln!(out, "page loaded"),
+        WebEvent::PageUnload => writeln!(out, "page unloaded"),
+        // Destructure `c` from inside the `enum`.
+        WebEvent::KeyPress(c) => writeln!(out, "pressed '{}'.", c),
+        WebEvent::Paste(s) => writeln!(out, "pasted \"{}\".", s),
+        // Destructure `Click` into `x` and `y`.
+        WebEvent::Click { x, y } => {
+            writeln!(out, "clicked at x={}, y={}.", x, y)
         }
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "enums", about: "用enum对网页事件分类", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let pressed = WebEvent::KeyPress('w');
+    let paste = WebEvent::Paste("michael,w".to_owned());
+    let click = WebEvent::Click { x: 1024, y: 2048 };
+    let (page_load, page_unload) = (WebEvent::PageLoad, WebEvent::PageUnload);
+
+    inspect(page_load, out)?;
+    inspect(page_unload, out)?;
+    inspect(pressed, out)?;
+    inspect(paste, out)?;
+    inspect(click, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use crate::snapshot::{assert_demo_snapshot, assert_snapshot, capture};
 
     #[test]
     fn test_enums() {
-        let pressed = WebEvent::KeyPress('w');
-        let paste = WebEvent::Paste("michael,w".to_owned());
-        let click = WebEvent::Click { x: 1024, y: 2048 };
-        let (page_load, page_unload) = (WebEvent::PageLoad, WebEvent::PageUnload);
-
-        inspect(page_load);
-        inspect(page_unload);
-        inspect(pressed);
-        inspect(paste);
-        inspect(click);
+        assert_demo_snapshot("enums", super::demo);
     }
 
     enum VeryVerboseEnumOfThingsToDoWithNumbers {
@@ -84,12 +98,11 @@ mod tests {
         Blue = 0x0000ff,
     }
 
-    #[test]
-    fn test_type_aliases() {
+    fn type_aliases(out: &mut dyn Write) -> io::Result<()> {
         // We can refer to each variant via its alias, not its long and inconvenient name
         let x = V::Subtract;
 
-        println!("{}", x.run(10, 5));
+        writeln!(out, "{}", x.run(10, 5))?;
 
         // Explicitly `use` each name so they are available without manual scoping.
         use VeryVerboseEnumOfThingsToDoWithNumbers::{Add, Subtract};
@@ -101,12 +114,17 @@ mod tests {
         let _y = Multiply;
 
         // `enums` can be cast as integers.
-        println!("{:?}", Number::Zero as i32);  // 0
-        println!("{:?}", Number::One as i32);   // 1
-        println!("{:?}", Number::Two as i32);   // 2
-        println!("{:06x}", Color::Red as i32);  // ff0000
-        println!("{:06x}", Color::Blue as i32); // 0000ff
-        println!("{:06x}", Color::Green as i32);// 00ff00
+        writeln!(out, "{:?}", Number::Zero as i32)?;  // 0
+        writeln!(out, "{:?}", Number::One as i32)?;   // 1
+        writeln!(out, "{:?}", Number::Two as i32)?;   // 2
+        writeln!(out, "{:06x}", Color::Red as i32)?;  // ff0000
+        writeln!(out, "{:06x}", Color::Blue as i32)?; // 0000ff
+        writeln!(out, "{:06x}", Color::Green as i32)?;// 00ff00
+        Ok(())
     }
-}
 
+    #[test]
+    fn test_type_aliases() {
+        assert_snapshot("enums::type_aliases", &capture(type_aliases));
+    }
+}
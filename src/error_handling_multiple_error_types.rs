@@ -9,6 +9,30 @@
     Vec::first 返回一个 Option，而 parse::<i32> 返回一个 Result<i32, ParseIntError>：
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_multiple_error_types", about: "Option和Result两种错误混在一起", run: demo },
+];
+
+// first()返回Option，parse()返回Result，两种失败要分别处理
+fn double_first(vec: &[&str]) -> String {
+    match vec.first() {
+        None => "the vec is empty".to_string(),
+        Some(first) => match first.parse::<i32>() {
+            Ok(n) => format!("the first doubled is {}", 2 * n),
+            Err(e) => format!("cannot parse {:?}: {}", first, e),
+        },
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}", double_first(&["42", "93", "18"]))?;
+    writeln!(out, "{}", double_first(&["tofu", "93", "18"]))?;
+    writeln!(out, "{}", double_first(&[]))
+}
+
 #[cfg(test)]
 mod tests {
     fn double_first(vec: Vec<&str>) -> i32 {
@@ -30,4 +54,4 @@ mod tests {
         // 编译通过，但运行报错。因为vec.first().unwrap()返回None
         println!("{}", double_first(empty));
     }
-}
\ No newline at end of file
+}
@@ -8,68 +8,85 @@
     标准库的 Box 通过 From 为它们提供了到 Box<Error> 的转换。
 */
 
-mod tests {
-    use std::fmt::Formatter;
+use std::fmt::Formatter;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    // 为 `Box<error::Error>` 取别名
-    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+// 为 `Box<error::Error>` 取别名
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-    #[derive(Debug)]
-    struct EmptyVec;
+#[derive(Debug)]
+struct EmptyVec;
 
-    impl std::fmt::Display for EmptyVec {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            write!(f, "invalid first item to double")
-        }
+impl std::fmt::Display for EmptyVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid first item to double")
     }
+}
 
-    impl std::error::Error for EmptyVec {}
-
-    fn double_first(vec: Vec<&str>) -> Result<i32> {
-        // first()返回的是一个Option
-        vec.first().
-            // 将错误类型EmptyVec装箱.ok_or_else返回的是一个Result
-            ok_or_else(|| EmptyVec.into())
-            // 开始parse&str
-            .and_then(|num_str| num_str.parse::<i32>()
-                // Result中的错误装箱
-                .map_err(|e| e.into())
-                .map(|num| num * 2))
-    }
+impl std::error::Error for EmptyVec {}
+
+fn double_first(vec: Vec<&str>) -> Result<i32> {
+    // first()返回的是一个Option
+    vec.first().
+        // 将错误类型EmptyVec装箱.ok_or_else返回的是一个Result
+        ok_or_else(|| EmptyVec.into())
+        // 开始parse&str
+        .and_then(|num_str| num_str.parse::<i32>()
+            // Result中的错误装箱
+            .map_err(|e| e.into())
+            .map(|num| num * 2))
+}
 
-    /*
-    ok_or_else是Option的一个类方法：
-    pub fn ok_or_else<E, F: FnOnce() -> E>(self, err: F) -> Result<T, E> {
-        match self {
-            Some(v) => Ok(v),
-            None => Err(err()),
-        }
+/*
+ok_or_else是Option的一个类方法：
+pub fn ok_or_else<E, F: FnOnce() -> E>(self, err: F) -> Result<T, E> {
+    match self {
+        Some(v) => Ok(v),
+        None => Err(err()),
     }
+}
 
-    功能：
-        如果Option为Some，则返回对应的Ok(转成Result)
-        如果Option为None，则返回闭包err生成的Err
-    */
+功能：
+    如果Option为Some，则返回对应的Ok(转成Result)
+    如果Option为None，则返回闭包err生成的Err
+*/
 
-    fn print_result(result: Result<i32>) {
-        match result {
-            Ok(i) => println!("{}", i),
-            Err(e) => println!("Box Error: {}", e),
-        }
+fn print_result(out: &mut dyn Write, result: Result<i32>) -> io::Result<()> {
+    match result {
+        Ok(i) => writeln!(out, "{}", i),
+        Err(e) => writeln!(out, "Box Error: {}", e),
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_multiple_error_types_boxing_errors", about: "把不同的错误装箱成Box<dyn Error>", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    print_result(out, double_first(vec!["42", "93", "18"]))?;
+    print_result(out, double_first(vec!["tofu", "93", "18"]))?;
+    print_result(out, double_first(vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_multiple_error_types_boxing_errors() {
+        let mut out = Vec::new();
         let numbers = vec!["10", "20", "30"];
-        print_result(double_first(numbers));
+        print_result(&mut out, double_first(numbers)).unwrap();
         // 输出：20
 
         let strings = vec!["michael.w", "20", "30"];
-        print_result(double_first(strings));
+        print_result(&mut out, double_first(strings)).unwrap();
         // 输出：Box Error: invalid digit found in string
 
         let empty = Vec::new();
-        print_result(double_first(empty));
-        // 输出：Box Error: invalid digit found in string
+        print_result(&mut out, double_first(empty)).unwrap();
+        // 输出：Box Error: invalid first item to double
+        assert_eq!(String::from_utf8(out).unwrap(), "20\nBox Error: invalid digit found in string\nBox Error: invalid first item to double\n");
     }
-}
\ No newline at end of file
+}
@@ -2,6 +2,34 @@
     遍历Result
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_multiple_error_types_iterating_over_results", about: "遍历Result：filter_map、collect与partition", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let strings = ["1024", "michael.w", "2048"];
+
+    // filter_map忽略失败的项
+    let numbers: Vec<i32> = strings.iter().filter_map(|s| s.parse().ok()).collect();
+    writeln!(out, "{:?}", numbers)?;
+
+    // collect使整个操作失败
+    let numbers: Result<Vec<i32>, _> = strings.iter().map(|s| s.parse::<i32>()).collect();
+    writeln!(out, "{:?}", numbers)?;
+
+    // partition收集所有合法的值与错误
+    let (numbers, errors): (Vec<_>, Vec<_>) = strings
+        .iter()
+        .map(|s| s.parse::<i32>())
+        .partition(Result::is_ok);
+    let numbers: Vec<i32> = numbers.into_iter().map(Result::unwrap).collect();
+    let errors: Vec<_> = errors.into_iter().map(Result::unwrap_err).collect();
+    writeln!(out, "{:?}\n{:?}", numbers, errors)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -92,4 +120,4 @@ mod tests {
         //          [1024, 2048]
         //          [ParseIntError { kind: InvalidDigit }]
     }
-}
\ No newline at end of file
+}
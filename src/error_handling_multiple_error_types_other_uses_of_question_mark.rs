@@ -14,58 +14,74 @@
 
     这里，使用 ? 重写之前的例子。这样，只要为我们的错误类型实现 From::from，就可以不再使用 map_err。
 */
-#[cfg(test)]
-mod tests {
-    use std::fmt::Formatter;
+use std::fmt::Formatter;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    // 为 `Box<error::Error>` 取别名。
-    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+// 为 `Box<error::Error>` 取别名。
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-    #[derive(Debug)]
-    struct EmptyVec;
+#[derive(Debug)]
+struct EmptyVec;
 
-    impl std::fmt::Display for EmptyVec {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            write!(f, "invalid first item to double")
-        }
+impl std::fmt::Display for EmptyVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid first item to double")
     }
+}
 
-    impl std::error::Error for EmptyVec {}
+impl std::error::Error for EmptyVec {}
 
-    // 这里的结构和之前一样，但是这次没有把所有的 `Results` 和 `Options` 串起来，
-    // 而是使用 `?` 立即得到内部值。
-    fn double_first(vec: Vec<&str>) -> Result<i32> {
-        // first 为 &&str
-        let first_str = vec.first().ok_or(EmptyVec)?;
-        let parsed_num = first_str.parse::<i32>()?;
+// 这里的结构和之前一样，但是这次没有把所有的 `Results` 和 `Options` 串起来，
+// 而是使用 `?` 立即得到内部值。
+fn double_first(vec: Vec<&str>) -> Result<i32> {
+    // first 为 &&str
+    let first_str = vec.first().ok_or(EmptyVec)?;
+    let parsed_num = first_str.parse::<i32>()?;
 
-        // 扩大二倍并返回
-        Ok(parsed_num * 2)
-    }
+    // 扩大二倍并返回
+    Ok(parsed_num * 2)
+}
 
-    fn print_result(result: Result<i32>) {
-        match result {
-            Ok(i) => println!("{}", i),
-            Err(e) => println!("Box Error: {}", e),
-        }
+fn print_result(out: &mut dyn Write, result: Result<i32>) -> io::Result<()> {
+    match result {
+        Ok(i) => writeln!(out, "{}", i),
+        Err(e) => writeln!(out, "Box Error: {}", e),
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_multiple_error_types_other_uses_of_question_mark", about: "用?把错误自动装箱", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    print_result(out, double_first(vec!["42", "93", "18"]))?;
+    print_result(out, double_first(vec!["tofu", "93", "18"]))?;
+    print_result(out, double_first(vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_multiple_error_types_other_uses_of_question_mark() {
+        let mut out = Vec::new();
         let numbers = vec!["10", "20", "30"];
-        print_result(double_first(numbers));
+        print_result(&mut out, double_first(numbers)).unwrap();
         // 输出:20
 
         let strings = vec!["michael.w", "20", "30"];
-        print_result(double_first(strings));
+        print_result(&mut out, double_first(strings)).unwrap();
         // 输出:Box Error: invalid digit found in string
 
         let empty = Vec::new();
-        print_result(double_first(empty))
+        print_result(&mut out, double_first(empty)).unwrap();
         // 输出:Box Error: invalid first item to double
+        assert_eq!(String::from_utf8(out).unwrap(), "20\nBox Error: invalid digit found in string\nBox Error: invalid first item to double\n");
 
         // 注：
         //   这段代码现在已经很清晰了。相比原始的 panic，它就像是把所有的 unwrap 调用都换成 ? 一样。
         //   与 panic 相比，这样做的区别在于返回类型是 Result，因而必须在顶层解构它们。
     }
-}
\ No newline at end of file
+}
@@ -1,19 +1,36 @@
 /*
     处理混合错误类型的最基本的手段就是让它们互相包含。
 */
-#[cfg(test)]
-mod tests {
-    use std::num::ParseIntError;
+use std::num::ParseIntError;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    fn double_first(vec: Vec<&str>) -> Option<Result<i32, ParseIntError>> {
-        // vec.first()返回值是Option，map只是在该Option里面更新值
-        vec.first().map(|num_str| {
-            // Option中将&str换成: num_str.parse::<i32>().map(|num| num * 2)
-            num_str.parse::<i32>().map(|num| num * 2)
-            // num_str.parse::<i32>()的返回值是Result<i32,Err>
-            // map只是在该Result中将原来的i32值扩大二倍
-        })
+fn double_first(vec: Vec<&str>) -> Option<Result<i32, ParseIntError>> {
+    // vec.first()返回值是Option，map只是在该Option里面更新值
+    vec.first().map(|num_str| {
+        // Option中将&str换成: num_str.parse::<i32>().map(|num| num * 2)
+        num_str.parse::<i32>().map(|num| num * 2)
+        // num_str.parse::<i32>()的返回值是Result<i32,Err>
+        // map只是在该Result中将原来的i32值扩大二倍
+    })
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_multiple_error_types_pull_result_out_of_option", about: "在Option<Result>和Result<Option>之间转换", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    for vec in [vec!["10", "20"], vec!["tofu", "20"], vec![]] {
+        let result = double_first(vec);
+        // transpose交换Option和Result的位置
+        writeln!(out, "{:?} -> {:?}", result, result.clone().transpose())?;
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_multiple_error_types_pull_result_out_of_option() {
@@ -78,4 +95,4 @@ mod tests {
         println!("{:?}", double_first_v2(empty));
         // 输出:Ok(None)
     }
-}
\ No newline at end of file
+}
@@ -13,6 +13,32 @@
 
     在下面例子中，显式处理将举出更受控制的结果，同时如果需要的话，仍然可以使程序 panic。
 */
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_option_and_unwrap", about: "用match显式处理Option，而不是unwrap", run: demo },
+];
+
+// 显式处理每一种情况，不会意外地panic
+fn give_adult(drink: Option<&str>) -> String {
+    match drink {
+        Some("lemonade") => "Yuck! Too sugary.".to_string(),
+        Some(inner) => format!("{}? How nice.", inner),
+        None => "No drink? Oh well.".to_string(),
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    for drink in [Some("water"), Some("lemonade"), None] {
+        writeln!(out, "{}", give_adult(drink))?;
+    }
+    // 确定有值时才用unwrap，expect可以附带说明
+    let menu = ["coffee", "tea"];
+    writeln!(out, "{}", menu.first().expect("the menu is never empty"))
+}
+
 #[cfg(test)]
 mod tests {
     // 显式处理panic
@@ -56,4 +82,4 @@ mod tests {
         // 会panic
         occurs_panic_implicit(Some("panic"));
     }
-}
\ No newline at end of file
+}
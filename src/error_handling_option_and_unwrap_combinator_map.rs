@@ -8,65 +8,91 @@
     多个不同的 map() 调用可以串起来，这样更加灵活。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 // 在下面例子中，process() 轻松取代了前面的所有函数，且更加紧凑。
 
-#[cfg(test)]
-mod tests {
-    #[derive(Debug)]
-    enum Food {
-        Apple,
-        Carrot,
-        Potato,
-    }
+#[derive(Debug)]
+enum Food {
+    Apple,
+    Carrot,
+    Potato,
+}
 
-    struct Peeled(Food);
+struct Peeled(Food);
 
-    struct Chopped(Food);
+struct Chopped(Food);
 
-    #[derive(Debug)]
-    struct Cooked(Food);
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Cooked(Food);
 
-    // 下面是用match来写逻辑：
+// 下面是用match来写逻辑：
 
-    // 削皮。如果没有食物，就返回 `None`。否则返回削好皮的食物。
-    fn peel(food: Option<Food>) -> Option<Peeled> {
-        match food {
-            Some(i) => Some(Peeled(i)),
-            None => None
-        }
+// 削皮。如果没有食物，就返回 `None`。否则返回削好皮的食物。
+#[allow(clippy::manual_map)]
+fn peel(food: Option<Food>) -> Option<Peeled> {
+    match food {
+        Some(i) => Some(Peeled(i)),
+        None => None
     }
-
-    // 切食物。如果没有食物，就返回 `None`。否则返回切好的食物。
-    fn chop(peeled: Option<Peeled>) -> Option<Chopped> {
-        match peeled {
-            // 利用match的模式匹配将里层的Food掏出来
-            Some(Peeled(i)) => Some(Chopped(i)),
-            None => None
-        }
+}
+
+// 切食物。如果没有食物，就返回 `None`。否则返回切好的食物。
+#[allow(clippy::manual_map)]
+fn chop(peeled: Option<Peeled>) -> Option<Chopped> {
+    match peeled {
+        // 利用match的模式匹配将里层的Food掏出来
+        Some(Peeled(i)) => Some(Chopped(i)),
+        None => None
     }
-
-    // 烹饪食物。煮的原料必须是进过切割的食物。
-    // 这里，我们使用 `map()` 来替代 `match` 以处理各种情况。
-    fn cook(chopped: Option<Chopped>) -> Option<Cooked> {
-        // 利用Chopped(i)将food模式匹配出来，并生成返回值Cooked(i)
-        chopped.map(|Chopped(i)| Cooked(i))
+}
+
+// 烹饪食物。煮的原料必须是进过切割的食物。
+// 这里，我们使用 `map()` 来替代 `match` 以处理各种情况。
+fn cook(chopped: Option<Chopped>) -> Option<Cooked> {
+    // 利用Chopped(i)将food模式匹配出来，并生成返回值Cooked(i)
+    chopped.map(|Chopped(i)| Cooked(i))
+}
+
+// 这个函数会完成削皮切块烹饪一条龙。
+// 把 `map()` 串起来，以简化代码。
+#[allow(clippy::redundant_closure)]
+fn process(food: Option<Food>) -> Option<Cooked> {
+    food.map(|i| Peeled(i))             // Option中现在是Peeled()
+        .map(|Peeled(i)| Chopped(i))    // Option中现在是Chopped()
+        .map(|Chopped(i)| Cooked(i))    // Option中现在是Cooked()
+}
+
+// 在尝试吃食物之前确认食物是否存在是非常重要的！
+fn eat(out: &mut dyn Write, cooked_food: Option<Cooked>) -> io::Result<()> {
+    match cooked_food {
+        Some(i) => writeln!(out, "good cooked food {:?}", i),
+        None => writeln!(out, "there is no cooked food")
     }
+}
 
-    // 这个函数会完成削皮切块烹饪一条龙。
-    // 把 `map()` 串起来，以简化代码。
-    fn process(food: Option<Food>) -> Option<Cooked> {
-        food.map(|i| Peeled(i))             // Option中现在是Peeled()
-            .map(|Peeled(i)| Chopped(i))    // Option中现在是Chopped()
-            .map(|Chopped(i)| Cooked(i))    // Option中现在是Cooked()
-    }
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_option_and_unwrap_combinator_map", about: "用map串起对Option的处理", run: demo },
+];
 
-    // 在尝试吃食物之前确认食物是否存在是非常重要的！
-    fn eat(cooked_food: Option<Cooked>) {
-        match cooked_food {
-            Some(i) => println!("good cooked food {:?}", i),
-            None => println!("there is no cooked food")
-        }
-    }
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let cooked_apple = cook(chop(peel(Some(Food::Apple))));
+    let cooked_carrot = cook(chop(peel(Some(Food::Carrot))));
+    // 用串起来的map完成一条龙
+    let cooked_potato = process(Some(Food::Potato));
+
+    eat(out, cooked_apple)?;
+    eat(out, cooked_carrot)?;
+    eat(out, cooked_potato)?;
+    // 没有食物时，每一步都原样传递None
+    eat(out, process(None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_option_and_unwrap_combinator_map() {
@@ -83,8 +109,10 @@ mod tests {
         println!("{:?}", cooked_potato);
 
         // 开吃
-        eat(cooked_apple);
-        eat(cooked_carrot);
-        eat(cooked_potato);
+        let mut out = Vec::new();
+        eat(&mut out, cooked_apple).unwrap();
+        eat(&mut out, cooked_carrot).unwrap();
+        eat(&mut out, cooked_potato).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "good cooked food Cooked(Apple)\ngood cooked food Cooked(Carrot)\ngood cooked food Cooked(Potato)\n");
     }
-}
\ No newline at end of file
+}
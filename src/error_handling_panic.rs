@@ -24,6 +24,31 @@
     这里显式地在错误条件下调用 panic
 */
 
+use std::panic;
+use std::io::{self, Write};
+use crate::runner::{panic_message, Demo};
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_panic", about: "显式调用panic!，并在外层捕获它", run: demo },
+];
+
+fn drink(beverage: &str) -> String {
+    // 遇到不可恢复的情况时显式panic
+    if beverage == "lemonade" {
+        panic!("AAAaaaaa!!!!");
+    }
+    format!("Some refreshing {} is all I need.", beverage)
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}", drink("water"))?;
+    // panic会回退（unwind）调用栈，catch_unwind可以在外层拦住它
+    match panic::catch_unwind(|| drink("lemonade")) {
+        Ok(text) => writeln!(out, "{}", text),
+        Err(payload) => writeln!(out, "caught panic: {}", panic_message(payload.as_ref())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     fn occurs_panic(mark: &str) {
@@ -39,4 +64,4 @@ mod tests {
         occurs_panic("michael.w");
         occurs_panic("panic");
     }
-}
\ No newline at end of file
+}
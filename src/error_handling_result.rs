@@ -15,6 +15,32 @@
 */
 
 // 我们来看看当 parse() 字符串成功和失败时会发生什么：
+
+use std::num::ParseIntError;
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_result", about: "parse返回Result，成功和失败时分别是什么", run: demo },
+];
+
+fn multiply(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
+    let first_num = first_num_str.parse::<i32>()?;
+    let second_num = second_num_str.parse::<i32>()?;
+    Ok(first_num * second_num)
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 不unwrap，而是显式地处理两种结果
+    for (first, second) in [("10", "2"), ("t", "2")] {
+        match multiply(first, second) {
+            Ok(n) => writeln!(out, "{} * {} = {}", first, second, n)?,
+            Err(e) => writeln!(out, "{} * {}: Error: {}", first, second, e)?,
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     fn multiply(first_num_str: &str, second_num_str: &str) -> i32 {
@@ -39,4 +65,4 @@ mod tests {
 
         // 为了改善错误消息的质量，我们应该更具体地了解返回类型并考虑显式地处理错误。
     }
-}
\ No newline at end of file
+}
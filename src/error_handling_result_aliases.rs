@@ -8,31 +8,46 @@
 */
 
 // 下面给出一个简短的示例来展示语法：
-#[cfg(test)]
-mod tests {
-    use std::num::ParseIntError;
+use std::num::ParseIntError;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    // 为带有错误类型 `ParseIntError` 的 `Result` 定义一个泛型别名。
-    type AliasedRusult<T> = Result<T, ParseIntError>;
+// 为带有错误类型 `ParseIntError` 的 `Result` 定义一个泛型别名。
+type AliasedRusult<T> = Result<T, ParseIntError>;
 
-    fn multiply(first_num_str: &str, second_num_str: &str) -> AliasedRusult<i32> {
-        first_num_str.parse::<i32>().and_then(|n1| {
-            second_num_str.parse::<i32>().map(|n2| n1 * n2)
-        })
-    }
+fn multiply(first_num_str: &str, second_num_str: &str) -> AliasedRusult<i32> {
+    first_num_str.parse::<i32>().and_then(|n1| {
+        second_num_str.parse::<i32>().map(|n2| n1 * n2)
+    })
+}
 
-    fn print_aliased_result(result: AliasedRusult<i32>) {
-        match result {
-            Ok(i) => println!("{}", i),
-            Err(e) => println!("Error: {}", e)
-        }
+fn print_aliased_result(out: &mut dyn Write, result: AliasedRusult<i32>) -> io::Result<()> {
+    match result {
+        Ok(i) => writeln!(out, "{}", i),
+        Err(e) => writeln!(out, "Error: {}", e)
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_result_aliases", about: "给Result取别名", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    print_aliased_result(out, multiply("10", "2"))?;
+    print_aliased_result(out, multiply("t", "2"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_result_aliases() {
+        let mut out = Vec::new();
         // 无错误
-        print_aliased_result(multiply("10", "11"));
+        print_aliased_result(&mut out, multiply("10", "11")).unwrap();
         // 有error
-        print_aliased_result(multiply("michael.w", "11"));
+        print_aliased_result(&mut out, multiply("michael.w", "11")).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "110\nError: invalid digit found in string\n");
     }
-}
\ No newline at end of file
+}
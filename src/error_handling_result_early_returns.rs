@@ -6,39 +6,56 @@
     如果发生错误，我们可以`停止`函数的执行然后返回错误。
     这样的代码更好写，更易读。
 */
-#[cfg(test)]
-mod tests {
-    use std::num::ParseIntError;
-
-    // 如果遇到
-    fn multiply(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
-        let n1 = match first_num_str.parse::<i32>() {
-            Ok(i) => i,
-            // 提前返回，如果出了错误
-            Err(e) => return Err(e),
-        };
-
-        let n2 = match second_num_str.parse::<i32>() {
-            Ok(i) => i,
-            Err(e) => return Err(e),
-        };
-
-        // 正确的返回
-        Ok(n1 * n2)
-    }
 
-    fn print_result(result: Result<i32, ParseIntError>) {
-        match result {
-            Ok(i) => println!("{}", i),
-            Err(e) => println!("Error: {}", e),
-        }
+use std::num::ParseIntError;
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+// 如果遇到
+#[allow(clippy::question_mark)]
+fn multiply(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
+    let n1 = match first_num_str.parse::<i32>() {
+        Ok(i) => i,
+        // 提前返回，如果出了错误
+        Err(e) => return Err(e),
+    };
+
+    let n2 = match second_num_str.parse::<i32>() {
+        Ok(i) => i,
+        Err(e) => return Err(e),
+    };
+
+    // 正确的返回
+    Ok(n1 * n2)
+}
+
+fn print_result(out: &mut dyn Write, result: Result<i32, ParseIntError>) -> io::Result<()> {
+    match result {
+        Ok(i) => writeln!(out, "{}", i),
+        Err(e) => writeln!(out, "Error: {}", e),
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_result_early_returns", about: "用match和提前返回处理错误", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    print_result(out, multiply("10", "2"))?;
+    print_result(out, multiply("t", "2"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_result_early_returns() {
+        let mut out = Vec::new();
         // 正常现象
-        print_result(multiply("10", "11"));
+        print_result(&mut out, multiply("10", "11")).unwrap();
         // 返回错误(提前返回)
-        print_result(multiply("michael.w", "11"));
+        print_result(&mut out, multiply("michael.w", "11")).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "110\nError: invalid digit found in string\n");
     }
-}
\ No newline at end of file
+}
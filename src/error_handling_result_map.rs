@@ -22,66 +22,85 @@
 
 */
 
+use std::io::{self, Write};
+use std::num::ParseIntError;
+use crate::runner::Demo;
 
-mod tests {
-    use std::num::ParseIntError;
-
-    // 直接返回Result类型，而不是返回i32
-    fn multiply_v1(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
-        match first_num_str.parse::<i32>() {
-            Ok(n1) => {
-                match second_num_str.parse::<i32>() {
-                    Ok(n2) => {
-                        // 经过重重匹配，返回了正确的结果
-                        Ok(n1 * n2)
-                    }
-                    Err(e) => Err(e)
+// 直接返回Result类型，而不是返回i32
+fn multiply_v1(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
+    match first_num_str.parse::<i32>() {
+        Ok(n1) => {
+            match second_num_str.parse::<i32>() {
+                Ok(n2) => {
+                    // 经过重重匹配，返回了正确的结果
+                    Ok(n1 * n2)
                 }
+                Err(e) => Err(e)
             }
-            Err(e) => Err(e)
         }
+        Err(e) => Err(e)
     }
+}
 
-    // 打印Result
-    fn print_result(result: Result<i32, ParseIntError>) {
-        match result {
-            Ok(n) => println!("Right: {}", n),
-            Err(e) => println!("Error: {}", e)
-        }
+// 打印Result
+fn print_result(out: &mut dyn Write, result: Result<i32, ParseIntError>) -> io::Result<()> {
+    match result {
+        Ok(n) => writeln!(out, "Right: {}", n),
+        Err(e) => writeln!(out, "Error: {}", e)
     }
+}
+
+// 就像 `Option` 那样，我们可以使用 `map()` 之类的组合算子。
+// 除去写法外，这个函数与上面那个完全一致，它的作用是：
+// 如果值是合法的，计算其乘积，否则返回错误
+fn multiply_v2(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
+    first_num_str.parse::<i32>().and_then(|n1| {
+        second_num_str.parse::<i32>().map(|n2| n1 * n2)
+    })
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_result_map", about: "把错误返回给调用者，并用and_then和map组合Result", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 两种写法的结果相同
+    print_result(out, multiply_v1("10", "2"))?;
+    print_result(out, multiply_v2("10", "2"))?;
+    print_result(out, multiply_v2("t", "2"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_result_map_v1() {
+        let mut out = Vec::new();
         // 仍然会给出正确的答案
         let result1 = multiply_v1("10", "11");
-        print_result(result1);
+        print_result(&mut out, result1).unwrap();
 
         // 这种情况下就会提供一条更有用的错误信息。
         let result2 = multiply_v1("michael.w", "11");
-        print_result(result2);
+        print_result(&mut out, result2).unwrap();
         // Error: invalid digit found in string
+        assert_eq!(String::from_utf8(out).unwrap(), "Right: 110\nError: invalid digit found in string\n");
     }
 
 //    幸运的是，Option 的 map、and_then、以及很多其他组合算子也为 Result 实现了。
 //    官方文档的 Result 一节包含完整的方法列表。
 
-    // 就像 `Option` 那样，我们可以使用 `map()` 之类的组合算子。
-    // 除去写法外，这个函数与上面那个完全一致，它的作用是：
-    // 如果值是合法的，计算其乘积，否则返回错误
-    fn multiply_v2(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
-        first_num_str.parse::<i32>().and_then(|n1| {
-            second_num_str.parse::<i32>().map(|n2| n1 * n2)
-        })
-    }
-
     #[test]
     fn test_error_handling_result_map_v2() {
+        let mut out = Vec::new();
         // 仍然会给出正确的答案
         let result1 = multiply_v2("10", "11");
-        print_result(result1);
+        print_result(&mut out, result1).unwrap();
 
         // 这种情况下就会提供一条更有用的错误信息。
         let result2 = multiply_v2("michael.w", "11");
-        print_result(result2);
+        print_result(&mut out, result2).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Right: 110\nError: invalid digit found in string\n");
     }
 }
\ No newline at end of file
@@ -12,30 +12,45 @@
     `?` 几乎就等于一个会返回 Err 而不是 panic 的 unwrap。
     看下面的例子：
 */
-#[cfg(test)]
-mod tests {
-    use std::num::ParseIntError;
+use std::num::ParseIntError;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    fn multiply(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
-        let n1 = first_num_str.parse::<i32>()?;
-        let n2 = second_num_str.parse::<i32>()?;
+fn multiply(first_num_str: &str, second_num_str: &str) -> Result<i32, ParseIntError> {
+    let n1 = first_num_str.parse::<i32>()?;
+    let n2 = second_num_str.parse::<i32>()?;
 
-        Ok(n1 * n2)
-    }
+    Ok(n1 * n2)
+}
 
-    fn print_result(result: Result<i32, ParseIntError>) {
-        match result {
-            Ok(i) => println!("{}", i),
-            Err(e) => println!("Error: {}", e),
-        }
+fn print_result(out: &mut dyn Write, result: Result<i32, ParseIntError>) -> io::Result<()> {
+    match result {
+        Ok(i) => writeln!(out, "{}", i),
+        Err(e) => writeln!(out, "Error: {}", e),
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_result_question_mark", about: "用?代替会panic的unwrap", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    print_result(out, multiply("10", "2"))?;
+    print_result(out, multiply("t", "2"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_result_question_mark() {
+        let mut out = Vec::new();
         // 正常现象
-        print_result(multiply("10", "11"));
+        print_result(&mut out, multiply("10", "11")).unwrap();
         // 返回错误(提前返回)
-        print_result(multiply("michael.w", "11"));
+        print_result(&mut out, multiply("michael.w", "11")).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "110\nError: invalid digit found in string\n");
     }
 
     /*
@@ -69,4 +84,4 @@ mod tests {
 
     }
     */
-}
\ No newline at end of file
+}
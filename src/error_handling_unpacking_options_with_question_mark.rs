@@ -7,19 +7,33 @@
         如果没有在上述类型的任意函数中使用?，编译会报错。
 */
 
-#[cfg(test)]
-mod tests {
-    fn next_birthday(current_age: Option<u8>) -> Option<String> {
-        // 如果 current_age 是 None， 则返回 None；
-        // 如果 current_age 是 Some， 则将u8转成String返回
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-        // 使用?操作符
-        let next_age = current_age?;
-        // 如果current_age为None,直接结束函数并返回。
+fn next_birthday(current_age: Option<u8>) -> Option<String> {
+    // 如果 current_age 是 None， 则返回 None；
+    // 如果 current_age 是 Some， 则将u8转成String返回
 
-        // 将u8转成String
-        Some(format!("{}", next_age))
-    }
+    // 使用?操作符
+    let next_age = current_age?;
+    // 如果current_age为None,直接结束函数并返回。
+
+    // 将u8转成String
+    Some(format!("{}", next_age))
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "error_handling_unpacking_options_with_question_mark", about: "用?取出Option中的值", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{:?}", next_birthday(Some(18)))?;
+    writeln!(out, "{:?}", next_birthday(None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_error_handling_unpacking_options_with_question_mark() {
@@ -40,6 +54,7 @@ mod tests {
     }
 
     #[derive(Copy, Clone)]
+    #[allow(dead_code)]
     struct PhoneNumber {
         area_code: Option<u8>,
         number: u32,
@@ -91,4 +106,4 @@ mod tests {
         // 返回Some(1)
         println!("{:?}", person.work_phone_area_code());
     }
-}
\ No newline at end of file
+}
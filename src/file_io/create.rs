@@ -4,27 +4,204 @@
     create静态方法以`只写`模式（write-only mode）打开一个文件。
 
     若文件已经存在，则旧内容将被销毁。否则，将创建一个新文件。
+
+    File::create会先把文件截断为空，如果写到一半程序崩溃或断电，留下的就是一个只写了一半的文件。
+    需要“要么是旧内容，要么是完整的新内容”时，使用atomic_write或AtomicFile：
+
+        1. 在目标文件所在的目录中创建一个临时文件（同一个文件系统，rename才是原子的）；
+        2. 把内容写进临时文件，sync_all保证数据落盘；
+        3. rename到目标路径，原子地替换旧文件；
+        4. 对目录fsync，保证rename本身也落盘（仅unix）。
+
+    AtomicFile在commit之前被drop（例如写入途中出错、panic）时，临时文件被删除，目标文件保持原样。
+
+    create_new模式用hard_link代替rename：目标已存在时hard_link失败，不会覆盖它。
+    有些文件系统（例如FAT）不支持硬链接，这时commit直接返回hard_link的错误。
 */
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[allow(dead_code)]
 static TEXT: &str = "This is a file for create test!!";
 
+// 同一进程内的计数器，保证临时文件名不重复
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// atomic_write与AtomicFile的选项
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AtomicOptions {
+    preserve_permissions: bool,
+    create_new: bool,
+}
+
+impl AtomicOptions {
+    /// 默认：新文件使用默认权限，目标已存在时替换它
+    pub fn new() -> AtomicOptions {
+        AtomicOptions::default()
+    }
+
+    /// 目标文件已存在时，新文件沿用它的权限
+    pub fn preserve_permissions(mut self, preserve: bool) -> AtomicOptions {
+        self.preserve_permissions = preserve;
+        self
+    }
+
+    /// 目标文件已存在时返回AlreadyExists，而不是替换它
+    pub fn create_new(mut self, create_new: bool) -> AtomicOptions {
+        self.create_new = create_new;
+        self
+    }
+}
+
+/// 先写临时文件、commit时再原子地替换目标文件的写入器
+#[derive(Debug)]
+pub struct AtomicFile {
+    target: PathBuf,
+    temp: PathBuf,
+    // commit之后为None
+    file: Option<File>,
+    options: AtomicOptions,
+}
+
+impl AtomicFile {
+    /// 准备原子地写入path
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<AtomicFile> {
+        AtomicFile::with_options(path, AtomicOptions::new())
+    }
+
+    pub fn with_options<P: AsRef<Path>>(path: P, options: AtomicOptions) -> io::Result<AtomicFile> {
+        let target = path.as_ref().to_path_buf();
+        let name = target.file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "atomic write needs a file name"))?
+            .to_string_lossy()
+            .into_owned();
+        // 提前失败，免得白写一遍；commit时还会再检查一次
+        if options.create_new && target.exists() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", target.display())));
+        }
+        let temp = parent_dir(&target).join(format!(".{}.{}-{}.tmp", name, std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+        let file = OpenOptions::new().write(true).create_new(true).open(&temp)?;
+        let mut atomic = AtomicFile { target, temp, file: Some(file), options };
+        if options.preserve_permissions {
+            match fs::metadata(&atomic.target) {
+                Ok(metadata) => atomic.file()?.set_permissions(metadata.permissions())?,
+                Err(ref err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(atomic)
+    }
+
+    /// 目标文件的路径
+    pub fn path(&self) -> &Path {
+        &self.target
+    }
+
+    fn file(&mut self) -> io::Result<&mut File> {
+        self.file.as_mut().ok_or_else(|| io::Error::other("atomic file is already committed"))
+    }
+
+    /// 把写入的内容落盘，并原子地替换目标文件
+    pub fn commit(mut self) -> io::Result<()> {
+        let file = self.file.take().expect("file is only taken by commit");
+        file.sync_all()?;
+        drop(file);
+        if self.options.create_new {
+            link_new(&self.temp, &self.target)?;
+        } else {
+            fs::rename(&self.temp, &self.target)?;
+        }
+        sync_dir(&parent_dir(&self.target))
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file()?.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // 没有commit（或者commit失败），删除临时文件，目标文件保持原样。
+        // commit成功后临时文件已经不存在了，删除失败也无妨
+        let _ = fs::remove_file(&self.temp);
+    }
+}
+
+// 文件所在的目录；只有文件名时是当前目录
+fn parent_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+// 把temp放到target，target已存在时返回AlreadyExists。
+// hard_link在目标已存在时失败，检查与创建是一步完成的，不会覆盖别人刚刚创建的文件
+fn link_new(temp: &Path, target: &Path) -> io::Result<()> {
+    fs::hard_link(temp, target)?;
+    // 目标已经就位，临时文件删不掉也不影响结果，Drop还会再删一次
+    let _ = fs::remove_file(temp);
+    Ok(())
+}
+
+// rename修改的是目录，目录本身也要fsync才算落盘
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// 原子地把contents写入path：读者看到的要么是旧文件，要么是完整的新文件
+pub fn atomic_write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    atomic_write_with(path, contents, AtomicOptions::new())
+}
+
+/// 按options原子地写入
+pub fn atomic_write_with<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C, options: AtomicOptions) -> io::Result<()> {
+    let mut file = AtomicFile::with_options(path, options)?;
+    file.write_all(contents.as_ref())?;
+    file.commit()
+}
+
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
-    use std::fs::File;
-    use std::error::Error;
-    use std::io::Write;
-    use crate::file_io::create::TEXT;
+    use std::fs::{self, File};
+    use std::io::{self, Write};
+    use crate::file_io::create::{atomic_write, atomic_write_with, AtomicFile, AtomicOptions, TEXT};
+    use crate::fixture::TempDir;
+
+    // 目录中的文件名，用来检查没有留下临时文件
+    fn entries(dir: &TempDir) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
 
     #[test]
     fn test_create() {
         // 创建路径
-        let path = Path::new("file_write.txt");
-        // 注：该文件会在与Cargo.lock和Cargo.toml文件同级目录
+        // 注：文件放在测试专用的临时目录中，测试结束时随目录一起删除
+        let dir = TempDir::new("file_io-create").unwrap();
+        let path = dir.join("file_write.txt");
         let display = path.display();
 
         // 以只写模式打开文件，返回io::Result<File>
         let mut file = match File::create(&path) {
-            Err(err) => panic!("file {} is created failed: {}", display, err.description()),
+            Err(err) => panic!("file {} is created failed: {}", display, err),
             Ok(file) => file
         };
 
@@ -34,5 +211,81 @@ mod tests {
             Err(err) => panic!("write to file {} failed: {}", display, err),
             Ok(_) => println!("write to {} successfully", display)
         }
+        assert_eq!(fs::read_to_string(&path).unwrap(), TEXT);
+
+        // 文件已经存在时，create会把旧内容清空
+        File::create(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+    }
+
+    #[test]
+    fn test_atomic_write_replaces_contents() {
+        let dir = TempDir::new("file_io-atomic").unwrap();
+        let path = dir.join("config.txt");
+        atomic_write(&path, "first").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        atomic_write(&path, TEXT).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), TEXT);
+        assert_eq!(entries(&dir), vec!["config.txt"]);
+    }
+
+    #[test]
+    fn test_drop_before_commit_keeps_old_contents() {
+        let dir = TempDir::new("file_io-atomic").unwrap();
+        let path = dir.write("data.txt", "old contents").unwrap();
+        {
+            // 模拟写到一半被中断：只写了一部分，没有commit
+            let mut file = AtomicFile::create(&path).unwrap();
+            file.write_all(b"new cont").unwrap();
+            assert_eq!(file.path(), path.as_path());
+            // 写入期间别人读到的仍是旧内容
+            assert_eq!(fs::read_to_string(&path).unwrap(), "old contents");
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old contents");
+        assert_eq!(entries(&dir), vec!["data.txt"]);
+
+        // 目标还不存在时，中断不会留下任何文件
+        drop(AtomicFile::create(dir.join("never.txt")).unwrap());
+        assert_eq!(entries(&dir), vec!["data.txt"]);
+    }
+
+    #[test]
+    fn test_create_new() {
+        let dir = TempDir::new("file_io-atomic").unwrap();
+        let options = AtomicOptions::new().create_new(true);
+        let path = dir.join("once.txt");
+        atomic_write_with(&path, "first", options).unwrap();
+        let err = atomic_write_with(&path, "second", options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+
+        // 写入期间目标被别人创建：commit失败，不覆盖对方的文件
+        let racing = dir.join("race.txt");
+        let mut file = AtomicFile::with_options(&racing, options).unwrap();
+        file.write_all(b"mine").unwrap();
+        fs::write(&racing, "theirs").unwrap();
+        assert_eq!(file.commit().unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&racing).unwrap(), "theirs");
+        assert_eq!(entries(&dir), vec!["once.txt", "race.txt"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_preserve_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("file_io-atomic").unwrap();
+        let path = dir.write("secret.txt", "v1").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        atomic_write_with(&path, "v2", AtomicOptions::new().preserve_permissions(true)).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v2");
+    }
+
+    #[test]
+    fn test_missing_directory() {
+        let dir = TempDir::new("file_io-atomic").unwrap();
+        let err = atomic_write(dir.join("no/such/dir.txt"), "x").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
     }
-}
\ No newline at end of file
+}
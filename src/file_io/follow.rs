@@ -0,0 +1,520 @@
+/*
+    跟踪文件（tail -f）
+
+    日志文件会不断地在末尾追加新行。Follower打开文件后定位到末尾（或者倒数第N行），
+    之后每隔一段时间（poll_interval）检查一次文件，把新追加的完整行逐一返回。
+    这里只用到了File、BufReader和metadata，不依赖任何操作系统的文件变化通知机制。
+
+    和read_lines::LineReader一样识别`\n`、`\r\n`和单独的`\r`三种换行符。还没有写完的行先留在缓冲里，
+    等换行符写进来之后再返回；`\r`之后的`\n`可能还没写进来，所以遇到`\r`就结束这一行，
+    下一次读到的第一个字节如果是`\n`，把它当作同一个换行符跳过。
+    行长度有上限（max_line_length），超出部分直接丢弃，这一行返回InvalidData错误，之后可以继续跟踪。
+
+    读到文件末尾时检查两种情况：
+        - 截断（truncate）：文件还是原来那个，但长度比已经读过的字节数小，从头开始重新读；
+        - 轮转（rotate）：日志工具把文件改名（例如app.log -> app.log.1），再在原路径创建一个新文件。
+          路径指向的文件的inode变了（仅unix），先读完旧文件剩下的行，再打开新文件从头读。
+          轮转过程中原路径暂时不存在时，继续等待。
+*/
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::read_lines::{find_terminator, DEFAULT_MAX_LINE_LENGTH};
+
+// 倒着查找最后N行时，每次读取的块大小
+const BLOCK_SIZE: u64 = 8 * 1024;
+
+/// 开始跟踪的位置
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Start {
+    /// 从文件末尾开始，只返回之后追加的行
+    #[default]
+    End,
+    /// 从倒数第N行开始
+    Lines(usize),
+    /// 从文件开头开始
+    Beginning,
+}
+
+/// Follower的选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FollowOptions {
+    start: Start,
+    poll_interval: Duration,
+    max_line_length: usize,
+}
+
+impl FollowOptions {
+    /// 默认：从末尾开始，每250毫秒检查一次，行长度上限为DEFAULT_MAX_LINE_LENGTH
+    pub fn new() -> FollowOptions {
+        FollowOptions { start: Start::End, poll_interval: Duration::from_millis(250), max_line_length: DEFAULT_MAX_LINE_LENGTH }
+    }
+
+    pub fn start(mut self, start: Start) -> FollowOptions {
+        self.start = start;
+        self
+    }
+
+    /// 没有新数据时，两次检查之间等待的时间
+    pub fn poll_interval(mut self, interval: Duration) -> FollowOptions {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// 一行（不含换行符）最多的字节数，超出时这一行返回InvalidData错误
+    pub fn max_line_length(mut self, max: usize) -> FollowOptions {
+        self.max_line_length = max;
+        self
+    }
+}
+
+impl Default for FollowOptions {
+    fn default() -> Self {
+        FollowOptions::new()
+    }
+}
+
+/// Follower返回的事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// 新的一行，不含换行符；非法的UTF-8替换为U+FFFD
+    Line(String),
+    /// 文件被截断，之后从头开始读
+    Truncated,
+    /// 文件被轮转，之后读的是原路径上的新文件
+    Rotated,
+}
+
+// 用来判断路径是否还指向同一个文件；非unix平台无法判断，不检测轮转
+#[cfg(unix)]
+fn identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn identity(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// 跟踪一个不断增长的文件，逐行返回新追加的内容
+#[derive(Debug)]
+pub struct Follower {
+    path: PathBuf,
+    poll_interval: Duration,
+    max_line_length: usize,
+    reader: BufReader<File>,
+    identity: Option<(u64, u64)>,
+    // 已经从当前文件读出的字节数，含partial
+    position: u64,
+    // 还没有遇到换行符的半行，最多max_line_length个字节
+    partial: Vec<u8>,
+    // 半行是否超出了max_line_length
+    overflow: bool,
+    // 上一行以`\r`结束，紧跟着的`\n`属于同一个换行符
+    after_cr: bool,
+    // 已经检查到、还没有返回的事件
+    pending: VecDeque<io::Result<Event>>,
+}
+
+impl Follower {
+    /// 从文件末尾开始跟踪
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Follower> {
+        Follower::with_options(path, FollowOptions::new())
+    }
+
+    pub fn with_options<P: AsRef<Path>>(path: P, options: FollowOptions) -> io::Result<Follower> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = File::open(&path)?;
+        let metadata = file.metadata()?;
+        let position = match options.start {
+            Start::Beginning => 0,
+            Start::End => metadata.len(),
+            Start::Lines(count) => tail_start(&mut file, metadata.len(), count)?,
+        };
+        file.seek(SeekFrom::Start(position))?;
+        Ok(Follower {
+            path,
+            poll_interval: options.poll_interval,
+            max_line_length: options.max_line_length,
+            reader: BufReader::new(file),
+            identity: identity(&metadata),
+            position,
+            partial: vec![],
+            overflow: false,
+            after_cr: false,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// 跟踪的路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 检查一次文件，不等待：有新事件时返回它，否则返回None
+    pub fn poll(&mut self) -> io::Result<Option<Event>> {
+        if let Some(event) = self.pending.pop_front() {
+            return event.map(Some);
+        }
+        if let Some(line) = self.read_line()? {
+            return Ok(Some(Event::Line(line)));
+        }
+        // 读到了末尾，看看文件是否被截断或轮转
+        self.check_file()?;
+        self.pending.pop_front().transpose()
+    }
+
+    /// 最多等待timeout，期间没有新事件时返回None
+    pub fn next_timeout(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(event) = self.poll()? {
+                return Ok(Some(event));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(self.poll_interval.min(deadline - now));
+        }
+    }
+
+    // 读出一个完整的行；读到末尾还没有遇到换行符时，已读的部分留在partial里，返回None
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            let available = match self.reader.fill_buf() {
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                result => result?,
+            };
+            if available.is_empty() {
+                return Ok(None);
+            }
+            if std::mem::take(&mut self.after_cr) && available[0] == b'\n' {
+                self.reader.consume(1);
+                self.position += 1;
+                continue;
+            }
+            let end = find_terminator(available);
+            let chunk = &available[..end.unwrap_or(available.len())];
+            // 超出上限的部分直接丢弃
+            let room = self.max_line_length.saturating_sub(self.partial.len());
+            if chunk.len() > room {
+                self.overflow = true;
+            }
+            self.partial.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            let terminator = end.map(|index| available[index]);
+            let used = chunk.len() + terminator.map_or(0, |_| 1);
+            self.reader.consume(used);
+            self.position += used as u64;
+            if let Some(byte) = terminator {
+                self.after_cr = byte == b'\r';
+                return self.take_partial().map(Some);
+            }
+        }
+    }
+
+    // 取出partial作为一行，超长时返回InvalidData错误
+    fn take_partial(&mut self) -> io::Result<String> {
+        let bytes = std::mem::take(&mut self.partial);
+        if std::mem::take(&mut self.overflow) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("line is longer than {} bytes", self.max_line_length)));
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn check_file(&mut self) -> io::Result<()> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // 轮转时旧文件已经改名、新文件还没有创建，下次再看
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if identity(&metadata) != self.identity {
+            return self.reopen();
+        }
+        if metadata.len() < self.position {
+            self.reader.seek(SeekFrom::Start(0))?;
+            self.position = 0;
+            self.partial.clear();
+            self.overflow = false;
+            self.after_cr = false;
+            self.pending.push_back(Ok(Event::Truncated));
+        }
+        Ok(())
+    }
+
+    fn reopen(&mut self) -> io::Result<()> {
+        let file = File::open(&self.path)?;
+        let metadata = file.metadata()?;
+        // 旧文件在改名之后可能还被写入了几行，先把它们读完；最后没写完的半行也不会再有下文了
+        loop {
+            match self.read_line() {
+                Ok(Some(line)) => self.pending.push_back(Ok(Event::Line(line))),
+                Ok(None) => break,
+                // 超长的行已经被跳过，错误按顺序留给调用者
+                Err(err) if err.kind() == io::ErrorKind::InvalidData => self.pending.push_back(Err(err)),
+                Err(err) => return Err(err),
+            }
+        }
+        if !self.partial.is_empty() || self.overflow {
+            let rest = self.take_partial().map(Event::Line);
+            self.pending.push_back(rest);
+        }
+        self.pending.push_back(Ok(Event::Rotated));
+        self.reader = BufReader::new(file);
+        self.identity = identity(&metadata);
+        self.position = 0;
+        self.after_cr = false;
+        Ok(())
+    }
+}
+
+impl Iterator for Follower {
+    type Item = io::Result<Event>;
+
+    /// 阻塞直到有新事件，永远不会返回None
+    fn next(&mut self) -> Option<io::Result<Event>> {
+        loop {
+            match self.poll() {
+                Ok(Some(event)) => return Some(Ok(event)),
+                Ok(None) => thread::sleep(self.poll_interval),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+// 倒数第count行的起始位置：从末尾开始按块往前找换行符。
+// 与find_terminator一样，`\n`、`\r\n`和单独的`\r`都结束一行（`\r\n`只算一次）；
+// 文件末尾的换行符结束的是最后一行，不算作分隔
+fn tail_start(file: &mut File, len: u64, count: usize) -> io::Result<u64> {
+    if count == 0 {
+        return Ok(len);
+    }
+    let mut end = len;
+    let mut block = vec![0; BLOCK_SIZE as usize];
+    let mut newlines = 0;
+    // 上一次看到的字节，即当前字节在文件中的下一个字节
+    let mut next = None;
+    while end > 0 {
+        let start = end.saturating_sub(BLOCK_SIZE);
+        let chunk = &mut block[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        for (index, &byte) in chunk.iter().enumerate().rev() {
+            let position = start + index as u64;
+            let terminator = byte == b'\n' || (byte == b'\r' && next != Some(b'\n'));
+            next = Some(byte);
+            if terminator && position + 1 < len {
+                newlines += 1;
+                if newlines == count {
+                    return Ok(position + 1);
+                }
+            }
+        }
+        end = start;
+    }
+    // 不足count行，从头开始
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::TempDir;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    fn options() -> FollowOptions {
+        FollowOptions::new().poll_interval(Duration::from_millis(5))
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn line(text: &str) -> Option<Event> {
+        Some(Event::Line(text.to_string()))
+    }
+
+    #[test]
+    fn test_follow_appended_lines() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let path = dir.write("app.log", "old line\n").unwrap();
+        let mut follower = Follower::with_options(&path, options()).unwrap();
+        assert_eq!(follower.poll().unwrap(), None);
+
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                for text in ["first\n", "second\r\n", "half ", "done\n"] {
+                    thread::sleep(Duration::from_millis(20));
+                    append(&path, text);
+                }
+            })
+        };
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), line("first"));
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), line("second"));
+        // 半行要等换行符写进来之后才返回
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), line("half done"));
+        writer.join().unwrap();
+        assert_eq!(follower.next_timeout(Duration::from_millis(30)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_iterator_blocks_until_line() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let path = dir.write("app.log", "").unwrap();
+        let follower = Follower::with_options(&path, options()).unwrap();
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                for i in 0..3 {
+                    thread::sleep(Duration::from_millis(10));
+                    append(&path, &format!("event {}\n", i));
+                }
+            })
+        };
+        let events: Vec<Event> = follower.take(3).map(Result::unwrap).collect();
+        writer.join().unwrap();
+        assert_eq!(events, (0..3).map(|i| Event::Line(format!("event {}", i))).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_start_positions() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        // 超过一个块，覆盖跨块查找换行符的情况
+        let contents: String = (0..3000).map(|i| format!("line {}\n", i)).collect();
+        let path = dir.write("big.log", &contents).unwrap();
+        let drain = |start: Start| -> Vec<Option<Event>> {
+            let mut follower = Follower::with_options(&path, options().start(start)).unwrap();
+            std::iter::from_fn(|| follower.poll().unwrap().map(Some)).collect()
+        };
+        assert_eq!(drain(Start::Lines(3)), vec![line("line 2997"), line("line 2998"), line("line 2999")]);
+        assert_eq!(drain(Start::Lines(0)), vec![]);
+        assert_eq!(drain(Start::End), vec![]);
+        assert_eq!(drain(Start::Lines(5000)).len(), 3000);
+        assert_eq!(drain(Start::Beginning).len(), 3000);
+
+        // 没有结尾换行符时，最后的半行也算一行，但要等它写完才返回
+        let path = dir.write("short.log", "a\nb\nc").unwrap();
+        let mut follower = Follower::with_options(&path, options().start(Start::Lines(2))).unwrap();
+        assert_eq!(follower.poll().unwrap(), line("b"));
+        assert_eq!(follower.poll().unwrap(), None);
+        append(&path, "d\n");
+        assert_eq!(follower.poll().unwrap(), line("cd"));
+    }
+
+    #[test]
+    fn test_truncation() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let path = dir.write("app.log", "some history\n").unwrap();
+        let mut follower = Follower::with_options(&path, options()).unwrap();
+        append(&path, "before\n");
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), line("before"));
+
+        // 截断后写入的内容比读过的少，才能被识别为截断
+        fs::write(&path, "after\n").unwrap();
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), Some(Event::Truncated));
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), line("after"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rotation() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let path = dir.write("app.log", "").unwrap();
+        let mut follower = Follower::with_options(&path, options()).unwrap();
+        append(&path, "one\n");
+        assert_eq!(follower.poll().unwrap(), line("one"));
+
+        fs::rename(&path, dir.join("app.log.1")).unwrap();
+        // 改名之后仍然打开着旧文件的写入者又写了几行
+        append(&dir.join("app.log.1"), "two\nunfinished");
+        assert_eq!(follower.poll().unwrap(), line("two"));
+        // 新文件还没有创建
+        assert_eq!(follower.poll().unwrap(), None);
+
+        fs::write(&path, "fresh\n").unwrap();
+        assert_eq!(follower.poll().unwrap(), line("unfinished"));
+        assert_eq!(follower.poll().unwrap(), Some(Event::Rotated));
+        assert_eq!(follower.poll().unwrap(), line("fresh"));
+        append(&path, "more\n");
+        assert_eq!(follower.next_timeout(WAIT).unwrap(), line("more"));
+        assert_eq!(follower.path(), path.as_path());
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let path = dir.write("app.log", "").unwrap();
+        let mut follower = Follower::with_options(&path, options()).unwrap();
+        append(&path, "cr\rcrlf\r\nlf\nsplit\r");
+        assert_eq!(follower.poll().unwrap(), line("cr"));
+        assert_eq!(follower.poll().unwrap(), line("crlf"));
+        assert_eq!(follower.poll().unwrap(), line("lf"));
+        // 单独的`\r`立即结束一行
+        assert_eq!(follower.poll().unwrap(), line("split"));
+        assert_eq!(follower.poll().unwrap(), None);
+        // 之后才写进来的`\n`和前面的`\r`是同一个换行符，不会多出一个空行
+        append(&path, "\nnext\n\n");
+        assert_eq!(follower.poll().unwrap(), line("next"));
+        assert_eq!(follower.poll().unwrap(), line(""));
+        assert_eq!(follower.poll().unwrap(), None);
+    }
+
+    #[test]
+    fn test_start_lines_with_cr() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let drain = |path: &Path, count: usize| -> Vec<Option<Event>> {
+            let mut follower = Follower::with_options(path, options().start(Start::Lines(count))).unwrap();
+            std::iter::from_fn(|| follower.poll().unwrap().map(Some)).collect()
+        };
+        // 只用`\r`换行，并且超过一个块
+        let contents: String = (0..3000).map(|i| format!("line {}\r", i)).collect();
+        let path = dir.write("cr.log", &contents).unwrap();
+        assert_eq!(drain(&path, 2), vec![line("line 2998"), line("line 2999")]);
+        assert_eq!(drain(&path, 5000).len(), 3000);
+
+        // 混合的换行符，`\r\n`只算一次，结尾的`\r\n`结束的是最后一行
+        let path = dir.write("mixed.log", "a\rb\r\nc\nd\r\n").unwrap();
+        assert_eq!(drain(&path, 3), vec![line("b"), line("c"), line("d")]);
+        let path = dir.write("trailing_cr.log", "a\rb\rc\r").unwrap();
+        assert_eq!(drain(&path, 1), vec![line("c")]);
+    }
+
+    #[test]
+    fn test_max_line_length() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        let path = dir.write("app.log", "").unwrap();
+        let mut follower = Follower::with_options(&path, options().max_line_length(8)).unwrap();
+        append(&path, "short\n");
+        assert_eq!(follower.poll().unwrap(), line("short"));
+        // 没写完的超长行只保留上限以内的部分，不会无限增长
+        append(&path, &"x".repeat(10_000));
+        assert_eq!(follower.poll().unwrap(), None);
+        assert_eq!(follower.partial.len(), 8);
+        append(&path, "\nafter\n");
+        let err = follower.poll().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line is longer than 8 bytes");
+        // 出错之后可以继续跟踪
+        assert_eq!(follower.poll().unwrap(), line("after"));
+        assert_eq!(follower.poll().unwrap(), None);
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        let dir = TempDir::new("file_io-follow").unwrap();
+        assert_eq!(Follower::open(dir.join("missing.log")).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
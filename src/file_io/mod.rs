@@ -7,6 +7,34 @@
     这使得所有 I/O 操作的失败都变成`显式`的。借助这点，程序员可以看到所有的失败路径，并被鼓励主动地处理这些情形。
 */
 
-mod open;
-mod create;
-mod read_lines;
\ No newline at end of file
+pub mod open;
+pub mod create;
+pub mod read_lines;
+
+pub mod follow;
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::process;
+
+use self::create::atomic_write;
+use self::read_lines::LineReader;
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "file_io", about: "原子地写入文件，再用LineReader按行读回", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let path = env::temp_dir().join(format!("rust-exercise-file_io-{}.txt", process::id()));
+    atomic_write(&path, include_str!("rust.txt"))?;
+
+    let lines: io::Result<Vec<_>> = LineReader::open(&path)?.collect();
+    // 先删掉文件，再处理读取的结果
+    fs::remove_file(&path)?;
+    for line in lines? {
+        writeln!(out, "{:>2} @{:<2} {:?} {:?}", line.number, line.byte_offset, line.text.to_string(), line.ending)?;
+    }
+    Ok(())
+}
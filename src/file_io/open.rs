@@ -6,31 +6,41 @@
 */
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
     use std::fs::File;
-    use std::error::Error;
-    use std::io::Read;
+    use std::io::{ErrorKind, Read};
+    use crate::fixture::{TempDir, RUST_TXT};
 
     #[test]
     fn test_open() {
         // 创建指向所需的文件的Path
-        let path = Path::new("/Users/oker/CLionProjects/rust-exercise/src/file_io/rust.txt");
+        // 注：rust.txt由fixture写进临时目录，不依赖本机上的绝对路径
+        let dir = TempDir::with_fixtures("file_io-open", &[RUST_TXT]).unwrap();
+        let path = dir.join(RUST_TXT.name);
         let display = path.display();
 
         // 以只读方式打开路径，返回io::Result<File>
         let mut file = match File::open(&path) {
-            // io::Error 的 description 方法返回一个描述错误的字符串。
-            Err(err) => panic!("file {} is opened failed: {}", display, err.description()),
+            // io::Error 实现了 Display，可以直接打印出错误描述。
+            Err(err) => panic!("file {} is opened failed: {}", display, err),
             Ok(file) => file
         };
 
         // 读取文件内容到一个字符串中，返回io::Result<usize>
         let mut text = String::new();
         match file.read_to_string(&mut text) {
-            Err(err) => panic!("file {} is read failed: {}", display, err.description()),
+            Err(err) => panic!("file {} is read failed: {}", display, err),
             Ok(_) => print!("text in {} is: \n{}", display, text),
         }
+        assert_eq!(text, RUST_TXT.contents);
 
-        // file离开作用域时，hello.txt文件将被关闭。
+        // file离开作用域时，rust.txt文件将被关闭。
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        let dir = TempDir::new("file_io-open").unwrap();
+        // 文件不存在时返回Err，而不是panic
+        let err = File::open(dir.join("missing.txt")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
     }
 }
\ No newline at end of file
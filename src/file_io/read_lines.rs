@@ -5,26 +5,371 @@
 
     File::open 需要一个泛型`AsRef<Path>`。
     这正是 read_lines() 期望的输入。
+
+    lines()只认`\n`（顺带去掉`\r\n`中的`\r`），遇到非UTF-8的行返回Err，调用者很容易用`if let Ok`把它悄悄跳过；
+    它也不告诉我们行号、行在文件中的位置和原来的换行符，一行再长也会整行读进内存。
+    LineReader解决这些问题：
+        - 识别`\n`、`\r\n`和单独的`\r`三种换行符，并在Line.ending中保留下来；
+        - 每一行带有行号（从1开始）和这一行第一个字节在文件中的偏移；
+        - 解码方式可选：Lossy（非法字节替换为U+FFFD）、Strict（返回InvalidData错误）、Raw（不解码，保留原始字节）；
+        - 行长度有上限，超出部分直接丢弃并返回错误，不会因为一行巨大的数据耗尽内存。
+    出错的行不影响后面的行，迭代可以继续。
 */
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+/// 打开文件，返回按行读取的迭代器
+///
+/// 输出被包裹在Result中，以便处理打开文件时的错误；每一行本身也是一个io::Result
+pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+    where P: AsRef<Path>,
+{
+    let file = File::open(filename)?;
+    Ok(io::BufReader::new(file).lines())
+}
+
+/// LineReader默认的行长度上限：1 MiB
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 1 << 20;
+
+/// 一行结尾的换行符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// 文件的最后一行没有换行符
+    None,
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::None => "",
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+
+    /// 换行符的字节数
+    pub fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == LineEnding::None
+    }
+}
+
+/// 行内容的解码方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Decoding {
+    /// 非法的UTF-8字节替换为U+FFFD
+    #[default]
+    Lossy,
+    /// 非法的UTF-8返回InvalidData错误
+    Strict,
+    /// 不解码，保留原始字节
+    Raw,
+}
+
+/// 一行的内容（不含换行符）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Text {
+    Utf8(String),
+    Bytes(Vec<u8>),
+}
+
+impl Text {
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Text::Utf8(text) => text.as_bytes(),
+            Text::Bytes(bytes) => bytes,
+        }
+    }
+
+    /// Raw模式下读到的内容返回None
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Text::Utf8(text) => Some(text),
+            Text::Bytes(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Text::Utf8(text) => f.write_str(text),
+            Text::Bytes(bytes) => f.write_str(&String::from_utf8_lossy(bytes)),
+        }
+    }
+}
+
+/// LineReader读到的一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// 行号，从1开始
+    pub number: usize,
+    /// 这一行第一个字节在输入中的偏移
+    pub byte_offset: u64,
+    pub text: Text,
+    pub ending: LineEnding,
+}
+
+/// 按行读取，保留行号、偏移和换行符的迭代器
+#[derive(Debug)]
+pub struct LineReader<R> {
+    reader: R,
+    decoding: Decoding,
+    max_line_length: usize,
+    // 已经读出的行数
+    number: usize,
+    // 下一行的起始偏移
+    offset: u64,
+}
+
+impl LineReader<io::BufReader<File>> {
+    /// 打开文件，返回按行读取的LineReader
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(LineReader::new(io::BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// 默认Lossy解码，行长度上限为DEFAULT_MAX_LINE_LENGTH
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader { reader, decoding: Decoding::default(), max_line_length: DEFAULT_MAX_LINE_LENGTH, number: 0, offset: 0 }
+    }
+
+    pub fn decoding(mut self, decoding: Decoding) -> LineReader<R> {
+        self.decoding = decoding;
+        self
+    }
+
+    /// 一行（不含换行符）最多的字节数，超出时这一行返回InvalidData错误
+    pub fn max_line_length(mut self, max: usize) -> LineReader<R> {
+        self.max_line_length = max;
+        self
+    }
+
+    /// 下一行的起始偏移，也就是目前读过的字节数
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        loop {
+            match self.reader.fill_buf() {
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                // 借用检查器不允许在循环里直接返回这个借用，这里再取一次
+                Ok(_) => return self.reader.fill_buf(),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    // 读出一行，输入结束时返回None
+    fn read_raw(&mut self) -> io::Result<Option<RawLine>> {
+        let mut bytes = vec![];
+        let mut consumed = 0u64;
+        let mut overflow = false;
+        let ending = loop {
+            let max = self.max_line_length;
+            let available = self.fill_buf()?;
+            if available.is_empty() {
+                if consumed == 0 {
+                    return Ok(None);
+                }
+                break LineEnding::None;
+            }
+            let end = find_terminator(available);
+            let chunk = &available[..end.unwrap_or(available.len())];
+            // 超出上限的部分直接丢弃
+            let room = max - bytes.len();
+            if chunk.len() > room {
+                overflow = true;
+            }
+            bytes.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            let terminator = end.map(|index| available[index]);
+            let used = chunk.len() + terminator.map_or(0, |_| 1);
+            self.reader.consume(used);
+            consumed += used as u64;
+            match terminator {
+                None => continue,
+                Some(b'\n') => break LineEnding::Lf,
+                // `\r`之后紧跟的`\n`可能在下一块缓冲里
+                Some(_) => {
+                    if self.fill_buf()?.first() == Some(&b'\n') {
+                        self.reader.consume(1);
+                        consumed += 1;
+                        break LineEnding::CrLf;
+                    }
+                    break LineEnding::Cr;
+                }
+            }
+        };
+        Ok(Some(RawLine { bytes, ending, consumed, overflow }))
+    }
+}
+
+// 第一个换行符（`\n`或`\r`）的位置；follow::Follower也用它来切分行
+pub(crate) fn find_terminator(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().position(|&byte| byte == b'\n' || byte == b'\r')
+}
+
+// 解码之前的一行
+struct RawLine {
+    // 最多max_line_length个字节
+    bytes: Vec<u8>,
+    ending: LineEnding,
+    // 在输入中占的字节数，含换行符和丢弃的部分
+    consumed: u64,
+    // 是否超出了max_line_length
+    overflow: bool,
+}
+
+impl<R: BufRead> Iterator for LineReader<R> {
+    type Item = io::Result<Line>;
+
+    fn next(&mut self) -> Option<io::Result<Line>> {
+        let RawLine { bytes, ending, consumed, overflow } = match self.read_raw() {
+            Ok(Some(raw)) => raw,
+            Ok(None) => return None,
+            Err(err) => return Some(Err(err)),
+        };
+        self.number += 1;
+        let byte_offset = self.offset;
+        self.offset += consumed;
+        let number = self.number;
+        if overflow {
+            return Some(Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("line {} is longer than {} bytes", number, self.max_line_length))));
+        }
+        let text = match self.decoding {
+            Decoding::Raw => Text::Bytes(bytes),
+            Decoding::Lossy => Text::Utf8(String::from_utf8_lossy(&bytes).into_owned()),
+            Decoding::Strict => match String::from_utf8(bytes) {
+                Ok(text) => Text::Utf8(text),
+                Err(err) => return Some(Err(io::Error::new(io::ErrorKind::InvalidData,
+                    format!("line {}: invalid UTF-8 at byte {}", number, byte_offset + err.utf8_error().valid_up_to() as u64)))),
+            },
+        };
+        Some(Ok(Line { number, byte_offset, text, ending }))
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use std::fs::File;
-    use std::io;
-    use std::io::BufRead;
+    use super::{read_lines, Decoding, Line, LineEnding, LineReader, Text};
+    use std::io::{self, BufReader};
+    use crate::fixture::{TempDir, RUST_TXT};
 
     #[test]
     fn test_read_lines() {
-        let file = File::open("src/file_io/rust.txt").unwrap();
+        let dir = TempDir::with_fixtures("file_io-read_lines", &[RUST_TXT]).unwrap();
         // 生成一个行迭代器
-        let lines_iterator = io::BufReader::new(file).lines();
+        let lines_iterator = read_lines(dir.join(RUST_TXT.name)).unwrap();
         // 迭代出每一行字符串内容
+        let mut lines = vec![];
         for (num, line) in lines_iterator.enumerate() {
             println!("Line {}: ", num);
             if let Ok(line_string) = line {
                 println!("{}", line_string);
+                lines.push(line_string);
             }
         }
+        assert_eq!(lines, vec!["This is", "a", "file", "for test!!"]);
+        assert!(read_lines(dir.join("missing.txt")).is_err());
+    }
+
+    // 每次只缓冲capacity个字节，用来覆盖`\r\n`被缓冲区边界切开的情况
+    fn reader(input: &[u8], capacity: usize) -> LineReader<BufReader<&[u8]>> {
+        LineReader::new(BufReader::with_capacity(capacity, input))
+    }
+
+    fn utf8(number: usize, byte_offset: u64, text: &str, ending: LineEnding) -> Line {
+        Line { number, byte_offset, text: Text::Utf8(text.to_string()), ending }
+    }
+
+    #[test]
+    fn test_line_endings_and_offsets() {
+        let input = b"one\ntwo\r\nthree\rfour\n\n\r\nlast";
+        for capacity in [1, 2, 3, 8192] {
+            let lines: Vec<Line> = reader(input, capacity).map(Result::unwrap).collect();
+            assert_eq!(lines, vec![
+                utf8(1, 0, "one", LineEnding::Lf),
+                utf8(2, 4, "two", LineEnding::CrLf),
+                utf8(3, 9, "three", LineEnding::Cr),
+                utf8(4, 15, "four", LineEnding::Lf),
+                utf8(5, 20, "", LineEnding::Lf),
+                utf8(6, 21, "", LineEnding::CrLf),
+                utf8(7, 23, "last", LineEnding::None),
+            ], "capacity {}", capacity);
+            // 内容加上换行符可以原样拼回输入
+            let rebuilt: Vec<u8> = lines.iter()
+                .flat_map(|line| [line.text.as_bytes(), line.ending.as_str().as_bytes()].concat())
+                .collect();
+            assert_eq!(rebuilt, input);
+        }
+        // 结尾的换行符不会多出一个空行
+        assert_eq!(reader(b"a\r", 1).count(), 1);
+        assert_eq!(reader(b"", 1).count(), 0);
+    }
+
+    #[test]
+    fn test_decoding_modes() {
+        let input: &[u8] = b"ok\nbad \xff\xfe!\nfine";
+        let lossy: Vec<String> = reader(input, 4).map(|line| line.unwrap().text.to_string()).collect();
+        assert_eq!(lossy, vec!["ok", "bad \u{fffd}\u{fffd}!", "fine"]);
+
+        let raw: Vec<Line> = reader(input, 4).decoding(Decoding::Raw).map(Result::unwrap).collect();
+        assert_eq!(raw[1].text, Text::Bytes(b"bad \xff\xfe!".to_vec()));
+        assert_eq!(raw[1].text.as_str(), None);
+
+        // Strict模式下坏的一行返回错误，后面的行照常读出
+        let strict: Vec<io::Result<Line>> = reader(input, 4).decoding(Decoding::Strict).collect();
+        assert_eq!(strict[0].as_ref().unwrap().text.as_str(), Some("ok"));
+        let err = strict[1].as_ref().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 2: invalid UTF-8 at byte 7");
+        assert_eq!(strict[2].as_ref().unwrap(), &utf8(3, 11, "fine", LineEnding::None));
+    }
+
+    #[test]
+    fn test_max_line_length() {
+        let mut input = b"short\n".to_vec();
+        input.extend(std::iter::repeat_n(b'x', 10_000));
+        input.extend(b"\r\nafter");
+        let mut lines = reader(&input, 64).max_line_length(16);
+        assert_eq!(lines.next().unwrap().unwrap().text.as_str(), Some("short"));
+        let err = lines.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 2 is longer than 16 bytes");
+        // 超长的行被整行跳过，偏移仍然准确
+        assert_eq!(lines.next().unwrap().unwrap(), utf8(3, 10_008, "after", LineEnding::None));
+        assert!(lines.next().is_none());
+        assert_eq!(lines.offset(), input.len() as u64);
+
+        // 正好等于上限的行不算超长
+        let exact: Vec<Line> = reader(b"abcd\n", 2).max_line_length(4).map(Result::unwrap).collect();
+        assert_eq!(exact, vec![utf8(1, 0, "abcd", LineEnding::Lf)]);
+    }
+
+    #[test]
+    fn test_open() {
+        let dir = TempDir::with_fixtures("file_io-line_reader", &[RUST_TXT]).unwrap();
+        let texts: Vec<String> = LineReader::open(dir.join(RUST_TXT.name)).unwrap()
+            .map(|line| line.unwrap().text.to_string())
+            .collect();
+        assert_eq!(texts, vec!["This is", "a", "file", "for test!!"]);
+        assert_eq!(LineReader::open(dir.join("missing.txt")).unwrap_err().kind(), io::ErrorKind::NotFound);
     }
 
     /*
@@ -0,0 +1,123 @@
+/*
+    测试夹具fixture
+
+    文件相关的测试不应该依赖某台机器上的绝对路径，也不应该往仓库目录里写文件。
+
+    TempDir在系统临时目录下创建一个`唯一`的目录，离开作用域（drop）时连同其中的文件一起删除。
+    Fixture是一份有名字的文件内容，可以写进TempDir，然后把路径交给测试使用。
+*/
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 一份有名字的文件内容
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture {
+    /// 写入TempDir时使用的文件名
+    pub name: &'static str,
+    pub contents: &'static str,
+}
+
+/// file_io示例读取的文本文件
+pub const RUST_TXT: Fixture = Fixture {
+    name: "rust.txt",
+    contents: include_str!("file_io/rust.txt"),
+};
+
+// 同一进程内的计数器，保证同一时刻创建的目录名也不会重复
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// 离开作用域时自动删除的临时目录
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// 在系统临时目录下创建一个以prefix开头的新目录
+    pub fn new(prefix: &str) -> io::Result<TempDir> {
+        let base = std::env::temp_dir();
+        loop {
+            let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+            let name = format!("{}-{}-{}-{}", prefix, std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed), nanos);
+            let path = base.join(name);
+            // create_dir（而不是create_dir_all）在目录已存在时会报错，借此保证目录是新建的
+            match fs::create_dir(&path) {
+                Ok(()) => return Ok(TempDir { path }),
+                Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// 创建临时目录，并写入若干fixture
+    pub fn with_fixtures(prefix: &str, fixtures: &[Fixture]) -> io::Result<TempDir> {
+        let dir = TempDir::new(prefix)?;
+        for fixture in fixtures {
+            dir.seed(fixture)?;
+        }
+        Ok(dir)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 目录下某个文件的路径（不要求文件存在）
+    pub fn join<P: AsRef<Path>>(&self, name: P) -> PathBuf {
+        self.path.join(name)
+    }
+
+    /// 在目录下写入一个文件，返回它的路径
+    pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(&self, name: P, contents: C) -> io::Result<PathBuf> {
+        let path = self.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// 把fixture写进目录，返回它的路径
+    pub fn seed(&self, fixture: &Fixture) -> io::Result<PathBuf> {
+        self.write(fixture.name, fixture.contents)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // drop中不能返回错误，删除失败（例如文件仍被占用）时只能忽略
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_dir_is_unique_and_removed_on_drop() {
+        let first = TempDir::new("rust-exercise-fixture").unwrap();
+        let second = TempDir::new("rust-exercise-fixture").unwrap();
+        assert_ne!(first.path(), second.path());
+        assert!(first.path().is_dir());
+
+        let path = first.path().to_path_buf();
+        let file = first.write("nested/a.txt", "a").unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a");
+
+        drop(first);
+        assert!(!path.exists());
+        assert!(second.path().exists());
+    }
+
+    #[test]
+    fn test_with_fixtures() {
+        let dir = TempDir::with_fixtures("rust-exercise-fixture", &[RUST_TXT]).unwrap();
+        let text = fs::read_to_string(dir.join(RUST_TXT.name)).unwrap();
+        assert_eq!(text, RUST_TXT.contents);
+        assert!(text.starts_with("This is"));
+    }
+}
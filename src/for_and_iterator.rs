@@ -1,9 +1,41 @@
 // for in 结构能以几种方式与 Iterator 互动。
 // 如果没有特别指定，for 循环会对给出的集合应用 into_iter 函数,，把它转换成 一个迭代器。
 // 这并不是把集合变成迭代器的唯一方法，其他的方法有 iter 和 iter_mut 函数。
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "for_and_iterator", about: "for循环中的iter、into_iter与iter_mut", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut names = vec!["Bob", "Frank", "Ferris"];
+
+    // iter：借用每个元素，循环之后集合仍然可用
+    for name in names.iter() {
+        match *name {
+            "Ferris" => writeln!(out, "There is a rustacean among us!")?,
+            _ => writeln!(out, "Hello {}", name)?,
+        }
+    }
+
+    // iter_mut：可变地借用每个元素，就地修改集合
+    for name in names.iter_mut() {
+        *name = if *name == "Ferris" { "crab" } else { "human" };
+    }
+
+    // into_iter：消耗集合，之后names不能再使用
+    for name in names.into_iter() {
+        writeln!(out, "{}", name)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
+    #[allow(clippy::redundant_pattern)]
     fn test_for_and_iterator() {
         let names = vec!["Bob", "Frank", "Ferris"];
         // 三个函数会以不同的方式返回集合中的数据
@@ -50,4 +82,4 @@ mod tests {
 
         // 可见names中的数据已经被修改
     }
-}
\ No newline at end of file
+}
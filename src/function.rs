@@ -1,5 +1,42 @@
 // 和 C/C++ 不一样，Rust 的函数定义位置是没有限制的。
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function", about: "函数的定义、提前返回与返回值", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 函数可以先使用，后定义
+    for n in 1..=15 {
+        writeln!(out, "{}", fizzbuzz(n))?;
+    }
+
+    #[allow(clippy::manual_is_multiple_of)]
+    fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
+        // 边界情况，提前返回
+        if rhs == 0 {
+            return false;
+        }
+        // 最后的表达式就是返回值
+        lhs % rhs == 0
+    }
+
+    fn fizzbuzz(n: u32) -> String {
+        if is_divisible_by(n, 15) {
+            "fizzbuzz".to_string()
+        } else if is_divisible_by(n, 3) {
+            "fizz".to_string()
+        } else if is_divisible_by(n, 5) {
+            "buzz".to_string()
+        } else {
+            n.to_string()
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -9,6 +46,7 @@ mod tests {
 
         /*定义函数*/
         // 一个返回布尔值的函数
+        #[allow(clippy::manual_is_multiple_of)]
         fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
             // 边界情况，提前返回
             if rhs == 0 {
@@ -20,6 +58,7 @@ mod tests {
         }
 
         // 一个 “不” 返回值的函数。实际上会返回一个单元类型 `()`。
+        #[allow(clippy::unused_unit)]
         fn fizzbuzz(n: u32) -> () {
             if is_divisible_by(n, 15) {
                 println!("fizzbuzz");
@@ -41,4 +80,4 @@ mod tests {
 
         // 即，一个函数签名中没有返回值，其实表明其返回一个()类型
     }
-}
\ No newline at end of file
+}
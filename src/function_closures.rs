@@ -15,6 +15,32 @@
     - 有能力捕获外部环境的变量。
  */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures", about: "用函数和闭包分别实现自增", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    fn function(i: i32) -> i32 {
+        i + 1
+    }
+
+    // 类型标注和`{}`对闭包来说都是可选的
+    let closure_annotated = |i: i32| -> i32 { i + 1 };
+    let closure_inferred = |i| i + 1;
+
+    let i = 1024;
+    writeln!(out, "function: {}", function(i))?;
+    writeln!(out, "closure_annotated: {}", closure_annotated(i))?;
+    writeln!(out, "closure_inferred: {}", closure_inferred(i))?;
+
+    // 没有参数的闭包，返回类型是推导出来的
+    let closure_one = || 1;
+    writeln!(out, "closure returning one: {}", closure_one())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -29,6 +55,7 @@ mod tests {
         // 闭包是匿名的，这里我们将它们绑定到引用。
         // 类型标注和函数的一样，不过类型标注和使用 `{}` 来围住函数体都是可选的。
         // 这些匿名函数（nameless function）被赋值给合适地命名的变量。
+        #[allow(unused_variables)]
         let closure_annotated = |i: i32| -> i32 { i + 1 };
         // 手动设定闭包类型时，赋值号右侧内容可以省略 类型标注 和 ->
         // 等价于下面
@@ -51,4 +78,4 @@ mod tests {
         let closure_one = || 1024 + 100; // 省略使用 `{}`
         println!("{}", closure_one());
     }
-}
\ No newline at end of file
+}
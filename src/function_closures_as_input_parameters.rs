@@ -19,6 +19,41 @@
       注意：
         反过来就不再成立：如果参数的类型说明是 Fn，那么不允许该闭包通过 &mut T 或 T 捕获变量。
 */
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures_as_input_parameters", about: "以Fn、FnMut和FnOnce约束闭包参数", run: demo },
+];
+
+// 闭包只会被调用一次，可以通过值捕获变量
+fn apply<F: FnOnce() -> String>(f: F) -> String {
+    f()
+}
+
+// 闭包可能修改捕获的变量
+fn apply_twice<F: FnMut()>(mut f: F) {
+    f();
+    f();
+}
+
+fn apply_to_3<F: Fn(i32) -> i32>(f: F) -> i32 {
+    f(3)
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut my_str = "Bitcoin".to_owned();
+    apply_twice(|| my_str.push('!'));
+    writeln!(out, "after FnMut: {}", my_str)?;
+
+    // 把my_str整个交给闭包，闭包返回它，因此只能是FnOnce
+    let owned = apply(move || my_str);
+    writeln!(out, "after FnOnce: {}", owned)?;
+
+    writeln!(out, "apply_to_3: {}", apply_to_3(|n| n * 10))
+}
+
 #[cfg(test)]
 mod tests {
     //    试着分别用一用 Fn、FnMut 和 FnOnce，看看会发生什么：
@@ -80,4 +115,4 @@ mod tests {
 
         println!("{}", apply_to_3(closure_double)); // 30
     }
-}
\ No newline at end of file
+}
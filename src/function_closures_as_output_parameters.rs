@@ -16,6 +16,35 @@
     这是必须的，因为在函数退出时，任何通过引用的捕获都被丢弃，在闭包中留下无效的引用。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures_as_output_parameters", about: "用Box<dyn Fn>返回闭包", run: demo },
+];
+
+// 必须用move把text交给闭包，函数返回后它就被析构了
+fn create_fn() -> Box<dyn Fn() -> String> {
+    let text = "Fn()".to_owned();
+    Box::new(move || text.clone())
+}
+
+fn create_fnmut() -> Box<dyn FnMut() -> String> {
+    let mut calls = 0;
+    Box::new(move || {
+        calls += 1;
+        format!("FnMut() called {} times", calls)
+    })
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let closure_fn = create_fn();
+    let mut closure_fnmut = create_fnmut();
+    writeln!(out, "{}", closure_fn())?;
+    writeln!(out, "{}", closure_fnmut())?;
+    writeln!(out, "{}", closure_fnmut())
+}
+
 #[cfg(test)]
 mod tests {
     // 举个例子：
@@ -46,4 +75,4 @@ mod tests {
         create_fn()();
         create_fnmut()();
     }
-}
\ No newline at end of file
+}
@@ -11,6 +11,32 @@
 闭包更倾向于通过引用来捕获变量，并且只在被要求时才使用其他手段。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures_capturing", about: "闭包通过引用、可变引用和值捕获变量", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let color = String::from("green");
+    // 通过引用捕获color
+    let describe = || format!("color: {}", color);
+    writeln!(out, "{}", describe())?;
+
+    let mut count = 0;
+    // 通过可变引用捕获count，闭包本身也要是mut
+    let mut inc = || count += 1;
+    inc();
+    inc();
+    writeln!(out, "count: {}", count)?;
+
+    // move强制闭包取得v的所有权
+    let v: Vec<i32> = (1..=3).collect();
+    let contains = move |number: &i32| v.contains(number);
+    writeln!(out, "contains 1: {}, contains 100: {}", contains(&1), contains(&100))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -45,6 +71,7 @@ mod tests {
         // 变量count通过打印证明其值确实被修改了
 //        println!("{}", count);  //2
 
+        #[allow(unused_variables)]
         let reborrow = &mut count;
         // 下面在执行闭包会报错，因为count的可变借用给reborrow了，再调用closure_inc闭包时候，无法再使用
         // 保存在闭包中的&mut count。
@@ -74,6 +101,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_function_closures_capturing_3() {
         /*
         在竖线 | 之前使用 move 会强制闭包取得被捕获变量的所有权
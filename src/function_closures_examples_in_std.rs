@@ -1,6 +1,25 @@
 // 列出几个标准库中使用闭包的例子
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures_examples_in_std", about: "Iterator::any与Iterator::find", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let v = vec![1, 2, 3];
+    // `iter()`举出`&i32`，any的闭包收到的也是`&i32`
+    writeln!(out, "any even: {}", v.iter().any(|&x| x % 2 == 0))?;
+    // find的闭包收到的是元素的引用，这里是`&&i32`
+    writeln!(out, "find == 2: {:?}", v.iter().find(|&&x| x == 2))?;
+    // `into_iter()`举出`i32`
+    writeln!(out, "position > 1: {:?}", v.into_iter().position(|x| x > 1))
+}
+
 mod tests {
     #[test]
+    #[allow(array_into_iter, clippy::into_iter_on_ref, clippy::manual_contains, clippy::useless_vec)]
     fn test_function_closures_iter_any() {
 
 //        Iterator::any是一个函数，若传给它一个迭代器（iterator），
@@ -46,6 +65,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(array_into_iter, clippy::into_iter_on_ref, clippy::useless_vec)]
     fn test_function_closures_iter_find() {
         // Iterator::find是一个函数，在传给它一个迭代器时，将用`Option类型`返回`第一个`满足谓词的元素。
         /*
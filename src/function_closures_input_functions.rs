@@ -3,6 +3,27 @@
     确实可以！如果你声明一个接受闭包作为参数的函数，那么任何满足该闭包的trait约束的`函数`都可以作为其参数。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures_input_functions", about: "满足Fn约束的函数也可以作为参数", run: demo },
+];
+
+fn call_func<F: Fn() -> &'static str>(f: F) -> &'static str {
+    f()
+}
+
+fn function() -> &'static str {
+    "function!!!"
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let closure = || "closure!!!";
+    writeln!(out, "{}", call_func(closure))?;
+    writeln!(out, "{}", call_func(function))
+}
+
 #[cfg(test)]
 mod tests {
     // 定义一个函数，可以接受一个由 `Fn` 限定的泛型 `F` 参数并调用它。
@@ -33,4 +54,4 @@ mod tests {
         // 通过函数变量调用函数
         call_func(function);
     }
-}
\ No newline at end of file
+}
@@ -17,6 +17,26 @@ fn apply<F>(f: F) where
     事实上，指明为该结构体实现的是Fn、FnMut或FnOnce中的哪种trait，对于约束该结构体的类型而言就已经足够了。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_closures_type_anonymity", about: "闭包的类型是匿名的，只能用泛型接收", run: demo },
+];
+
+// `F`必须是泛型的，约束为Fn就足以描述闭包的匿名类型
+fn apply<F>(f: F) -> String where
+    F: Fn() -> String {
+    f()
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let x = 1024;
+    // 捕获`x`到匿名类型中，并为它实现`Fn`
+    let closure_print = || format!("captured {}", x);
+    writeln!(out, "{}", apply(closure_print))
+}
+
 #[cfg(test)]
 mod tests {
     // `F` 必须为一个没有输入参数和返回值的闭包实现 `Fn`，这和对 `closure_print` 的要求恰好一样。
@@ -34,4 +54,4 @@ mod tests {
         let closure_print = || println!("{}", x);
         apply(closure_print);
     }
-}
\ No newline at end of file
+}
@@ -2,8 +2,39 @@
 // 指那些输入一个或多个函数，并且/或者产生一个更有用的函数的函数。
 // HOF和惰性迭代器（lazy iterator）给Rust带来了函数式（functional）编程的风格。
 
+use std::io::{self, Write};
+use crate::runner::Demo;
 
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "function_higher_order_functions", about: "用命令式和函数式两种写法求奇数平方和", run: demo },
+];
 
+fn is_odd(number: u32) -> bool {
+    number % 2 == 1
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let upper = 1000;
+
+    // 命令式的写法
+    let mut acc = 0;
+    for n in 0.. {
+        let n_squared = n * n;
+        if n_squared > upper {
+            break;
+        } else if is_odd(n_squared) {
+            acc += n_squared;
+        }
+    }
+    writeln!(out, "imperative style: {}", acc)?;
+
+    // 函数式的写法
+    let sum: u32 = (0..).map(|n| n * n)
+        .take_while(|&n| n < upper)
+        .filter(|&n| is_odd(n))
+        .sum();
+    writeln!(out, "functional style: {}", sum)
+}
 
 #[cfg(test)]
 mod tests {
@@ -13,6 +44,7 @@ mod tests {
 
     // Find the sum of all the squared odd numbers under 1000
     #[test]
+    #[allow(clippy::unnecessary_fold)]
     fn test_function_higher_order_functions() {
         let upper = 1000;
 
@@ -43,4 +75,4 @@ mod tests {
 
         // 注：Option和迭代器都实现了不少高阶函数。
     }
-}
\ No newline at end of file
+}
@@ -11,6 +11,10 @@
 
 
 // 例如定义一个泛型函数generics_function，它可接受类型为T的任何参数arg：
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+#[allow(dead_code, unused_variables)]
 fn generics_function<T>(arg: T) {
     println!("generics_function");
 }
@@ -18,6 +22,27 @@ fn generics_function<T>(arg: T) {
 // 因为使用了泛型类型参数 <T>，所以这里的 (arg: T) 中的 T 就是泛型类型。
 // 即使 T 在之前被定义为 struct，这里的 T 仍然代表泛型。
 
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics", about: "具体类型与泛型类型", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 具体类型 `S`
+    #[derive(Debug)]
+    struct S;
+    // `Single`第一次使用`S`之前没有写`<S>`，所以它是具体类型
+    #[derive(Debug)]
+    struct Single(S);
+    // `<T>`出现在第一次使用`T`之前，所以`SingleGen`是泛型类型
+    #[derive(Debug)]
+    struct SingleGen<T>(T);
+
+    writeln!(out, "{:?}", Single(S))?;
+    // 类型参数可以显式指定，也可以隐式推断
+    let char_object: SingleGen<char> = SingleGen('a');
+    writeln!(out, "{:?} {:?} {:?}", char_object, SingleGen(S), SingleGen(1024))
+}
+
 #[cfg(test)]
 mod tests {
     // 泛型语法的使用的例子
@@ -53,4 +78,4 @@ mod tests {
 
         println!("{:?} {:?} {:?} {:?}", char_object, s_object, i, str_object);
     }
-}
\ No newline at end of file
+}
@@ -25,46 +25,64 @@
     后面会看到，关联类型恰好提供了这样的功能。
 */
 
-#[cfg(test)]
-mod tests {
-    struct Container(i32, i32);
-
-    // 定义trait。
-    // 这个 trait 能检查给定的 2 个项是否储存于容器中
-    // 并且能够获得容器的第一个或最后一个值。
-    trait Contains<A, B> {
-        // 显式地需要 `A` 和 `B`
-        fn contains(&self, _: &A, _: &B) -> bool;
-        // 未显式地需要 `A` 或 `B`
-        fn first(&self) -> i32;
-        fn last(&self) -> i32;
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+struct Container(i32, i32);
+
+// 定义trait。
+// 这个 trait 能检查给定的 2 个项是否储存于容器中
+// 并且能够获得容器的第一个或最后一个值。
+trait Contains<A, B> {
+    // 显式地需要 `A` 和 `B`
+    fn contains(&self, _: &A, _: &B) -> bool;
+    // 未显式地需要 `A` 或 `B`
+    fn first(&self) -> i32;
+    fn last(&self) -> i32;
+}
+
+impl Contains<i32, i32> for Container {
+    // 如果存储的数字和给定的相等则为真
+    fn contains(&self, n1: &i32, n2: &i32) -> bool {
+        (&self.0 == n1) && (&self.1 == n2)
     }
 
-    impl Contains<i32, i32> for Container {
-        // 如果存储的数字和给定的相等则为真
-        fn contains(&self, n1: &i32, n2: &i32) -> bool {
-            (&self.0 == n1) && (&self.1 == n2)
-        }
-
-        // 得到第一个数字
-        fn first(&self) -> i32 {
-            self.0
-        }
-
-        // 得到最后一个数字
-        fn last(&self) -> i32 {
-            self.1
-        }
+    // 得到第一个数字
+    fn first(&self) -> i32 {
+        self.0
     }
 
-    // 容器 `C` 就包含了 `A` 和 `B` 类型。difference函数的参数就是容器C。
-    // 鉴于此，还需要重新指出 `A` 和 `B` 显得很麻烦。
-    // (where子句中有用到A和B,所以在函数名后要显式写上A和B)
-    fn difference<A, B, C>(container: &C) -> i32 where
-    // where子句
-        C: Contains<A, B> {
-        container.last() - container.first()
+    // 得到最后一个数字
+    fn last(&self) -> i32 {
+        self.1
     }
+}
+
+// 容器 `C` 就包含了 `A` 和 `B` 类型。difference函数的参数就是容器C。
+// 鉴于此，还需要重新指出 `A` 和 `B` 显得很麻烦。
+// (where子句中有用到A和B,所以在函数名后要显式写上A和B)
+fn difference<A, B, C>(container: &C) -> i32 where
+// where子句
+    C: Contains<A, B> {
+    container.last() - container.first()
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_associated_items_problem", about: "不用关联类型时，泛型trait的使用者要写出全部类型参数", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let (n1, n2) = (3, 10);
+    let container = Container(n1, n2);
+    writeln!(out, "Does container contain {} and {}: {}", n1, n2, container.contains(&n1, &n2))?;
+    writeln!(out, "First number: {}", container.first())?;
+    writeln!(out, "Last number: {}", container.last())?;
+    writeln!(out, "The difference is: {}", difference(&container))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_generics_associated_items_problem() {
@@ -77,4 +95,4 @@ mod tests {
 
         println!("{}", difference(&container)); // 20-10=10
     }
-}
\ No newline at end of file
+}
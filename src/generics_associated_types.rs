@@ -17,51 +17,69 @@
     fn difference<C: Contains>(container: &C) -> i32 { ... }
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 // 利用关联类型重写generics_associated_items_problem.rs文件中的demo
-#[cfg(test)]
-mod tests {
-    struct Container(i32, i32);
-
-    // 这个 trait 检查给定的 2 个项是否储存于容器中
-    // 并且能够获得容器的第一个或最后一个值。
-    trait Contains {
-        // 在这里定义可以被方法使用的泛型类型。即关联类型
-        type A;
-        type B;
-
-        fn contains(&self, _: &Self::A, _: &Self::B) -> bool;
-        // 得到第一个数字
-        // (因为difference函数中涉及到container.last() - container.first()，
-        // 所有在定义trait Contains时候first和last方法返回类型为i32，
-        // 而不是Self::A和Self::B)
-        fn first(&self) -> i32;
-        fn last(&self) -> i32;
-    }
+struct Container(i32, i32);
 
-    impl Contains for Container {
-        // 在impl中需要指出 `A` 和 `B` 是什么类型。
-        // 如果 `input`（输入）类型为 `Container(i32, i32)`，
-        // 那么 `output`（输出）类型会被确定为 `i32` 和 `i32`。
-        type A = i32;
-        type B = i32;
+// 这个 trait 检查给定的 2 个项是否储存于容器中
+// 并且能够获得容器的第一个或最后一个值。
+trait Contains {
+    // 在这里定义可以被方法使用的泛型类型。即关联类型
+    type A;
+    type B;
 
-        fn contains(&self, n1: &Self::A, n2: &Self::B) -> bool {
-            (n1 == &self.0) && (n2 == &self.1)
-        }
+    fn contains(&self, _: &Self::A, _: &Self::B) -> bool;
+    // 得到第一个数字
+    // (因为difference函数中涉及到container.last() - container.first()，
+    // 所有在定义trait Contains时候first和last方法返回类型为i32，
+    // 而不是Self::A和Self::B)
+    fn first(&self) -> i32;
+    fn last(&self) -> i32;
+}
+
+impl Contains for Container {
+    // 在impl中需要指出 `A` 和 `B` 是什么类型。
+    // 如果 `input`（输入）类型为 `Container(i32, i32)`，
+    // 那么 `output`（输出）类型会被确定为 `i32` 和 `i32`。
+    type A = i32;
+    type B = i32;
 
-        fn first(&self) -> i32 {
-            self.0
-        }
+    fn contains(&self, n1: &Self::A, n2: &Self::B) -> bool {
+        (n1 == &self.0) && (n2 == &self.1)
+    }
 
-        fn last(&self) -> i32 {
-            self.1
-        }
+    fn first(&self) -> i32 {
+        self.0
     }
 
-    // 这是不在需要将A和B的显式的写出来
-    fn difference<C: Contains>(container: &C) -> i32 {
-        container.last() - container.first()
+    fn last(&self) -> i32 {
+        self.1
     }
+}
+
+// 这是不在需要将A和B的显式的写出来
+fn difference<C: Contains>(container: &C) -> i32 {
+    container.last() - container.first()
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_associated_types", about: "用关联类型简化泛型trait的约束", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let (n1, n2) = (3, 10);
+    let container = Container(n1, n2);
+    writeln!(out, "Does container contain {} and {}: {}", n1, n2, container.contains(&n1, &n2))?;
+    writeln!(out, "First number: {}", container.first())?;
+    writeln!(out, "Last number: {}", container.last())?;
+    writeln!(out, "The difference is: {}", difference(&container))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_generics_associated_types() {
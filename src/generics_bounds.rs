@@ -19,39 +19,56 @@
 */
 
 //  约束的另一个作用是泛型的实例可以访问作为约束的 trait 的方法。例如：
-#[cfg(test)]
-mod tests {
-    // 这个 trait 用来实现打印标记：`{:?}`。
-    use std::fmt::Debug;
 
-    // 新建一个trait
-    trait HasArea {
-        fn area(&self) -> f64;
-    }
+// 这个 trait 用来实现打印标记：`{:?}`。
+use std::fmt::Debug;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    #[derive(Debug)]
-    struct Rectangle {
-        length: f64,
-        height: f64,
-    }
+// 新建一个trait
+/// 可以计算面积的图形
+pub trait HasArea {
+    fn area(&self) -> f64;
+}
 
-    impl HasArea for Rectangle {
-        fn area(&self) -> f64 {
-            self.length * self.height
-        }
-    }
+/// 由长和高确定的矩形
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    pub length: f64,
+    pub height: f64,
+}
 
-    // 泛型 `T` 必须实现 `Debug` 。
-    // 只要满足这点，无论什么类型都可以让下面函数正常工作。
-    fn print_debug<T: Debug>(t: &T) {
-        println!("{:?}", t);
+impl HasArea for Rectangle {
+    fn area(&self) -> f64 {
+        self.length * self.height
     }
+}
 
-    // `T` 必须实现 `HasArea`。
-    // 任意符合该约束的泛型的实例都可访问 `HasArea` 的 `area` 函数
-    fn area<T: HasArea>(t: &T) -> f64 {
-        t.area()
-    }
+// 泛型 `T` 必须实现 `Debug` 。
+// 只要满足这点，无论什么类型都可以让下面函数正常工作。
+pub fn print_debug<T: Debug>(t: &T) {
+    println!("{:?}", t);
+}
+
+// `T` 必须实现 `HasArea`。
+// 任意符合该约束的泛型的实例都可访问 `HasArea` 的 `area` 函数
+pub fn area<T: HasArea>(t: &T) -> f64 {
+    t.area()
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_bounds", about: "用trait约束泛型，并调用约束中的方法", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let rectangle = Rectangle { length: 3.0, height: 4.0 };
+    writeln!(out, "{:?}", rectangle)?;
+    writeln!(out, "Area: {}", area(&rectangle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rectangle, print_debug, area};
 
     #[test]
     fn test_generics_bounds() {
@@ -70,4 +87,4 @@ mod tests {
     }
 
     // 注： 某些情况下也可使用 where 分句来形成约束，这拥有更好的表现力。
-}
\ No newline at end of file
+}
@@ -2,27 +2,42 @@
     约束的工作机制会产生这样的效果：即使一个 trait 不包含任何功能，你仍然可以用它作为约束。
     标准库中的 Eq 和 Ord 就是这样的 trait。
 */
-#[cfg(test)]
-mod tests {
-    struct Cardinal;
 
-    struct BlueJay;
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    struct Turkey;
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_empty_bounds", about: "空trait也可以作为约束", run: demo },
+];
+
+struct Cardinal;
+
+struct BlueJay;
+
+trait Red {}
 
-    trait Red {}
+trait Blue {}
 
-    trait Blue {}
+impl Red for Cardinal {}
 
-    impl Red for Cardinal {}
+impl Blue for BlueJay {}
 
-    impl Blue for BlueJay {}
+// 这些trait内部是空的，但只有实现了它们的类型才能调用下面的函数
+fn red<T: Red>(_: &T) -> &'static str { "red" }
 
-    // 这些函数只对实现了相应的 trait 的类型有效。
-    // 事实上这些 trait 内部是空的，但这没有关系。
-    fn red<T: Red>(_: &T) -> &str { "red" }
+fn blue<T: Blue>(_: &T) -> &'static str { "blue" }
 
-    fn blue<T: Blue>(_: &T) -> &str { "blue" }
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "A cardinal is {}", red(&Cardinal))?;
+    writeln!(out, "A blue jay is {}", blue(&BlueJay))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    struct Turkey;
 
     #[test]
     fn test_generics_empty_bounds() {
@@ -35,4 +50,4 @@ mod tests {
 //        println!("{}", red(&turkey));
         // 编译错误
     }
-}
\ No newline at end of file
+}
@@ -7,6 +7,31 @@
 
 //  调用函数时，使用显式指定的类型参数会像是这样：fun::<A, B, ...>()。
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_function", about: "泛型函数与显式指定类型参数", run: demo },
+];
+
+// 关于`T`的泛型函数
+fn type_name_of<T>(_value: &T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+// 返回类型是泛型的函数，调用时往往要显式指定类型参数
+fn parse_or_default<T: std::str::FromStr + Default>(text: &str) -> T {
+    text.parse().unwrap_or_default()
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}", type_name_of(&1024))?;
+    writeln!(out, "{}", type_name_of(&'a'))?;
+    // 用turbofish显式指定类型参数
+    writeln!(out, "{}", parse_or_default::<i32>("42"))?;
+    writeln!(out, "{}", parse_or_default::<f64>("not a number"))
+}
+
 #[cfg(test)]
 mod tests {
     struct A;
@@ -55,4 +80,4 @@ mod tests {
         // 注：泛型函数的指定是在函数的定义位置，在调用位置上其实是不需要做任何特殊指定的
         // 因为编译器可以隐式指定。
     }
-}
\ No newline at end of file
+}
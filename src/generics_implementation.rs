@@ -16,6 +16,43 @@
     // `<T>` 必须在类型之前写出来，以使类型 `T` 代表泛型。
     impl <T> GenericVal<T>{}
 */
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_implementation", about: "为泛型类型实现方法", run: demo },
+];
+
+/// 具体类型
+pub struct Val {
+    val: f64,
+}
+
+/// 泛型类型
+pub struct GenVal<T> {
+    gen_val: T,
+}
+
+impl Val {
+    pub fn value(&self) -> &f64 {
+        &self.val
+    }
+}
+
+// `<T>`必须写在impl之后，`T`才代表泛型
+impl<T> GenVal<T> {
+    pub fn value(&self) -> &T {
+        &self.gen_val
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let x = Val { val: 3.0 };
+    let y = GenVal { gen_val: 3i32 };
+    writeln!(out, "{}, {}", x.value(), y.value())
+}
+
 #[cfg(test)]
 mod tests {
     //  具体类型 `S`
@@ -2,6 +2,36 @@
     多重约束（multiple bounds）可以用 `+` 连接。
     和平常一样，类型之间使用 `,` 隔开
 */
+
+use std::fmt::{Debug, Display};
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_multiple_bounds", about: "用+连接多重约束", run: demo },
+];
+
+// 要求参数同时实现Debug和Display
+fn compare_prints<T: Debug + Display>(out: &mut dyn Write, t: &T) -> io::Result<()> {
+    writeln!(out, "Debug: `{:?}`", t)?;
+    writeln!(out, "Display: `{}`", t)
+}
+
+// T和U各自实现Debug
+fn compare_types<T: Debug, U: Debug>(out: &mut dyn Write, t: &T, u: &U) -> io::Result<()> {
+    writeln!(out, "t: `{:?}`", t)?;
+    writeln!(out, "u: `{:?}`", u)
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let string = "words";
+    let array = [1, 2, 3];
+    let vec = vec![1, 2, 3];
+    compare_prints(out, &string)?;
+    // 数组没有实现Display，只能用于compare_types
+    compare_types(out, &array, &vec)
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::{Debug, Display};
@@ -32,4 +62,4 @@ mod tests {
         // vec和数组都实现了Debug
         multiple_types(&arr, &vec);
     }
-}
\ No newline at end of file
+}
@@ -5,31 +5,49 @@
 
 //  比如说，实现一个 “年龄认证” 函数，它要求输入必须是 Years 类型。
 
-#[cfg(test)]
-mod tests {
-    // 下面的Years和Days就是newtype 惯用法
-    // 为不同种类的数据分别定义新的类型(struct)，原先的数据类型都是i64.
-    struct Years(i64);
+// 下面的Years和Days就是newtype 惯用法
+// 为不同种类的数据分别定义新的类型(struct)，原先的数据类型都是i64.
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    struct Days(i64);
+struct Years(i64);
 
-    impl Years {
-        // 单位年转换为天
-        pub fn to_days(&self) -> Days {
-            Days(self.0 * 365)
-        }
-    }
+struct Days(i64);
 
-    impl Days {
-        pub fn to_years(&self) -> Years {
-            // 地板除
-            Years(self.0 / 365)
-        }
+impl Years {
+    // 单位年转换为天
+    pub fn to_days(&self) -> Days {
+        Days(self.0 * 365)
     }
+}
 
-    fn old_enough(age: &Years) -> bool {
-        age.0 >= 18
+impl Days {
+    pub fn to_years(&self) -> Years {
+        // 地板除
+        Years(self.0 / 365)
     }
+}
+
+fn old_enough(age: &Years) -> bool {
+    age.0 >= 18
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_new_type_idiom", about: "用newtype区分年和天", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let age = Years(25);
+    let age_days = age.to_days();
+    writeln!(out, "Old enough {}", old_enough(&age))?;
+    writeln!(out, "Old enough {}", old_enough(&age_days.to_years()))?;
+    // old_enough(&age_days)无法编译：Days不是Years
+    writeln!(out, "{} years are {} days", age.0, age_days.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_generics_new_type_idiom() {
@@ -42,4 +60,4 @@ mod tests {
         // 编译报错
 //        println!("{}", old_enough(&age_days));
     }
-}
\ No newline at end of file
+}
@@ -3,6 +3,41 @@
     在这里定义了一个 trait，它把 Drop trait 作为泛型方法重实现了，可以 drop（丢弃）`调用者本身`和`一个输入参数`。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_trait", about: "泛型trait：一次丢弃调用者和参数", run: demo },
+];
+
+/// `T`的泛型trait：获得调用者和一个参数的所有权，并把两者都丢弃
+pub trait DoubleDrop<T> {
+    fn double_drop(self, _: T);
+}
+
+// 对任意调用者类型`U`和任意参数类型`T`实现
+impl<T, U> DoubleDrop<T> for U {
+    fn double_drop(self, _: T) {}
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 析构时写下自己的名字
+    struct Noisy<'a>(&'static str, &'a std::cell::RefCell<Vec<&'static str>>);
+
+    impl Drop for Noisy<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    let dropped = std::cell::RefCell::new(vec![]);
+    let empty = Noisy("empty", &dropped);
+    let null = Noisy("null", &dropped);
+    empty.double_drop(null);
+    // 这里empty和null都已经被释放
+    writeln!(out, "dropped: {:?}", dropped.into_inner())
+}
+
 #[cfg(test)]
 mod tests {
     // 不可复制的类型。
@@ -29,6 +64,7 @@ mod tests {
     }
 
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct A {
         i: i32,
     }
@@ -48,4 +84,4 @@ mod tests {
 //        println!("{:?}", null);
 
     }
-}
\ No newline at end of file
+}
@@ -16,6 +16,33 @@
            下面例子中的 impl 如果不用 where 从句，就无法直接表达：
 */
 
+use std::fmt::Debug;
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "generics_where_clauses", about: "用where分句约束任意类型", run: demo },
+];
+
+/// 把自己放进Option中格式化
+pub trait DebugInOption {
+    fn debug_in_option(self) -> String;
+}
+
+// 约束的是`Option<T>: Debug`而不是`T`本身，不用where分句就无法直接表达
+impl<T> DebugInOption for T where
+    Option<T>: Debug {
+    fn debug_in_option(self) -> String {
+        format!("{:?}", Some(self))
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let vec = vec![1, 2, 3];
+    writeln!(out, "{}", vec.debug_in_option())?;
+    writeln!(out, "{}", "text".debug_in_option())
+}
+
 #[cfg(test)]
 mod tests {
     // 针对情况2的例子：
@@ -47,4 +74,4 @@ mod tests {
 
         vec.print_in_option();
     }
-}
\ No newline at end of file
+}
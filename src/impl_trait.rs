@@ -23,16 +23,55 @@
 
     目前版本中， Rust只支持返回“具体类型”，而不能返回一个 trait。
 */
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+// Rust函数无法直接返回一个闭包。
+// 因为闭包的类型是编译器自动生成的一个`匿名类型`,无法在函数的返回类型中手工指定。
+// 所以返回一个闭包一定要 “装箱”到堆内存中，然后把胖指针返回回去，这样是有性能开销的 。
+
+fn multiply_v1(num: i32) -> Box<dyn Fn(i32) -> i32> {
+    // 闭包装箱
+    Box::new(move |x| x * num)
+}
+
+/*
+    为了不装箱也能返回闭包（见测试中的multiply_v2）， 提出了impl trait这个方案。
+    此方案引人了一个新的语法，可以表达一个不用装箱的匿名类型，
+*/
+fn multiply_v3(num: i32) -> impl Fn(i32) -> i32 {
+    move |x| x * num
+}
+
+/*
+    这里的impl Fn(i32)->i32 表示：
+    这个返回类型，虽然我们不知道它的具体名字(匿名标量)，
+    但是知道它满足 Fn(size)->isize 这个 trait 的约束 。
+
+    impl trait与泛型函数的主要区别是：
+    泛型函数的类型参数是函数的`调用者`指定的 ;
+    impl trait 的具体类型是函数的`实现体`指定的。
+*/
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "impl_trait", about: "用impl Trait返回闭包和迭代器", run: demo },
+];
+
+// 头部注释中的例子：返回一个不用装箱的迭代器
+fn hundreds(n: u32) -> impl Iterator<Item = u32> {
+    (0..n).map(|x| x * 100)
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "boxed: {}", multiply_v1(10)(5))?;
+    writeln!(out, "impl Fn: {}", multiply_v3(10)(5))?;
+    writeln!(out, "impl Iterator: {:?}", hundreds(4).collect::<Vec<_>>())
+}
+
 #[cfg(test)]
 mod tests {
-    // Rust函数无法直接返回一个闭包。
-    // 因为闭包的类型是编译器自动生成的一个`匿名类型`,无法在函数的返回类型中手工指定。
-    // 所以返回一个闭包一定要 “装箱”到堆内存中，然后把胖指针返回回去，这样是有性能开销的 。
-
-    fn multiply_v1(num: i32) -> Box<dyn Fn(i32) -> i32> {
-        // 闭包装箱
-        Box::new(move |x| x * num)
-    }
+    use super::*;
 
     #[test]
     fn test_impl_trait_v1() {
@@ -56,24 +95,6 @@ mod tests {
 //        let f: dyn Fn(i32) -> i32 = multiply_v2(10);
     }
 
-    /*
-        为了解决上面的问题， 提出了impl trait这个方案。
-        此方案引人了一个新的语法，可以表达一个不用装箱的匿名类型，
-    */
-    fn multiply_v3(num: i32) -> impl Fn(i32) -> i32 {
-        move |x| x * num
-    }
-
-    /*
-        这里的impl Fn(i32)->i32 表示：
-        这个返回类型，虽然我们不知道它的具体名字(匿名标量)，
-        但是知道它满足 Fn(size)->isize 这个 trait 的约束 。
-
-        impl trait与泛型函数的主要区别是：
-        泛型函数的类型参数是函数的`调用者`指定的 ;
-        impl trait 的具体类型是函数的`实现体`指定的。
-    */
-
     // 用trait实现类作为函数返回值类型例子
     #[test]
     fn test_impl_trait_v3() {
@@ -88,6 +109,7 @@ mod tests {
     struct S;
 
     impl S {
+        #[allow(clippy::new_ret_no_self)]
         fn new() -> impl Trait1 {
             S {}
         }
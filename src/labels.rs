@@ -1,10 +1,34 @@
 //It's possible to break or continue outer loops when dealing with nested loops.
 // In these cases, the loops must be annotated with some 'label, and the label must be passed to the break/continue statement.
-#![allow(unreachable_code)]
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "labels", about: "用标签跳出或继续外层循环", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    'outer: for i in 0..3 {
+        for j in 0..3 {
+            if j > i {
+                // 直接进入外层循环的下一轮
+                continue 'outer;
+            }
+            if i == 2 {
+                writeln!(out, "break 'outer at ({}, {})", i, j)?;
+                break 'outer;
+            }
+            writeln!(out, "({}, {})", i, j)?;
+        }
+    }
+    writeln!(out, "Exited the outer loop")
+}
 
 #[cfg(test)]
 mod tests {
     #[test]
+    #[allow(unreachable_code, unused_labels, clippy::never_loop)]
     fn test_labels() {
         'outer: loop {
             println!("Entered the outer loop");
@@ -17,4 +41,4 @@ mod tests {
         }
         println!("Exited the outer loop");
     }
-}
\ No newline at end of file
+}
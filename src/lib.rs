@@ -0,0 +1,198 @@
+//! Rust 练习合集（rust-exercise）
+//!
+//! 每个顶层模块对应一个主题，模块内的注释说明了相关的语言特性，
+//! 可复用的类型和函数以`pub`导出，便于在其他crate中`use`。
+//!
+//! 注：示例代码中有不少刻意保留的`未使用`项（用于演示可见性、析构顺序等），
+//! 它们各自用`#[allow]`标注，不在crate级别关闭lint。
+
+// 声明主题模块，同时把每个模块的`DEMOS`收集到TOPICS中，
+// 新增主题时只需在下面的列表中加一行，并在模块中定义`DEMOS`
+macro_rules! topics {
+    ($($(#[$attr:meta])* $name:ident;)*) => {
+        $($(#[$attr])* pub mod $name;)*
+
+        /// 各主题模块登记的演示，由runner::registry()汇总
+        pub(crate) const TOPICS: &[&[runner::Demo]] = &[$($name::DEMOS),*];
+    };
+}
+
+topics! {
+    /// 变量绑定
+    variable_bindings;
+    /// 类型转换与字面量
+    types;
+    /// From/Into、ToString/FromStr 等类型转换
+    conversion;
+    /// 常量 const 与 static
+    constants;
+    /// 用 enum 实现的链表
+    enum_linked_list;
+    /// 用 Rc 共享表尾的不可变（持久化）链表
+    enum_linked_list_persistent;
+    /// 双向链表实现的双端队列，带游标
+    linked_list_deque;
+    /// 枚举
+    enums;
+    /// 结构体与元组的练习
+    tuples;
+    /// 结构体
+    structures;
+    /// 数组与切片
+    array_and_slice;
+    /// 为 Vec 类型的结构体实现 Display
+    display_vec;
+    /// 格式化输出
+    display_formatting;
+    /// Display 与 Debug
+    display;
+    /// 元组练习：Matrix
+    tuples_activity;
+    /// 循环标签
+    labels;
+    /// while 循环
+    while_control;
+    /// for 循环与迭代器
+    for_and_iterator;
+    /// match 解构元组
+    match_tuple;
+    /// match 解构枚举
+    match_enum;
+    /// match 解构指针和引用
+    match_pointer_and_reference;
+    /// 解构结构体
+    destructure_struct;
+    /// match 守卫
+    match_guard;
+    /// match 绑定
+    match_binding;
+    /// if let
+    match_if_let;
+    /// while let
+    match_while_let;
+    /// 函数
+    function;
+    /// 方法
+    method;
+    /// 闭包
+    function_closures;
+    /// 闭包捕获
+    function_closures_capturing;
+    /// 闭包作为输入参数
+    function_closures_as_input_parameters;
+    /// 闭包的类型匿名性
+    function_closures_type_anonymity;
+    /// 函数作为输入参数
+    function_closures_input_functions;
+    /// 闭包作为输出参数
+    function_closures_as_output_parameters;
+    /// 标准库中使用闭包的例子
+    function_closures_examples_in_std;
+    /// 高阶函数
+    function_higher_order_functions;
+    /// 发散函数
+    function_diverging_functions;
+    /// 模块的可见性
+    module_visibility;
+    /// 结构体字段的可见性
+    module_struct_visibility;
+    /// use 声明
+    module_use_declaration;
+    /// super 与 self
+    module_super_and_self;
+    /// 模块的文件层次结构
+    module_file_hierarchy;
+    /// dead_code 属性
+    attribute_dead_code;
+    /// cfg 条件编译
+    attribute_cfg;
+    /// 泛型
+    generics;
+    /// 泛型函数
+    generics_function;
+    /// 泛型实现
+    generics_implementation;
+    /// 泛型 trait
+    generics_trait;
+    /// 泛型约束
+    generics_bounds;
+    /// 空约束
+    generics_empty_bounds;
+    /// 多重约束
+    generics_multiple_bounds;
+    /// where 分句
+    generics_where_clauses;
+    /// newtype 惯用法
+    generics_new_type_idiom;
+    /// 关联项要解决的问题
+    generics_associated_items_problem;
+    /// 关联类型
+    generics_associated_types;
+    /// 错误处理：panic
+    error_handling_panic;
+    /// Option 与 unwrap
+    error_handling_option_and_unwrap;
+    /// 用 ? 解开 Option
+    error_handling_unpacking_options_with_question_mark;
+    /// 组合算子 map
+    error_handling_option_and_unwrap_combinator_map;
+    /// 组合算子 and_then
+    error_handling_option_and_unwrap_combinator_and_then;
+    /// Result
+    error_handling_result;
+    /// Result 的 map
+    error_handling_result_map;
+    /// Result 别名
+    error_handling_result_aliases;
+    /// 提前返回
+    error_handling_result_early_returns;
+    /// ? 运算符
+    error_handling_result_question_mark;
+    /// 多种错误类型
+    error_handling_multiple_error_types;
+    /// 从 Option 中取出 Result
+    error_handling_multiple_error_types_pull_result_out_of_option;
+    /// 定义错误类型
+    error_handling_multiple_error_types_define_an_error_type;
+    /// 把错误装箱
+    error_handling_multiple_error_types_boxing_errors;
+    /// ? 的其他用法
+    error_handling_multiple_error_types_other_uses_of_question_mark;
+    /// 包裹错误
+    error_handling_multiple_error_types_wrapping_errors;
+    /// 遍历 Result
+    error_handling_multiple_error_types_iterating_over_results;
+    /// 静态分派与动态分派
+    static_and_dynamic_dispatch;
+    /// trait object
+    trait_object;
+    /// object safe
+    object_safe;
+    /// impl trait
+    impl_trait;
+    /// 作用域规则：RAII、所有权、借用
+    scoping_rules;
+    /// 生命周期
+    lifetimes;
+    /// 特性 trait
+    traits;
+    /// 标准库类型
+    std_library_types;
+    /// Path
+    path;
+    /// 文件输入输出
+    file_io;
+    /// macro_rules! 宏
+    macro_rules;
+    /// 线程
+    thread;
+}
+
+/// 测试夹具：自动清理的临时目录与具名的文件内容
+pub mod fixture;
+/// 快照测试：把演示输出与snapshots目录中的`.snap`文件比较
+pub mod snapshot;
+/// 编译失败测试：检查 ui/ 下的片段按预期报错
+pub mod compile_fail;
+/// 主题运行器：演示的登记表与命令行入口
+pub mod runner;
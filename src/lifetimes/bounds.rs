@@ -14,6 +14,7 @@ mod tests {
     // struct tuple
     // 里面成员是泛型类型，而且是引用
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct Ref<'a, T: 'a>(&'a T);
     /*
         Ref包含一个指向泛型类型T的引用，其中T拥有一个未知的生命周期'a。
@@ -23,6 +23,7 @@ mod tests {
     //      拥有相同的签名。
 
     // 换个函数名
+    #[allow(clippy::needless_lifetimes)]
     fn annotated_input<'a>(x: &'a i32) {
         println!("`annotated_input`: {}", x)
     }
@@ -31,6 +32,7 @@ mod tests {
     // 生命周期会被隐式地添加进 `elided_pass`：
     fn elided_pass(x: &i32) -> &i32 { x }
 
+    #[allow(clippy::needless_lifetimes)]
     fn annotated_pass<'a>(x: &'a i32) -> &'a i32 { x }
 
     #[test]
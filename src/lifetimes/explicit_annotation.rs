@@ -20,6 +20,7 @@
 mod tests {
     // 函数print_refs接受两个 `i32` 的引用，它们有不同的生命周期 `'a` 和 `'b`。
     // 这两个生命周期都必须至少要和print_refs函数一样长。
+    #[allow(clippy::needless_lifetimes)]
     fn print_refs<'a, 'b>(x: &'a i32, y: &'b i32) {
         println!("x is {} and y is {}", x, y);
     }
@@ -32,10 +33,12 @@ mod tests {
     */
 
     // 不带参数的函数，不过有一个生命周期参数 `'a`
+    #[allow(clippy::extra_unused_lifetimes)]
     fn failed_borrow<'a>() {
+        #[allow(unused_variables)]
         let x = 1024;
 
-        // 编译报错：error[E0597]: `x` does not live long enough
+        // 编译报错：error[E0597]: `x` does not live long enough（见 ui/lifetimes_failed_borrow.rs）
 //        let y: &'a i32 = &x;
         // 因为x是在函数内部声明的，它的生命周期一定小于函数的生命周期'a
         // 在函数内部使用生命周期 `'a` 作为显式类型标注将导致失败,
@@ -9,15 +9,18 @@
     这种情况下禁止它返回这样的引用。
     下面例子展示了一些合法的带有生命周期的函数。
 */
+
 #[cfg(test)]
 mod tests {
     // 一个拥有生命周期 `'a` 的输入引用
     // 其中 `'a` 的存活时间至少与函数的一样长。
+    #[allow(clippy::needless_lifetimes)]
     fn print_one<'a>(x: &'a i32) {
         println!("`print_one`: x is {}", x);
     }
 
     // 可变引用同样也可能拥有生命周期
+    #[allow(clippy::needless_lifetimes)]
     fn double<'a>(x: &'a mut i32) {
         *x *= 2
     }
@@ -25,12 +28,14 @@ mod tests {
     // 拥有不同生命周期的多个元素。
     // 对下面这种情形，两者即使拥有相同的生命周期 `'a` 也没问题。
     // 但对一些更复杂的情形，可能就需要不同的生命周期了。
+    #[allow(clippy::needless_lifetimes)]
     fn print_multi<'a, 'b>(x: &'a i32, y: &'b i32) {
         println!("`print_multi`: x is {}, y is {}", x, y);
     }
 
     // 返回传递进来的引用也是可行的。
     // 但必须返回正确的生命周期。
+    #[allow(clippy::needless_lifetimes)]
     fn pass_x<'a, 'b>(x: &'a i32, _: &'b i32) -> &'a i32 {
         x
     }
@@ -43,10 +48,12 @@ mod tests {
             // String对象在离开函数invalid_output时会被释放掉
             // 这时返回的引用将指向无效数据
             // 编译会报错：error[E0515]: cannot return reference to temporary value
+            // （见 ui/lifetimes_invalid_output.rs 和 ui/lifetimes_missing_specifier.rs）
         }
     */
 
     #[test]
+    #[allow(clippy::needless_borrow)]
     fn test_functions() {
         let (x, y) = (1024, 2048);
         print_one(&x);
@@ -5,16 +5,19 @@
 
     方法一般是不需要标明生命周期的，因为 self 的生命周期会赋给所有的输出生命周期参数
 */
+
 #[cfg(test)]
 mod tests {
     struct S(i32);
 
     impl S {
         // 标注生命周期，就像独立的函数一样
+        #[allow(clippy::needless_lifetimes)]
         fn double<'a>(&'a mut self) {
             self.0 *= 2;
         }
 
+        #[allow(clippy::needless_lifetimes)]
         fn print<'a>(&'a self) {
             println!("print: {}", self.0);
         }
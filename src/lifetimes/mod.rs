@@ -10,15 +10,36 @@
     于是，只要该借用在出借者（lender）被销毁前结束，借用就是有效的。
     然而，借用的作用域则是由使用引用的位置决定的。
 */
-mod explicit_annotation;
-mod functions;
-mod methods;
-mod structs;
-mod traits;
-mod bounds;
-mod coercion;
-mod r#static;
-mod elision;
+pub mod explicit_annotation;
+pub mod functions;
+pub mod methods;
+pub mod structs;
+pub mod traits;
+pub mod bounds;
+pub mod coercion;
+pub mod r#static;
+pub mod elision;
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "lifetimes", about: "返回值的生命周期受参数的生命周期约束", run: demo },
+];
+
+// 返回值的生命周期不长于x和y中较短的那个
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() >= y.len() { x } else { y }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let x = String::from("lifetimes");
+    {
+        let y = String::from("scope");
+        writeln!(out, "longest: {}", longest(&x, &y))?;
+    }   // y在这里销毁，longest的结果也不能再用了
+    writeln!(out, "x is still alive: {}", x)
+}
 
 #[cfg(test)]
 mod tests {
@@ -8,7 +8,9 @@
         - 使用 static 声明来产生常量（constant）
         - 产生一个拥有 &'static str 类型的 string 字面量
 */
+
 // 产生一个拥有 `'static` 生命周期的常量
+#[allow(dead_code)]
 static NUM: i32 = 1024;
 
 #[cfg(test)]
@@ -17,6 +19,7 @@ mod tests {
 
     // 返回一个指向 `NUM` 的引用，该引用不取 `NUM` 的 `'static` 生命周期，
     // 而是被强制转换成和输入参数的一样。
+    #[allow(clippy::needless_lifetimes)]
     fn coerce<'a>(_: &'a i32) -> &'a i32 {
         &NUM
     }
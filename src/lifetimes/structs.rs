@@ -10,10 +10,12 @@ mod tests {
     // 含有一个指向 `i32` 类型的引用
     // 注：&i32的生命周期必须比Borrowed寿命更长
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct Borrowed<'a>(&'a i32);
 
     // 和前面类似，这里的两个引用都必须比这个结构体长寿
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct NameBorrowed<'a> {
         x: &'a i32,
         y: &'a i32,
@@ -21,6 +23,7 @@ mod tests {
 
     // 一个枚举类型，其取值不是 i32 类型,就是一个指向 &i32
     #[derive(Debug)]
+    #[allow(dead_code)]
     enum Either<'a> {
         Num(i32),
         Ref(&'a i32),
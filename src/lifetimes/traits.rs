@@ -9,6 +9,7 @@
 mod tests {
     // 带有生命周期标注的结构体
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct S<'a> {
         name: &'a str
     }
@@ -0,0 +1,858 @@
+/*
+    双向链表实现的双端队列Deque
+
+    enum_linked_list中的List是单向的，只能在表头O(1)地插入和删除。
+    Deque的每个节点同时指向前一个和后一个节点，两端的push/pop都是O(1)，
+    并提供一个可以前后移动、在当前位置插入/删除的游标CursorMut。
+
+    双向链表中每个节点被前后两个节点同时指向，用Box表达不了这种“两个owner”的关系。
+    这里用裸指针（NonNull）实现，所有直接操作指针的代码都集中在下面标注为`unsafe核心`的一节中，
+    其余代码（迭代器、游标、各个trait）只通过这几个函数读写节点。
+
+    游标的位置可以是某个元素，也可以是表尾之后、表头之前的“幽灵”位置（ghost）：
+    从表尾move_next、从表头move_prev都会到达ghost，再继续移动会绕回另一端。
+*/
+use std::fmt::{self, Debug};
+use std::io::{self, Write};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+use crate::runner::Demo;
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+/// 双向链表实现的双端队列
+pub struct Deque<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    // 告诉编译器Deque拥有若干Box<Node<T>>，drop检查和型变都按拥有T来处理
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// ============================== unsafe核心 ==============================
+//
+// 不变式：
+//   1. head、tail以及节点中的prev/next要么为None，要么指向本Deque通过Box分配、尚未释放的节点；
+//   2. 从head沿next走len步恰好走完，最后一个节点是tail；prev方向与之相反；
+//   3. 节点只在unlink中释放，且只释放一次。
+// 只要下面几个函数维持这些不变式，其余代码就不需要再写unsafe（IterMut除外，见其说明）。
+impl<T> Deque<T> {
+    // 在相邻的prev和next之间插入新节点：prev为None表示插在表头，next为None表示插在表尾
+    fn link_between(&mut self, prev: Link<T>, next: Link<T>, elem: T) -> NonNull<Node<T>> {
+        let node = NonNull::from(Box::leak(Box::new(Node { elem, prev, next })));
+        // SAFETY: 由不变式1，prev/next指向活着的节点；&mut self保证此时没有其他对节点的引用
+        unsafe {
+            match prev {
+                Some(mut prev) => prev.as_mut().next = Some(node),
+                None => self.head = Some(node),
+            }
+            match next {
+                Some(mut next) => next.as_mut().prev = Some(node),
+                None => self.tail = Some(node),
+            }
+        }
+        self.len += 1;
+        node
+    }
+
+    // 把node从链表中摘下、释放，返回其中的元素。node必须属于本链表
+    fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        // SAFETY: node由link_between中的Box::leak得到，只在这里还原成Box并释放
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        // SAFETY: 同link_between，前后节点都还活着
+        unsafe {
+            match node.prev {
+                Some(mut prev) => prev.as_mut().next = node.next,
+                None => self.head = node.next,
+            }
+            match node.next {
+                Some(mut next) => next.as_mut().prev = node.prev,
+                None => self.tail = node.prev,
+            }
+        }
+        self.len -= 1;
+        node.elem
+    }
+
+    // 读取本链表中的一个节点，引用的生命周期受&self约束
+    fn node(&self, node: NonNull<Node<T>>) -> &Node<T> {
+        // SAFETY: 由不变式1，node指向活着的节点；&self保证期间没有可变借用
+        unsafe { &*node.as_ptr() }
+    }
+
+    // 修改本链表中的一个节点，引用的生命周期受&mut self约束
+    fn node_mut(&mut self, node: NonNull<Node<T>>) -> &mut Node<T> {
+        // SAFETY: 由不变式1，node指向活着的节点；&mut self保证期间没有其他引用
+        unsafe { &mut *node.as_ptr() }
+    }
+
+    // 在node之前断开：node及其之后的节点组成新的Deque返回，node必须不是head
+    fn split_before(&mut self, node: NonNull<Node<T>>, at: usize) -> Deque<T> {
+        let prev = self.node(node).prev.expect("split_before the head");
+        self.node_mut(prev).next = None;
+        self.node_mut(node).prev = None;
+        let tail = self.tail.replace(prev);
+        let len = mem::replace(&mut self.len, at) - at;
+        Deque { head: Some(node), tail, len, marker: PhantomData }
+    }
+
+    // 把other的全部节点接到self的表尾，other变为空
+    fn link_after_tail(&mut self, other: &mut Deque<T>) {
+        let (tail, other_head) = match (self.tail, other.head) {
+            (Some(tail), Some(other_head)) => (tail, other_head),
+            // 有一方为空时，交换或什么都不做即可
+            (None, _) => return mem::swap(self, other),
+            (_, None) => return,
+        };
+        // 此时两个节点分属两个链表，先改other中的节点，再改self中的节点
+        other.node_mut(other_head).prev = Some(tail);
+        self.node_mut(tail).next = Some(other_head);
+        self.tail = other.tail.take();
+        other.head = None;
+        self.len += mem::replace(&mut other.len, 0);
+    }
+}
+// ============================ unsafe核心结束 ============================
+
+// 节点只通过&mut Deque修改，Deque在线程之间传递和共享的条件与Vec<T>相同
+unsafe impl<T: Send> Send for Deque<T> {}
+unsafe impl<T: Sync> Sync for Deque<T> {}
+
+impl<T> Deque<T> {
+    /// 空队列
+    pub fn new() -> Self {
+        Deque { head: None, tail: None, len: 0, marker: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 在表头插入
+    pub fn push_front(&mut self, elem: T) {
+        self.link_between(None, self.head, elem);
+    }
+
+    /// 在表尾插入
+    pub fn push_back(&mut self, elem: T) {
+        self.link_between(self.tail, None, elem);
+    }
+
+    /// 取出表头元素
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|node| self.unlink(node))
+    }
+
+    /// 取出表尾元素
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|node| self.unlink(node))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|node| &self.node(node).elem)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|node| &self.node(node).elem)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        let head = self.head?;
+        Some(&mut self.node_mut(head).elem)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let tail = self.tail?;
+        Some(&mut self.node_mut(tail).elem)
+    }
+
+    /// 删除全部元素
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// 从表头到表尾的迭代器，也可以用rev()从表尾开始
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { deque: self, front: self.head, back: self.tail, remaining: self.len }
+    }
+
+    /// 可以修改元素的迭代器
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { front: self.head, back: self.tail, remaining: self.len, marker: PhantomData }
+    }
+
+    /// 指向表头的游标，空队列时指向ghost
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { current: self.head, index: 0, deque: self }
+    }
+
+    /// 指向表尾的游标，空队列时指向ghost
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let index = self.len.saturating_sub(1);
+        CursorMut { current: self.tail, index, deque: self }
+    }
+
+    /// 在下标at处一分为二：self保留[0, at)，返回[at, len)
+    ///
+    /// at大于len时panic。从离at较近的一端开始查找，最多走len/2步
+    pub fn split_off(&mut self, at: usize) -> Deque<T> {
+        assert!(at <= self.len, "split_off index (is {}) should be <= len (is {})", at, self.len);
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == self.len {
+            return Deque::new();
+        }
+        let node = self.node_at(at).expect("at < len");
+        self.split_before(node, at)
+    }
+
+    /// 把other的全部元素按顺序移到self的表尾，O(1)，other变为空
+    pub fn append(&mut self, other: &mut Deque<T>) {
+        self.link_after_tail(other);
+    }
+
+    // 下标为index的节点
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+        if index < self.len / 2 {
+            let mut node = self.head;
+            for _ in 0..index {
+                node = self.node(node?).next;
+            }
+            node
+        } else {
+            let mut node = self.tail;
+            for _ in index + 1..self.len {
+                node = self.node(node?).prev;
+            }
+            node
+        }
+    }
+}
+
+impl<T> Drop for Deque<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Deque::new()
+    }
+}
+
+/// Deque::iter返回的迭代器
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    front: Link<T>,
+    back: Link<T>,
+    // 两端相遇时停止
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.deque.node(self.front?);
+        self.front = node.next;
+        self.remaining -= 1;
+        Some(&node.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.deque.node(self.back?);
+        self.back = node.prev;
+        self.remaining -= 1;
+        Some(&node.elem)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Deque::iter_mut返回的迭代器
+///
+/// 它要同时交出多个`&'a mut T`，无法通过node_mut（一次只能借出一个）实现，
+/// 所以这里直接解引用指针：remaining保证两端不会交出同一个节点
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    remaining: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        // SAFETY: 节点在'a期间被&mut Deque借用而保持存活，每个节点只会交出一次
+        let node = unsafe { &mut *self.front?.as_ptr() };
+        self.front = node.next;
+        self.remaining -= 1;
+        Some(&mut node.elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        // SAFETY: 同next
+        let node = unsafe { &mut *self.back?.as_ptr() };
+        self.back = node.prev;
+        self.remaining -= 1;
+        Some(&mut node.elem)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+/// Deque::into_iter返回的迭代器
+pub struct IntoIter<T>(Deque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// 依次push_back
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T: Debug> Debug for Deque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone> Clone for Deque<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Deque<T> {}
+
+/// 可以在Deque上前后移动，并在当前位置插入、删除元素的游标
+pub struct CursorMut<'a, T> {
+    // None表示ghost位置
+    current: Link<T>,
+    // 当前元素的下标；位于ghost时等于deque.len()
+    index: usize,
+    deque: &'a mut Deque<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// 当前元素的下标，位于ghost时为None
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// 当前元素，位于ghost时为None
+    pub fn current(&mut self) -> Option<&mut T> {
+        let current = self.current?;
+        Some(&mut self.deque.node_mut(current).elem)
+    }
+
+    /// 下一个元素（不移动游标）；位于ghost时是表头
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(current) => self.deque.node(current).next,
+            None => self.deque.head,
+        }?;
+        Some(&mut self.deque.node_mut(next).elem)
+    }
+
+    /// 上一个元素（不移动游标）；位于ghost时是表尾
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(current) => self.deque.node(current).prev,
+            None => self.deque.tail,
+        }?;
+        Some(&mut self.deque.node_mut(prev).elem)
+    }
+
+    /// 向表尾方向移动一步：表尾之后是ghost，ghost之后是表头
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(current) => {
+                self.current = self.deque.node(current).next;
+                self.index += 1;
+            }
+            None => {
+                self.current = self.deque.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// 向表头方向移动一步：表头之前是ghost，ghost之前是表尾
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(current) => {
+                self.current = self.deque.node(current).prev;
+                self.index = match self.current {
+                    Some(_) => self.index - 1,
+                    None => self.deque.len,
+                };
+            }
+            None => {
+                self.current = self.deque.tail;
+                self.index = self.deque.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// 在当前元素之前插入；位于ghost时插到表尾。游标仍指向原来的元素
+    pub fn insert_before(&mut self, elem: T) {
+        match self.current {
+            Some(current) => {
+                let prev = self.deque.node(current).prev;
+                self.deque.link_between(prev, Some(current), elem);
+            }
+            None => self.deque.push_back(elem),
+        }
+        self.index += 1;
+    }
+
+    /// 在当前元素之后插入；位于ghost时插到表头。游标仍指向原来的元素
+    pub fn insert_after(&mut self, elem: T) {
+        match self.current {
+            Some(current) => {
+                let next = self.deque.node(current).next;
+                self.deque.link_between(Some(current), next, elem);
+            }
+            None => {
+                self.deque.push_front(elem);
+                self.index = self.deque.len;
+            }
+        }
+    }
+
+    /// 删除并返回当前元素，游标移到下一个元素（原来是表尾时移到ghost）
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+        self.current = self.deque.node(current).next;
+        // 下标不变：后面的元素补上来；删掉的是表尾时，index恰好等于新的len（ghost）
+        Some(self.deque.unlink(current))
+    }
+
+    /// 把当前元素及其之后的元素拆成新的Deque返回，游标移到ghost
+    pub fn split_off(&mut self) -> Deque<T> {
+        let at = match self.current {
+            Some(_) => self.index,
+            None => self.deque.len,
+        };
+        let rest = self.deque.split_off(at);
+        self.current = None;
+        self.index = self.deque.len;
+        rest
+    }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "linked_list_deque", about: "双端队列的两端操作与游标", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut deque: Deque<i32> = (1..=3).collect();
+    deque.push_front(0);
+    deque.push_back(4);
+    writeln!(out, "{:?}", deque)?;
+
+    // 用游标在2和3之间插入一个元素
+    let mut cursor = deque.cursor_front_mut();
+    while cursor.current().is_some_and(|elem| *elem != 2) {
+        cursor.move_next();
+    }
+    cursor.insert_after(23);
+    writeln!(out, "{:?}", deque)?;
+
+    writeln!(out, "front: {:?}, back: {:?}", deque.pop_front(), deque.pop_back())?;
+    writeln!(out, "{:?}", deque.iter().rev().collect::<Vec<_>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    fn contents<T: Clone>(deque: &Deque<T>) -> Vec<T> {
+        deque.iter().cloned().collect()
+    }
+
+    // 逐项比较，并检查反向遍历、len和两端元素
+    fn assert_same(deque: &Deque<i32>, model: &VecDeque<i32>) {
+        assert_eq!(deque.len(), model.len());
+        assert_eq!(deque.is_empty(), model.is_empty());
+        assert!(deque.iter().eq(model.iter()), "{:?} != {:?}", deque, model);
+        assert!(deque.iter().rev().eq(model.iter().rev()));
+        assert_eq!(deque.front(), model.front());
+        assert_eq!(deque.back(), model.back());
+    }
+
+    // 不依赖外部crate的伪随机数（线性同余）
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((self.0 >> 33) as usize) % bound
+        }
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut deque = Deque::new();
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        assert_eq!(contents(&deque), vec![1, 2, 3]);
+        *deque.front_mut().unwrap() = 10;
+        *deque.back_mut().unwrap() = 30;
+        assert_eq!(deque.pop_front(), Some(10));
+        assert_eq!(deque.pop_back(), Some(30));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert!(deque.is_empty());
+        assert_eq!(deque.front(), None);
+    }
+
+    // 长度不超过6的所有push/pop操作序列，都与VecDeque的结果一致
+    #[test]
+    fn test_every_short_sequence_matches_vec_deque() {
+        const OPS: usize = 4;
+        const MAX_LEN: u32 = 6;
+        for len in 0..=MAX_LEN {
+            for mut code in 0..OPS.pow(len) {
+                let mut deque = Deque::new();
+                let mut model = VecDeque::new();
+                for step in 0..len as i32 {
+                    match code % OPS {
+                        0 => { deque.push_front(step); model.push_front(step); }
+                        1 => { deque.push_back(step); model.push_back(step); }
+                        2 => assert_eq!(deque.pop_front(), model.pop_front()),
+                        _ => assert_eq!(deque.pop_back(), model.pop_back()),
+                    }
+                    code /= OPS;
+                    assert_same(&deque, &model);
+                }
+            }
+        }
+    }
+
+    // 在每个位置上用游标插入、删除，与VecDeque::insert/remove比较
+    #[test]
+    fn test_cursor_edits_at_every_position() {
+        for len in 0..6 {
+            let base: Vec<i32> = (0..len).collect();
+            // 位置0..len是元素，len是ghost
+            for position in 0..=len as usize {
+                for op in 0..3 {
+                    let mut deque: Deque<i32> = base.iter().copied().collect();
+                    let mut model: VecDeque<i32> = base.iter().copied().collect();
+                    let mut cursor = deque.cursor_front_mut();
+                    for _ in 0..position {
+                        cursor.move_next();
+                    }
+                    let on_ghost = position == len as usize;
+                    assert_eq!(cursor.index(), if on_ghost { None } else { Some(position) });
+
+                    match op {
+                        0 => {
+                            cursor.insert_before(-1);
+                            model.insert(position, -1);
+                            let expected = if on_ghost { None } else { Some(position + 1) };
+                            assert_eq!(cursor.index(), expected);
+                        }
+                        1 => {
+                            cursor.insert_after(-1);
+                            model.insert(if on_ghost { 0 } else { position + 1 }, -1);
+                            assert_eq!(cursor.index(), if on_ghost { None } else { Some(position) });
+                        }
+                        _ => {
+                            assert_eq!(cursor.remove_current(), model.remove(position));
+                            // 删除后游标指向原来的下一个元素
+                            let expected = model.get(position).copied();
+                            assert_eq!(cursor.current().copied(), if on_ghost { None } else { expected });
+                        }
+                    }
+                    assert_same(&deque, &model);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cursor_walks_both_ways_through_ghost() {
+        let mut deque: Deque<i32> = (1..=3).collect();
+        let mut cursor = deque.cursor_back_mut();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), None);
+
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        *cursor.current().unwrap() *= 10;
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(contents(&deque), vec![1, 2, 30]);
+
+        let mut empty: Deque<i32> = Deque::new();
+        let mut cursor = empty.cursor_front_mut();
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.insert_after(1);
+        cursor.insert_before(2);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(contents(&empty), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_split_off_and_append_at_every_index() {
+        for len in 0..8 {
+            for at in 0..=len {
+                let mut deque: Deque<i32> = (0..len).collect();
+                let mut model: VecDeque<i32> = (0..len).collect();
+                let mut rest = deque.split_off(at as usize);
+                let mut model_rest = model.split_off(at as usize);
+                assert_same(&deque, &model);
+                assert_same(&rest, &model_rest);
+
+                // 拆开的两部分各自仍然可用
+                rest.push_front(100);
+                model_rest.push_front(100);
+                deque.push_back(-100);
+                model.push_back(-100);
+
+                deque.append(&mut rest);
+                model.append(&mut model_rest);
+                assert_same(&deque, &model);
+                assert_same(&rest, &model_rest);
+            }
+        }
+
+        let mut deque: Deque<i32> = (0..5).collect();
+        let mut cursor = deque.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let rest = cursor.split_off();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(contents(&rest), vec![2, 3, 4]);
+        assert_eq!(contents(&deque), vec![0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "should be <= len")]
+    fn test_split_off_out_of_bounds() {
+        let mut deque: Deque<i32> = (0..3).collect();
+        deque.split_off(4);
+    }
+
+    // 混合所有操作的长序列
+    #[test]
+    fn test_random_operations_match_vec_deque() {
+        let mut rng = Lcg(2020);
+        let mut deque = Deque::new();
+        let mut model = VecDeque::new();
+        for step in 0..20_000 {
+            match rng.next(8) {
+                0 => { deque.push_front(step); model.push_front(step); }
+                1 => { deque.push_back(step); model.push_back(step); }
+                2 => assert_eq!(deque.pop_front(), model.pop_front()),
+                3 => assert_eq!(deque.pop_back(), model.pop_back()),
+                4 => {
+                    let index = rng.next(model.len() + 1);
+                    let mut cursor = deque.cursor_front_mut();
+                    for _ in 0..index {
+                        cursor.move_next();
+                    }
+                    cursor.insert_before(step);
+                    model.insert(index, step);
+                }
+                5 => {
+                    let index = rng.next(model.len() + 1);
+                    let mut cursor = deque.cursor_back_mut();
+                    // 从表尾往回走
+                    for _ in index..model.len().saturating_sub(1) {
+                        cursor.move_prev();
+                    }
+                    if index < model.len() {
+                        assert_eq!(cursor.index(), Some(index));
+                        assert_eq!(cursor.remove_current(), model.remove(index));
+                    }
+                }
+                6 => {
+                    let at = rng.next(model.len() + 1);
+                    let mut rest = deque.split_off(at);
+                    let mut model_rest = model.split_off(at);
+                    deque.append(&mut rest);
+                    model.append(&mut model_rest);
+                }
+                _ => {
+                    for (elem, expected) in deque.iter_mut().zip(model.iter_mut()) {
+                        *elem += 1;
+                        *expected += 1;
+                    }
+                }
+            }
+            assert_eq!(deque.len(), model.len());
+        }
+        assert_same(&deque, &model);
+    }
+
+    #[test]
+    fn test_iterators_and_traits() {
+        let mut deque: Deque<i32> = (1..=5).collect();
+        let mut iter = deque.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        let mut iter = deque.iter_mut();
+        *iter.next().unwrap() = 0;
+        *iter.next_back().unwrap() = 0;
+        for elem in &mut deque {
+            *elem += 1;
+        }
+        assert_eq!(format!("{:?}", deque), "[1, 3, 4, 5, 1]");
+
+        let copy = deque.clone();
+        assert_eq!(copy, deque);
+        deque.extend(vec![9]);
+        assert_ne!(copy, deque);
+        assert_eq!(deque.into_iter().rev().collect::<Vec<_>>(), vec![9, 1, 5, 4, 3, 1]);
+    }
+
+    // 所有元素都会被drop，不会泄漏
+    #[test]
+    fn test_no_leaks() {
+        let item = Rc::new(());
+        {
+            let mut deque: Deque<Rc<()>> = (0..10).map(|_| Rc::clone(&item)).collect();
+            let mut rest = deque.split_off(4);
+            rest.pop_back();
+            let mut cursor = deque.cursor_front_mut();
+            cursor.move_next();
+            cursor.remove_current();
+            assert_eq!(Rc::strong_count(&item), 9);
+            deque.append(&mut rest);
+            let mut iter = deque.into_iter();
+            iter.next();
+        }
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+}
@@ -8,6 +8,7 @@
 */
 
 // 比如:我想要定义一套小的计算器API，可以传给它表达式，它会把结果打印到控制台上。
+#[allow(unused_macros)]
 macro_rules! calculate {
     (michael $e:expr) => {
         {
@@ -20,10 +20,11 @@ mod tests {
                     stringify!($func),
                     ($a.len(),),
                     stringify!($op),
-                    ($b.len(),));
+                    ($b.len(),))
         )
     }
     #[test]
+    #[should_panic(expected = "dimension mismatch")]
     fn test_assert_equal_len() {
         let a = "abc";
         let b = "def";
@@ -40,7 +41,7 @@ mod tests {
     macro_rules! op {
     // $bound是trait名，$method是该trait中的方法名
         ($func:ident,$bound:ident,$op:tt,$method:ident)=>(  // => 后面跟()，表示该宏返回的是一个值
-            fn $func<T: $bound<T,Output=T> + Copy>(xs: &mut Vec<T>, ys: &Vec<T>){
+            fn $func<T: $bound<T,Output=T> + Copy>(xs: &mut [T], ys: &[T]){
             // 调用上面自定义的宏
                 assert_equal_len!(xs,ys,$func,$op);
 
@@ -57,13 +58,13 @@ mod tests {
     op!(mul_michael,Mul,*=,mul);
     op!(sub_michael,Sub,-=,sub);
 
-    #[test]
-    fn test_dont_repeat_yourself() {
-        // 自定义宏,在当前测试函数内创建对应的测试函数
-        macro_rules! test_michael {
-            ($func:ident,$x:expr,$y:expr,$z:expr) => {  // => 后面跟{}，表示该宏返回的是一个代码块
+    // 自定义宏,在mod tests内创建对应的测试函数
+    // 注：#[test]函数必须位于模块层级，定义在其他函数内部的测试函数不会被执行
+    macro_rules! test_michael {
+        ($func:ident,$x:expr,$y:expr,$z:expr) => {  // => 后面跟{}，表示该宏返回的是一个代码块
+            mod $func {
                 #[test]
-                fn $func(){
+                fn test_dont_repeat_yourself(){
                     for size in 0usize..=10{
                         let mut x: Vec<_> = std::iter::repeat($x).take(size).collect();
                         let y: Vec<_> = std::iter::repeat($y).take(size).collect();
@@ -74,16 +75,16 @@ mod tests {
                                iter::repeat(4).take(4) 生成一个迭代器，只能连续迭代输出4个4，到第5次迭代时，输出None
                         */
                         // 调用mod tests中的同名方法
-                        self::$func(&mut x, &y);
+                        super::$func(&mut x, &y);
 
                         assert_eq!(x,z)
                     }
                 }
             }
         }
-
-        test_michael!(add_michael,1,2,3);
-        test_michael!(mul_michael,4,5,20);
-        test_michael!(sub_michael,3,2,1);
     }
+
+    test_michael!(add_michael,1,2,3);
+    test_michael!(mul_michael,4,5,20);
+    test_michael!(sub_michael,3,2,1);
 }
\ No newline at end of file
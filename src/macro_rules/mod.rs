@@ -21,10 +21,36 @@
     3. 可变接口（variadic interface）。
         有时你需要能够接受不定数目参数的接口，比如 println!，根据格式化字符串的不同，它需要接受任意多的参数（稍后详述）。
 */
-mod syntax;
-mod dont_repeat_yourself;
-mod domain_specific_languages;
-mod variadic_interfaces;
+pub mod syntax;
+pub mod dont_repeat_yourself;
+pub mod domain_specific_languages;
+pub mod variadic_interfaces;
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "macro_rules", about: "用macro_rules!定义宏", run: demo },
+];
+
+// 把问候语写进$out，展开成一个writeln!表达式
+macro_rules! say_hello {
+    ($out:expr) => {
+        writeln!($out, "Hello Michael.W！")
+    };
+}
+
+// 接受任意多个表达式，展开成由它们的平方组成的数组
+macro_rules! squares {
+    ($($x:expr),*) => {
+        [$($x * $x),*]
+    };
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    say_hello!(out)?;
+    writeln!(out, "{:?}", squares!(1, 2, 3))
+}
 
 #[cfg(test)]
 mod tests {
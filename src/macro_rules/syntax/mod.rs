@@ -5,6 +5,6 @@
         2. 重载
         3. 重复
 */
-mod designators;
-mod overload;
-mod repeat;
\ No newline at end of file
+pub mod designators;
+pub mod overload;
+pub mod repeat;
\ No newline at end of file
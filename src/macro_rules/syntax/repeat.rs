@@ -23,6 +23,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn test_repeat() {
         // 只传一个参数
         println!("{}", get_min!(1024));
@@ -6,6 +6,7 @@
 */
 
 //  把 domain_specific_languages.rs 中的 calculater! 宏改写成可变参数接口：
+#[allow(unused_macros)]
 macro_rules! calculate {
     // 如果是单个参数
     (michael $e:expr) => {
@@ -1,6 +1,25 @@
 //  在 match 中，若间接地访问一个变量，则不经过重新绑定就无法在分支中再使用它。
 // match 提供了 @ 符号来绑定变量到名称：
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_binding", about: "用@把匹配到的值绑定到变量", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    for age in [0, 7, 15, 42] {
+        match age {
+            0 => writeln!(out, "I'm not born yet I guess")?,
+            n @ 1..=12 => writeln!(out, "I'm a child of age {:?}", n)?,
+            n @ 13..=19 => writeln!(out, "I'm a teen of age {:?}", n)?,
+            n => writeln!(out, "I'm an old person of age {:?}", n)?,
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -20,4 +39,4 @@ mod tests {
             n             => println!("I'm an old person of age {:?}", n),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,38 @@
 // 需要 `allow` 来消除警告，因为只使用了枚举类型的一种取值。
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_enum", about: "在match中解构枚举", run: demo },
+];
+
+/// 几种色彩模型
+#[allow(clippy::upper_case_acronyms)]
+pub enum Color {
+    Red,
+    RGB(u32, u32, u32),
+    HSL(u32, u32, u32),
+    CMYK(u32, u32, u32, u32),
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let colors = [Color::Red, Color::RGB(255, 128, 0), Color::HSL(122, 17, 40), Color::CMYK(0, 50, 100, 0)];
+    for color in colors {
+        match color {
+            Color::Red => writeln!(out, "The color is Red!")?,
+            Color::RGB(r, g, b) => writeln!(out, "Red: {}, green: {}, and blue: {}!", r, g, b)?,
+            Color::HSL(h, s, l) => writeln!(out, "Hue: {}, saturation: {}, lightness: {}!", h, s, l)?,
+            Color::CMYK(c, m, y, k) => writeln!(out, "Cyan: {}, magenta: {}, yellow: {}, key (black): {}!", c, m, y, k)?,
+        }
+    }
+    Ok(())
+}
+
 #[allow(dead_code)]
 #[cfg(test)]
 mod tests {
+    #[allow(clippy::upper_case_acronyms)]
     enum Color {
         // 这三个取值仅由它们的名字（而非类型）来指定。
         Red,
@@ -37,4 +68,4 @@ mod tests {
             // 不需要其它分支，因为所有的情形都已覆盖
         }
     }
-}
\ No newline at end of file
+}
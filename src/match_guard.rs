@@ -1,4 +1,24 @@
 //可以加上 match 守卫（guard） 来过滤分支。
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_guard", about: "用match守卫过滤分支", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    for pair in [(2, 2), (1024, -1024), (3, 4), (4, 5)] {
+        match pair {
+            (x, y) if x == y => writeln!(out, "{:?}: These are twins", pair)?,
+            (x, y) if x + y == 0 => writeln!(out, "{:?}: Antimatter, kaboom!", pair)?,
+            (x, _) if x % 2 == 1 => writeln!(out, "{:?}: The first one is odd", pair)?,
+            _ => writeln!(out, "{:?}: No correlation...", pair)?,
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -13,4 +33,4 @@ mod tests {
             _ => println!("No correlation..."),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,33 @@
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_if_let", about: "用if let代替只关心一个分支的match", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let number = Some(7);
+    let letter: Option<i32> = None;
+
+    if let Some(i) = number {
+        writeln!(out, "Matched {:?}!", i)?;
+    }
+
+    // 用else指明失败的情形
+    if let Some(i) = letter {
+        writeln!(out, "Matched {:?}!", i)?;
+    } else {
+        writeln!(out, "Didn't match a number. Let's go with a letter!")?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
 
 //    在一些场合下，用 match 匹配枚举类型并不优雅。比如：
+    #[allow(clippy::single_match)]
     fn test_match_if_let_1() {
         let optional = Some(7);
 
@@ -85,4 +110,4 @@ mod tests {
         }
 
     }
-}
\ No newline at end of file
+}
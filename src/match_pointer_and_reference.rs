@@ -5,9 +5,37 @@
 解构使用 &、ref、和 ref mut
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_pointer_and_reference", about: "解构与解引用，以及ref和ref mut", run: demo },
+];
+
+#[allow(clippy::match_single_binding)]
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let reference = &1024;
+    // 用`&val`解构引用
+    let &val = reference;
+    writeln!(out, "Got a value via destructuring: {:?}", val)?;
+    // 或者先解引用
+    writeln!(out, "Got a value via dereferencing: {:?}", *reference)?;
+
+    let mut mut_value = 4096;
+    // 用`ref mut`在模式中取得可变引用
+    match mut_value {
+        ref mut m => {
+            *m += 10;
+            writeln!(out, "We added 10. `m`: {:?}", m)?;
+        }
+    }
+    writeln!(out, "mut_value: {}", mut_value)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
+    #[allow(clippy::match_single_binding, clippy::toplevel_ref_arg)]
     fn test_match_pointer_and_reference() {
         // 获得一个 `i32` 类型的引用。`&` 表示取引用。
         let reference = &1024;
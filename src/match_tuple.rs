@@ -1,4 +1,23 @@
 //元组可以在 match 中解构
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_tuple", about: "在match中解构元组", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    for pair in [(0, 1), (2, 0), (3, 4)] {
+        match pair {
+            (0, y) => writeln!(out, "First is `0` and `y` is `{:?}`", y)?,
+            (x, 0) => writeln!(out, "`x` is `{:?}` and last is `0`", x)?,
+            _ => writeln!(out, "It doesn't matter what they are")?,
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -11,4 +30,4 @@ mod tests {
             _ => println!("It doesn't matter what they are"),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,31 @@
 // 和 if let 类似，while let 也可以把别扭的 match 改写得好看一些。
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "match_while_let", about: "用while let改写loop加match", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut optional = Some(0);
+    while let Some(i) = optional {
+        if i > 3 {
+            writeln!(out, "Greater than 3, quit!")?;
+            optional = None;
+        } else {
+            writeln!(out, "`i` is `{:?}`. Try again.", i)?;
+            optional = Some(i + 1);
+        }
+    }
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
     #[test]
     // 考虑下面这 段使 i 不断增加的代码：
+    #[allow(clippy::while_let_loop)]
     fn test_while_let_1() {
         // 将 `optional` 设为 `Option<i32>` 类型
         let mut optional = Some(0);
@@ -49,4 +70,4 @@ mod tests {
         // ^ `if let` 有可选的 `else`/`else if` 分句，
         // 而 `while let` 没有
     }
-}
\ No newline at end of file
+}
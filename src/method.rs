@@ -2,82 +2,119 @@
 // 这些方法通过关键字 self 来访问对象中的数据和 其他。
 // 方法在 impl 代码块中定义。
 
-#[cfg(test)]
-mod tests {
-    #[derive(Debug)]
-    struct Point {
-        x: f64,
-        y: f64,
-    }
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    impl Point {
-        // 这是一个静态方法（static method）
-        // 静态方法不需要被实例调用
-        // 这类方法一般用作构造器（constructor）
-        fn origin() -> Point {
-            Point { x: 0.0, y: 0.0 }
-        }
+/// 平面上的点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
 
-        // 另外一个静态方法，需要两个参数：
-        fn new(x: f64, y: f64) -> Point {
-            Point { x, y }
-        }
+impl Point {
+    // 这是一个静态方法（static method）
+    // 静态方法不需要被实例调用
+    // 这类方法一般用作构造器（constructor）
+    /// 原点(0, 0)
+    pub fn origin() -> Point {
+        Point { x: 0.0, y: 0.0 }
     }
 
-    #[derive(Debug)]
-    struct Rectangle {
-        p1: Point,
-        p2: Point,
+    // 另外一个静态方法，需要两个参数：
+    /// 由坐标创建一个点
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
     }
+}
 
-    impl Rectangle {
-        // 这是一个实例方法（instance method）
-        // `&self` 是 `self: &Self` 的语法糖（sugar），
-        // 其中 `Self` 是方法调用者的类型。
-        // 在这个例子中 `Self` = `Rectangle`
-        fn area(&self) -> f64 {
-            let Point { x: x1, y: y1 } = self.p1;
-            let Point { x: x2, y: y2 } = self.p2;
-            // `abs` 是一个 `f64` 类型的方法，返回调用者的绝对值
-            ((x1 - x2) * (y1 - y2)).abs()
-        }
+/// 由两个对角顶点确定的矩形
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    pub p1: Point,
+    pub p2: Point,
+}
 
-        fn perimeter(&self) -> f64 {
-            let Point { x: x1, y: y1 } = self.p1;
-            let Point { x: x2, y: y2 } = self.p2;
-            ((x1 - x2).abs() + (y1 - y2).abs()) * 2.0
-        }
+impl Rectangle {
+    // 这是一个实例方法（instance method）
+    // `&self` 是 `self: &Self` 的语法糖（sugar），
+    // 其中 `Self` 是方法调用者的类型。
+    // 在这个例子中 `Self` = `Rectangle`
+    /// 面积
+    pub fn area(&self) -> f64 {
+        let Point { x: x1, y: y1 } = self.p1;
+        let Point { x: x2, y: y2 } = self.p2;
+        // `abs` 是一个 `f64` 类型的方法，返回调用者的绝对值
+        ((x1 - x2) * (y1 - y2)).abs()
+    }
 
-        // 这个方法要求调用者是可变的
-        // `&mut self` 为 `self: &mut Self` 的语法糖
-        fn translate(&mut self, x: f64, y: f64) {
-            self.p1.x += x;
-            self.p2.x += x;
-            self.p1.y += y;
-            self.p2.y += y;
-        }
+    /// 周长
+    pub fn perimeter(&self) -> f64 {
+        let Point { x: x1, y: y1 } = self.p1;
+        let Point { x: x2, y: y2 } = self.p2;
+        ((x1 - x2).abs() + (y1 - y2).abs()) * 2.0
+    }
+
+    // 这个方法要求调用者是可变的
+    // `&mut self` 为 `self: &mut Self` 的语法糖
+    /// 平移矩形
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.p1.x += x;
+        self.p2.x += x;
+        self.p1.y += y;
+        self.p2.y += y;
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "method::rectangle", about: "Rectangle的静态方法与实例方法", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let rectangle = Rectangle {
+        p1: Point::origin(),
+        p2: Point::new(3.0, 4.0),
+    };
+
+    // 实例方法通过点运算符来调用
+    // 注意第一个参数 `&self` 是隐式传递的，亦即：
+    // `rectangle.perimeter()` 等价于 `Rectangle::perimeter(&rectangle)`
+    writeln!(out, "{} {}", rectangle.perimeter(), rectangle.area())?;
+
+    // square是可改变的
+    let mut square = Rectangle {
+        p1: Point::origin(),
+        p2: Point::new(1.0, 1.0),
+    };
+
+    square.translate(1.0, 0.0);
+    writeln!(out, "{:?}", square)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Point, Rectangle};
 
     #[test]
     fn match_method_1() {
+        let mut out = Vec::new();
+        super::demo(&mut out).unwrap();
+        print!("{}", String::from_utf8(out).unwrap());
+
         let rectangle = Rectangle {
             p1: Point::origin(),
             p2: Point::new(3.0, 4.0),
         };
+        assert_eq!(rectangle.perimeter(), 14.0);
+        assert_eq!(rectangle.area(), 12.0);
 
-        // 实例方法通过点运算符来调用
-        // 注意第一个参数 `&self` 是隐式传递的，亦即：
-        // `rectangle.perimeter()` 等价于 `Rectangle::perimeter(&rectangle)`
-        println!("{} {}", rectangle.perimeter(), rectangle.area());
-
-        // square是可改变的
         let mut square = Rectangle {
             p1: Point::origin(),
             p2: Point::new(1.0, 1.0),
         };
-
         square.translate(1.0, 0.0);
-        println!("{:?}", square);
+        assert_eq!(square.p1, Point::new(1.0, 0.0));
+        assert_eq!(square.p2, Point::new(2.0, 1.0));
     }
 
     #[derive(Debug)]
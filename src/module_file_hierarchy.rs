@@ -13,10 +13,24 @@
 
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 mod my_mod;
 
-fn function(){
-    println!("called `function()`. File path: src/module_file_hierarchy.rs")
+fn function(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "called `function()`. File path: src/module_file_hierarchy.rs")
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "module_file_hierarchy", about: "把模块拆分到目录和文件中", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    function(out)?;
+    my_mod::function(out)?;
+    my_mod::indirect_access(out)?;
+    my_mod::nested::function(out)
 }
 
 #[cfg(test)]
@@ -25,21 +39,30 @@ mod tests{
 
     #[test]
     fn test_module_file_hierarchy(){
+        let mut out = Vec::new();
         // 调用本文件内的函数function
-        function();
+        function(&mut out).unwrap();
 
         // 调用module_file_hierarchy/my_mod/mod.rs中的函数function
-        super::my_mod::function();
+        super::my_mod::function(&mut out).unwrap();
         // 等价于
-        crate::module_file_hierarchy::my_mod::function();
+        crate::module_file_hierarchy::my_mod::function(&mut out).unwrap();
 
         // 调用module_file_hierarchy/my_mod/mod.rs中的函数indirect_access
-        super::my_mod::indirect_access();
+        super::my_mod::indirect_access(&mut out).unwrap();
 
         // 调用module_file_hierarchy/my_mod/nested.rs中的函数
         // 由于nested.rs中有私有函数，所以默认该mod也是私有的
         // 如果需要调用该mod nested中的公共函数，需要在module_file_hierarchy/my_mod/mod.rs中导入nested.rs时，
         // 将其声明为：pub mod nested;
-        super::my_mod::nested::function()
+        super::my_mod::nested::function(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), concat!(
+            "called `function()`. File path: src/module_file_hierarchy.rs\n",
+            "called `my_mod::function()`. File path: src/module_file_hierarchy/my_mod/mod.rs\n",
+            "called `my_mod::function()`. File path: src/module_file_hierarchy/my_mod/mod.rs\n",
+            "called `my_mod::indirect_access()`, that\n",
+            "> called `my_mod::private_function()`. File path: src/module_file_hierarchy/my_mod/mod.rs\n",
+            "called `my_mod::nested::function()`. File path: src/module_file_hierarchy/my_mod/nested.rs\n",
+        ));
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub fn public_function() {
     println!("called `my_mod::inaccessible::public_function()`. File path: src/module_file_hierarchy/my_mod/inaccessible.rs")
 }
\ No newline at end of file
@@ -3,16 +3,18 @@
 mod inaccessible;
 pub mod nested;
 
-pub fn function() {
-    println!("called `my_mod::function()`. File path: src/module_file_hierarchy/my_mod/mod.rs")
+use std::io::{self, Write};
+
+pub fn function(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "called `my_mod::function()`. File path: src/module_file_hierarchy/my_mod/mod.rs")
 }
 
 // 私有的
-fn private_function() {
-    println!("called `my_mod::private_function()`. File path: src/module_file_hierarchy/my_mod/mod.rs");
+fn private_function(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "called `my_mod::private_function()`. File path: src/module_file_hierarchy/my_mod/mod.rs")
 }
 
-pub fn indirect_access() {
-    print!("called `my_mod::indirect_access()`, that\n> ");
-    private_function();
+pub fn indirect_access(out: &mut dyn Write) -> io::Result<()> {
+    write!(out, "called `my_mod::indirect_access()`, that\n> ")?;
+    private_function(out)
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
-pub fn function() {
-    println!("called `my_mod::nested::function()`. File path: src/module_file_hierarchy/my_mod/nested.rs")
+use std::io::{self, Write};
+
+pub fn function(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "called `my_mod::nested::function()`. File path: src/module_file_hierarchy/my_mod/nested.rs")
 }
 
 // 私有的
+#[allow(dead_code)]
 fn private_function() {
     println!("called `my_mod::nested::private_function()`. File path: src/module_file_hierarchy/my_mod/nested.rs")
 }
\ No newline at end of file
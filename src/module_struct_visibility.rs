@@ -4,14 +4,19 @@
 只有从结构体被定义的模块之外访问其字段时，这个可见性才会起作用，其意义是隐藏信息（即封装，encapsulatoin）。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 mod my_mod {
     // 一个公有的结构体，带有一个公有的字段（类型为泛型 `T`）
+    #[allow(dead_code)]
     pub struct OpenBox<T> {
         // 公有的字段contents,类型T
         pub contents: T,
     }
 
     // 一个公有的结构体，带有一个私有的字段（类型为泛型 `T`）
+    #[allow(dead_code)]
     pub struct ClosedBox<T> {
         // 私有字段
         contents: T,
@@ -19,6 +24,7 @@ mod my_mod {
 
     impl<T> ClosedBox<T> {
         // 一个公有的构造器方法
+        #[allow(dead_code)]
         pub fn new(contents: T) -> ClosedBox<T> {
             ClosedBox {
                 contents,
@@ -27,6 +33,20 @@ mod my_mod {
     }
 }
 
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "module_struct_visibility", about: "结构体字段的可见性与公有构造器", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 带有公有字段的公有结构体，可以直接创建并访问字段
+    let open_box = my_mod::OpenBox { contents: "public information" };
+    writeln!(out, "The open box contains: {}", open_box.contents)?;
+
+    // 带有私有字段的结构体只能用公有的构造器创建，字段也访问不到
+    let _closed_box = my_mod::ClosedBox::new("classified information");
+    writeln!(out, "The closed box keeps its contents private")
+}
+
 #[cfg(test)]
 mod tests {
     use crate::module_struct_visibility::my_mod;
@@ -49,4 +69,4 @@ mod tests {
         // 报错！`content` 字段是私有的。
 //        println!("{}",close_box.contents);
     }
-}
\ No newline at end of file
+}
@@ -5,77 +5,101 @@ Rust 提供了一套强大的模块（module）系统，可以将代码按层次
 模块是项（item）的集合，项可以是：函数，结构体，trait，impl 块，甚至其它模块。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 // 一个名为 `my_mod` 的模块
 mod my_mod {
+    use std::io::{self, Write};
+
     // 模块中的项默认具有私有的可见性
-    fn private_function() {
-        println!("called `my_mod::private_function()`");
+    fn private_function(out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "called `my_mod::private_function()`")
     }
 
     // 使用 `pub` 修饰语来改变默认可见性。
-    pub fn function() {
-        println!("called `my_mod::function()`");
+    pub fn function(out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "called `my_mod::function()`")
     }
 
     // 在同一模块中，项(item)可以访问其它项，即使它是私有的。
-    pub fn indirect_access() {
-        print!("called `my_mod::indirect_access()`, that\n> ");
-        private_function();
+    pub fn indirect_access(out: &mut dyn Write) -> io::Result<()> {
+        write!(out, "called `my_mod::indirect_access()`, that\n> ")?;
+        private_function(out)
     }
 
     // 模块也可以嵌套
     pub mod my_mod_inner {
+        use std::io::{self, Write};
+
         // 公有的
-        pub fn function() {
-            println!("called `my_mod::my_mod_inner::function()`");
+        pub fn function(out: &mut dyn Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::my_mod_inner::function()`")
         }
 
         // 私有的
-        fn private_function() {
-            println!("called `my_mod::my_mod_inner::private_function()`");
+        #[allow(dead_code)]
+        fn private_function(out: &mut dyn Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::my_mod_inner::private_function()`")
         }
 
         // 使用 `pub(in path)` 语法定义的函数只在给定的路径中可见。
         // `path` 必须是父模块（parent module）或祖先模块（ancestor module）
-        pub(in crate::module_visibility::my_mod) fn public_function_in_my_mod() {
-            print!("called `my_mod::my_mod_inner::public_function_in_my_mod()`, that\n > ");
-            public_function_in_my_mod_inner();
+        pub(in crate::module_visibility::my_mod) fn public_function_in_my_mod(out: &mut dyn Write) -> io::Result<()> {
+            write!(out, "called `my_mod::my_mod_inner::public_function_in_my_mod()`, that\n > ")?;
+            public_function_in_my_mod_inner(out)
         }
 //        注：relative paths are not supported in visibilities on 2018 edition，try: `crate::module_visibility::my_mod`
 
         // 使用 `pub(self)` 语法定义的函数则只在当前模块中可见。
-        pub(self) fn public_function_in_my_mod_inner() {
-            println!("called `my_mod::my_mod_inner::public_function_in_my_mod_inner");
+        #[allow(clippy::needless_pub_self)]
+        pub(self) fn public_function_in_my_mod_inner(out: &mut dyn Write) -> io::Result<()> {
+            writeln!(out, "called `my_mod::my_mod_inner::public_function_in_my_mod_inner")
         }
 
         // 使用 `pub(super)` 语法定义的函数只在父模块中可见。
-        pub(super) fn public_function_in_super_mod() {
-            println!("called my_mod::my_mod_inner::public_function_in_super_mod");
+        pub(super) fn public_function_in_super_mod(out: &mut dyn Write) -> io::Result<()> {
+            writeln!(out, "called my_mod::my_mod_inner::public_function_in_super_mod")
         }
     }
 
-    pub fn call_public_function_in_my_mod() {
-        print!("called `my_mod::call_public_function_in_my_mod()`, that\n> ");
-        my_mod_inner::public_function_in_my_mod();
-        print!("> ");
-        my_mod_inner::public_function_in_super_mod();
+    pub fn call_public_function_in_my_mod(out: &mut dyn Write) -> io::Result<()> {
+        write!(out, "called `my_mod::call_public_function_in_my_mod()`, that\n> ")?;
+        my_mod_inner::public_function_in_my_mod(out)?;
+        write!(out, "> ")?;
+        my_mod_inner::public_function_in_super_mod(out)
     }
 
     // `pub(crate)` 使得函数只在当前 crate 中可见
-    pub(crate) fn public_function_in_crate() {
-        println!("called `my_mod::public_function_in_crate()");
+    pub(crate) fn public_function_in_crate(out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "called `my_mod::public_function_in_crate()")
     }
 
     // 嵌套模块的可见性遵循相同的规则
     mod private_my_mod_inner {
+        #[allow(dead_code)]
         pub fn function() {
             println!("called `my_mod::private_my_mod_inner::function()`");
         }
     }
 }
 
-fn function() {
-    println!("called `function()`");
+fn function(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "called `function()`")
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "module_visibility", about: "pub、pub(crate)、pub(super)与pub(in path)的可见性", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 模块机制消除了相同名字的项之间的歧义
+    function(out)?;
+    my_mod::function(out)?;
+    my_mod::indirect_access(out)?;
+    my_mod::my_mod_inner::function(out)?;
+    my_mod::call_public_function_in_my_mod(out)?;
+    my_mod::public_function_in_crate(out)
 }
 
 #[cfg(test)]
@@ -91,20 +115,33 @@ mod tests {
 
     #[test]
     fn test_module_visibility() {
+        let mut out = Vec::new();
         // 模块机制消除了相同名字的项之间的歧义。
-        function();
-        my_mod::function();
+        function(&mut out).unwrap();
+        my_mod::function(&mut out).unwrap();
 
         // 公有项，包括嵌套模块内的，都可以在父模块外部访问。
         // 调用其他模块的pub函数
-        my_mod::indirect_access();
+        my_mod::indirect_access(&mut out).unwrap();
         // 调用其他模块中嵌套模块的pub函数
-        my_mod::my_mod_inner::function();
+        my_mod::my_mod_inner::function(&mut out).unwrap();
         // call_public_function_in_my_mod函数调用了同级嵌套模块中的函数
-        my_mod::call_public_function_in_my_mod();
+        my_mod::call_public_function_in_my_mod(&mut out).unwrap();
 
         // pub(crate) 项可以在同一个 crate 中的任何地方访问
-        my_mod::public_function_in_crate();
+        my_mod::public_function_in_crate(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), concat!(
+            "called `function()`\n",
+            "called `my_mod::function()`\n",
+            "called `my_mod::indirect_access()`, that\n",
+            "> called `my_mod::private_function()`\n",
+            "called `my_mod::my_mod_inner::function()`\n",
+            "called `my_mod::call_public_function_in_my_mod()`, that\n",
+            "> called `my_mod::my_mod_inner::public_function_in_my_mod()`, that\n",
+            " > called `my_mod::my_mod_inner::public_function_in_my_mod_inner\n",
+            "> called my_mod::my_mod_inner::public_function_in_super_mod\n",
+            "called `my_mod::public_function_in_crate()\n",
+        ));
 
         // 模块的私有项不能直接访问，即便它是嵌套在公有模块内部的
         // 报错！my_mod中的`private_function`函数是私有的
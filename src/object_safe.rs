@@ -3,6 +3,50 @@
     那么在哪些条件下trait object是无法构造出来的呢？见下：
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "object_safe", about: "用Self: Sized把方法从虚函数表中剔除", run: demo },
+];
+
+// 静态方法和返回Self的方法加上Self: Sized后，trait仍然是object safe的
+trait Scale {
+    fn unit() -> Self where Self: Sized;
+    fn scale(&mut self, factor: i32);
+    fn value(&self) -> i32;
+}
+
+impl Scale for i32 {
+    fn unit() -> Self { 1 }
+
+    fn scale(&mut self, factor: i32) { *self *= factor }
+
+    fn value(&self) -> i32 { *self }
+}
+
+struct Meters(i32);
+
+impl Scale for Meters {
+    fn unit() -> Self { Meters(1) }
+
+    fn scale(&mut self, factor: i32) { self.0 *= factor }
+
+    fn value(&self) -> i32 { self.0 }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut n = i32::unit();
+    let mut m = Meters::unit();
+    // unit不在虚函数表中，但scale和value可以通过trait object调用
+    let objects: [&mut dyn Scale; 2] = [&mut n, &mut m];
+    for object in objects {
+        object.scale(1024);
+        writeln!(out, "{}", object.value())?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Debug;
@@ -26,6 +70,7 @@ mod tests {
         x.foo();
         // 那试试trait object
         // 此处编译器报错：the trait `object_safe::tests::Foo` cannot be made into an object
+        // （见 ui/object_safe_sized_trait.rs）
 //        let p = &x as &dyn Foo;
 //        p.foo();
 
@@ -60,6 +105,7 @@ mod tests {
         let p = &x as &dyn Foo1;    // 这步可以编译通过，因为Foo1并未被 Self:Sized 修饰
         p.foo1();
         // 此处编译报错：error: the `foo2` method cannot be invoked on a trait object
+        // （见 ui/object_safe_sized_method.rs）
 //        p.foo2();
 
         /*
@@ -87,6 +133,7 @@ mod tests {
 
     //    Rust规定，如果函数中除了self这个参数之外，还在`其他参数`或者`返回值`中用到了Self类型， 那么这个函数就不是 object safe 的。
     // 看下面的例子：定义一个trait Double
+    #[allow(dead_code)]
     trait Double {
         fn new() -> Self;
         fn double(&mut self);
@@ -100,8 +147,10 @@ mod tests {
 
     #[test]
     fn test_object_safe_v3() {
+        #[allow(unused_mut, unused_variables)]
         let mut x = 1024;
         // 下面这步编译期直接报错：error[E0038]: the trait `std::clone::Clone` cannot be made into an object
+        // （见 ui/object_safe_clone.rs）
 //        let p = &mut x as &mut dyn Clone;
 
         /*
@@ -113,6 +162,7 @@ mod tests {
     }
 
     trait Double1 {
+        #[allow(dead_code)]
         fn new() -> Self where Self: Sized;
         // 编译器就不会在生成虚函数表的时候考虑new方法
         fn double(&mut self);
@@ -139,9 +189,11 @@ mod tests {
     trait Double2 {
         // 静态方法
         fn double_static(num: i32) -> i32;
+        #[allow(dead_code)]
         fn double(&mut self);
     }
 
+    #[allow(dead_code)]
     struct Foo2(i32);
 
     impl Double2 for Foo2 {
@@ -154,9 +206,11 @@ mod tests {
     fn test_object_safe_v5() {
         // 调用trait中的静态方法
         assert_eq!(20, Foo2::double_static(10));
+        #[allow(unused_mut, unused_variables)]
         let mut foo2 = Foo2(1024);
         // 尝试产生trait object
         // 这步编译器直接报错：error[E0038]: the trait `object_safe::tests::Double2` cannot be made into an object
+        // （见 ui/object_safe_static_method.rs）
 //        let p = &mut foo2 as &mut dyn Double2;
     }
 
@@ -208,6 +262,7 @@ mod tests {
 
         // 尝试使用trait object
         // 编译报错：error[E0038]: the trait `object_safe::tests::Trait` cannot be made into an object
+        // （见 ui/object_safe_generic_method.rs）
 //        let p = &foo4 as &dyn Trait;
 //        p.generic_func("michael.w");
 
@@ -220,4 +275,4 @@ mod tests {
             这个行为跟 C++是一样的。C++中同样规定了类的虚成员函数不可以是template方法 。
         */
     }
-}
\ No newline at end of file
+}
@@ -14,6 +14,25 @@
     需要注意的是：Path在内部并不是用UTF-8字符串表示的，而是存储为Vec<u8>。
     因此，将Path转化成&str并非零开销的（free），且可能失败（因此它返回一个Option）。
 */
+use std::io::{self, Write};
+use std::path::Path;
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "path", about: "用join拼接路径，并取出文件名、扩展名与父目录", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // join使用操作系统特定的分隔符，得到的是PathBuf
+    let path = Path::new("src").join("file_io").join("rust.txt");
+    writeln!(out, "{}", path.display())?;
+    writeln!(out, "file_name: {:?}", path.file_name())?;
+    writeln!(out, "extension: {:?}", path.extension())?;
+    writeln!(out, "parent: {:?}", path.parent())?;
+    // 转为&str可能失败，所以返回Option
+    writeln!(out, "to_str: {:?}", path.to_str())
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
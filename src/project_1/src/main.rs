@@ -1,5 +1,49 @@
+/*
+    project_1：一个用clap解析参数的小命令行工具
+
+    计算功能来自同一workspace中的project_2（通过path依赖引入）：
+        project_1 gcd 12 18
+        project_1 primes 30
+        project_1 fib 10
+*/
 extern crate clap;
 
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use clap::{App, Arg, SubCommand};
+
+// 参数必须能解析成T，例如fib的N超出u32时直接报错，而不是被截断
+fn number_arg<T>(name: &'static str) -> Arg<'static, 'static>
+    where T: FromStr,
+          T::Err: Display,
+{
+    Arg::with_name(name).required(true).validator(|v| {
+        v.parse::<T>().map(|_| ()).map_err(|err| format!("`{}` is not a number: {}", v, err))
+    })
+}
+
 fn main() {
-    println!("main");
+    let matches = App::new("project_1")
+        .about("small number-theory helpers backed by project_2")
+        .subcommand(SubCommand::with_name("gcd").about("greatest common divisor")
+            .arg(number_arg::<u64>("A")).arg(number_arg::<u64>("B")))
+        .subcommand(SubCommand::with_name("primes").about("all primes up to LIMIT")
+            .arg(number_arg::<u64>("LIMIT")))
+        .subcommand(SubCommand::with_name("fib").about("the N-th Fibonacci number")
+            .arg(number_arg::<u32>("N")))
+        .get_matches();
+
+    // validator已经保证参数是合法的数字
+    let number = |m: &clap::ArgMatches, name: &str| m.value_of(name).unwrap().parse::<u64>().unwrap();
+    match matches.subcommand() {
+        ("gcd", Some(m)) => println!("{}", project_2::gcd(number(m, "A"), number(m, "B"))),
+        ("primes", Some(m)) => println!("{:?}", project_2::primes_up_to(number(m, "LIMIT") as usize)),
+        ("fib", Some(m)) => match project_2::fibonacci(u32::try_from(number(m, "N")).expect("validator rejects N above u32::MAX")) {
+            Some(value) => println!("{}", value),
+            None => println!("overflows u64"),
+        },
+        _ => println!("{}", matches.usage()),
+    }
 }
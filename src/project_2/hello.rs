@@ -0,0 +1,150 @@
+/*
+    project_2的构建脚本（Cargo.toml中的`build = "hello.rs"`）
+
+    cargo会在编译project_2之前先编译并运行本文件，它做两件事：
+
+        1. 读取 data/units.tsv 这张声明式的单位表，生成每个单位的常量、
+           全部单位的列表，以及按符号查找单位的函数；
+        2. 记录构建信息：git describe（拿不到时为None）、profile和目标平台。
+
+    生成的代码写到 $OUT_DIR/generated.rs，lib.rs 中用`include!`引入。
+*/
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const TABLE: &str = "data/units.tsv";
+
+// 单位表中的一行
+struct Row {
+    ident: String,
+    symbol: String,
+    factor: f64,
+    doc: String,
+}
+
+// 解析单位表；表有问题时返回带行号的错误信息
+fn parse_table(text: &str) -> Result<Vec<Row>, String> {
+    let mut rows: Vec<Row> = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+        if columns.len() != 4 {
+            return Err(format!("line {}: expected 4 tab separated columns, found {}", line_no, columns.len()));
+        }
+        let (ident, symbol, factor, doc) = (columns[0], columns[1], columns[2], columns[3]);
+
+        let is_const_ident = ident.starts_with(|c: char| c.is_ascii_uppercase())
+            && ident.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !is_const_ident {
+            return Err(format!("line {}: `{}` is not an UPPER_SNAKE_CASE identifier", line_no, ident));
+        }
+        let factor: f64 = factor.parse()
+            .map_err(|err| format!("line {}: bad factor `{}`: {}", line_no, factor, err))?;
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(format!("line {}: factor must be a positive number", line_no));
+        }
+        if let Some(prev) = rows.iter().find(|row| row.ident == ident || row.symbol == symbol) {
+            return Err(format!("line {}: `{}`/`{}` duplicates {}", line_no, ident, symbol, prev.ident));
+        }
+        rows.push(Row { ident: ident.to_string(), symbol: symbol.to_string(), factor, doc: doc.to_string() });
+    }
+    if rows.is_empty() {
+        return Err("the table is empty".to_string());
+    }
+    Ok(rows)
+}
+
+// `git describe`的结果；不在git仓库中或没有git命令时返回None
+fn git_describe() -> Option<String> {
+    let output = Command::new("git").args(["describe", "--always", "--dirty", "--tags"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let describe = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if describe.is_empty() { None } else { Some(describe) }
+}
+
+// 提交变化时重新运行构建脚本，让GIT_DESCRIBE保持最新
+fn rerun_if_git_changed() {
+    let output = Command::new("git").args(["rev-parse", "--git-dir"]).output();
+    if let Ok(output) = output {
+        if output.status.success() {
+            let git_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            for file in &["HEAD", "index"] {
+                let path = Path::new(&git_dir).join(file);
+                if path.exists() {
+                    println!("cargo:rerun-if-changed={}", path.display());
+                }
+            }
+        }
+    }
+}
+
+fn generate(rows: &[Row], describe: Option<&str>, profile: &str, target: &str) -> String {
+    let mut code = String::new();
+    // 写入String不会失败，这里的unwrap是安全的
+    writeln!(code, "// @generated by hello.rs from {}, do not edit.", TABLE).unwrap();
+    writeln!(code).unwrap();
+
+    for row in rows {
+        writeln!(code, "/// {} (`{}`)", row.doc, row.symbol).unwrap();
+        writeln!(code, "pub const {}: Unit = Unit {{ name: {:?}, symbol: {:?}, meters: {:?} }};",
+                 row.ident, row.doc, row.symbol, row.factor).unwrap();
+    }
+    writeln!(code).unwrap();
+
+    writeln!(code, "/// 单位表中的全部单位，顺序与表中一致").unwrap();
+    writeln!(code, "pub const UNITS: &[Unit] = &[").unwrap();
+    for row in rows {
+        writeln!(code, "    {},", row.ident).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "/// 按符号查找单位，例如`unit(\"km\")`").unwrap();
+    writeln!(code, "pub fn unit(symbol: &str) -> Option<Unit> {{").unwrap();
+    writeln!(code, "    match symbol {{").unwrap();
+    for row in rows {
+        writeln!(code, "        {:?} => Some({}),", row.symbol, row.ident).unwrap();
+    }
+    writeln!(code, "        _ => None,").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+    writeln!(code).unwrap();
+
+    writeln!(code, "/// 构建信息，由构建脚本在编译时写入").unwrap();
+    writeln!(code, "pub mod build_info {{").unwrap();
+    writeln!(code, "    /// `git describe --always --dirty --tags`的结果，构建时拿不到则为None").unwrap();
+    writeln!(code, "    pub const GIT_DESCRIBE: Option<&str> = {:?};", describe).unwrap();
+    writeln!(code, "    /// 构建所用的profile，如`debug`或`release`").unwrap();
+    writeln!(code, "    pub const PROFILE: &str = {:?};", profile).unwrap();
+    writeln!(code, "    /// 目标平台的target triple").unwrap();
+    writeln!(code, "    pub const TARGET: &str = {:?};", target).unwrap();
+    writeln!(code, "}}").unwrap();
+    code
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=hello.rs");
+    println!("cargo:rerun-if-changed={}", TABLE);
+    rerun_if_git_changed();
+
+    let text = fs::read_to_string(TABLE).unwrap_or_else(|err| panic!("failed to read {}: {}", TABLE, err));
+    let rows = parse_table(&text).unwrap_or_else(|err| panic!("{}: {}", TABLE, err));
+
+    // 这些环境变量由cargo在运行构建脚本时设置
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let profile = env::var("PROFILE").expect("PROFILE is set by cargo");
+    let target = env::var("TARGET").expect("TARGET is set by cargo");
+
+    let code = generate(&rows, git_describe().as_deref(), &profile, &target);
+    let dest = Path::new(&out_dir).join("generated.rs");
+    fs::write(&dest, code).unwrap_or_else(|err| panic!("failed to write {}: {}", dest.display(), err));
+}
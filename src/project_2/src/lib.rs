@@ -1,3 +1,127 @@
+/*
+    project_2的库（lib）部分
+
+    提供一些简单的数论和统计函数，
+    tests/ 目录下的集成测试会像外部crate一样，通过`project_2::`来调用它们。
+
+    长度单位的常量、UNITS、unit()和build_info由构建脚本hello.rs根据
+    data/units.tsv 生成，见文件末尾的`include!`。
+*/
+
+/// 最大公约数（辗转相除法）
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// 最小公倍数，结果溢出u64时返回None
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// 判断n是否为素数
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut i = 2;
+    // 不写成i * i <= n：n接近u64::MAX时i会增长到2^32，i * i溢出
+    while i <= n / i {
+        if n.is_multiple_of(i) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// 不超过limit的所有素数（埃拉托斯特尼筛法）
+pub fn primes_up_to(limit: usize) -> Vec<usize> {
+    if limit < 2 {
+        return vec![];
+    }
+    let mut is_composite = vec![false; limit + 1];
+    let mut primes = vec![];
+    for i in 2..=limit {
+        if is_composite[i] {
+            continue;
+        }
+        primes.push(i);
+        let mut multiple = i * i;
+        while multiple <= limit {
+            is_composite[multiple] = true;
+            multiple += i;
+        }
+    }
+    primes
+}
+
+/// 斐波那契数列的第n项（从F(0) = 0开始），溢出u64时返回None
+pub fn fibonacci(n: u32) -> Option<u64> {
+    // next提前算出下一项，溢出时记为None，只有真正需要它时才返回None
+    let (mut current, mut next) = (0u64, Some(1u64));
+    for _ in 0..n {
+        let following = next?;
+        next = current.checked_add(following);
+        current = following;
+    }
+    Some(current)
+}
+
+/// 算术平均数，空切片返回None
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// 中位数，空切片返回None
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN is not allowed"));
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+/// 长度单位，meters是1个该单位等于多少米
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub meters: f64,
+}
+
+impl Unit {
+    /// 把以self为单位的value换算成以to为单位的值
+    pub fn convert(self, value: f64, to: Unit) -> f64 {
+        value * self.meters / to.meters
+    }
+}
+
+/// 按符号换算长度，任一符号不在单位表中时返回None
+pub fn convert(value: f64, from: &str, to: &str) -> Option<f64> {
+    Some(unit(from)?.convert(value, unit(to)?))
+}
+
+// 构建脚本生成的代码：单位常量、UNITS、unit()以及build_info模块
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -6,8 +130,26 @@ mod tests {
     }
 
     #[test]
+    #[should_panic(expected = "fail to panic!")]
     fn test_it_panics() {
         panic!("fail to panic!");
     }
-}
 
+    #[test]
+    fn test_gcd_with_zero() {
+        assert_eq!(super::gcd(0, 7), 7);
+        assert_eq!(super::gcd(7, 0), 7);
+    }
+
+    #[test]
+    fn test_generated_table_matches_source() {
+        // 生成的UNITS与data/units.tsv中的数据行一一对应
+        let rows = include_str!("../data/units.tsv").lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .count();
+        assert_eq!(super::UNITS.len(), rows);
+        for unit in super::UNITS {
+            assert_eq!(super::unit(unit.symbol), Some(*unit));
+        }
+    }
+}
@@ -1,3 +1,4 @@
 fn main() {
     println!("Hello, world!");
+    println!("primes up to 30: {:?}", project_2::primes_up_to(30));
 }
@@ -0,0 +1,43 @@
+// 集成测试：构建脚本hello.rs生成的单位表和构建信息
+use project_2::{build_info, convert, unit, Unit, FOOT, INCH, KILOMETER, METER, MILE, UNITS};
+
+#[test]
+fn test_generated_constants() {
+    assert_eq!(METER, Unit { name: "meter", symbol: "m", meters: 1.0 });
+    assert_eq!(KILOMETER.symbol, "km");
+    assert_eq!(INCH.meters, 0.0254);
+    assert_eq!(UNITS.first(), Some(&project_2::MILLIMETER));
+    assert_eq!(UNITS.last(), Some(&project_2::NAUTICAL_MILE));
+}
+
+#[test]
+fn test_lookup_by_symbol() {
+    assert_eq!(unit("mi"), Some(MILE));
+    assert_eq!(unit("ft"), Some(FOOT));
+    assert_eq!(unit("furlong"), None);
+    assert_eq!(unit("M"), None);
+}
+
+// 浮点换算会有舍入误差，只比较到1e-9
+fn approx(actual: Option<f64>, expected: f64) -> bool {
+    actual.is_some_and(|actual| (actual - expected).abs() < 1e-9)
+}
+
+#[test]
+fn test_convert() {
+    assert!(approx(convert(1.0, "ft", "in"), 12.0));
+    assert!(approx(convert(2.5, "km", "m"), 2500.0));
+    assert!(approx(Some(MILE.convert(1.0, KILOMETER)), 1.609344));
+    assert_eq!(convert(1.0, "km", "parsec"), None);
+}
+
+#[test]
+fn test_build_info() {
+    // 测试总是在debug或release之一下编译
+    assert!(build_info::PROFILE == "debug" || build_info::PROFILE == "release", "{}", build_info::PROFILE);
+    // target triple至少包含架构、厂商和系统三段，例如x86_64-unknown-linux-gnu
+    assert!(build_info::TARGET.split('-').count() >= 3, "{}", build_info::TARGET);
+    if let Some(describe) = build_info::GIT_DESCRIBE {
+        assert!(!describe.is_empty());
+    }
+}
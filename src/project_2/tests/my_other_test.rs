@@ -0,0 +1,21 @@
+// 集成测试：统计相关的函数
+use project_2::{mean, median};
+
+#[test]
+fn test_mean() {
+    assert_eq!(mean(&[]), None);
+    assert_eq!(mean(&[1.0, 2.0, 3.0, 4.0]), Some(2.5));
+}
+
+#[test]
+fn test_median() {
+    assert_eq!(median(&[]), None);
+    assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+    assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+}
+
+#[test]
+#[should_panic(expected = "NaN is not allowed")]
+fn test_median_rejects_nan() {
+    median(&[1.0, f64::NAN]);
+}
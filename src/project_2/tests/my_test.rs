@@ -0,0 +1,53 @@
+// 集成测试：数论相关的函数
+// tests目录下的每个文件都会被编译成一个单独的crate，只能访问project_2的公有接口
+use project_2::{fibonacci, gcd, is_prime, lcm, primes_up_to};
+
+#[test]
+fn test_gcd_and_lcm() {
+    assert_eq!(gcd(12, 18), 6);
+    assert_eq!(gcd(17, 5), 1);
+    assert_eq!(lcm(4, 6), Some(12));
+    assert_eq!(lcm(0, 6), Some(0));
+    assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+}
+
+#[test]
+fn test_is_prime() {
+    let primes: Vec<u64> = (0..30).filter(|&n| is_prime(n)).collect();
+    assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert!(is_prime(1_000_000_007));
+}
+
+#[test]
+fn test_is_prime_near_u64_max() {
+    // u64::MAX = 3 * 5 * 17 * 257 * 641 * 65537 * 6700417
+    assert!(!is_prime(u64::MAX));
+    assert!(!is_prime(u64::MAX - 1));
+    // 小于2^32的最大素数
+    assert!(is_prime(4_294_967_291));
+}
+
+// 小于u64::MAX的最大素数：试除要一直进行到2^32，release下也要半分钟左右
+//     cargo test -p project_2 --release -- --ignored
+#[test]
+#[ignore]
+fn test_is_prime_largest_u64_prime() {
+    assert!(is_prime(18_446_744_073_709_551_557));
+}
+
+#[test]
+fn test_primes_up_to_agrees_with_is_prime() {
+    let sieve = primes_up_to(1000);
+    let naive: Vec<usize> = (0..=1000).filter(|&n| is_prime(n as u64)).collect();
+    assert_eq!(sieve, naive);
+    assert!(primes_up_to(1).is_empty());
+}
+
+#[test]
+fn test_fibonacci() {
+    let first: Vec<u64> = (0..10).map(|n| fibonacci(n).unwrap()).collect();
+    assert_eq!(first, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    // F(93)是u64能表示的最后一项
+    assert_eq!(fibonacci(93), Some(12_200_160_415_121_876_738));
+    assert_eq!(fibonacci(94), None);
+}
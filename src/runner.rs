@@ -0,0 +1,213 @@
+/*
+    主题运行器runner
+
+    每个主题模块通过`DEMOS`常量登记自己的演示入口（Demo），
+    lib.rs中的`topics!`宏在声明主题模块的同时收集它们，registry()再把它们汇总起来，
+    二进制程序只负责把命令行参数交给cli()。
+
+    演示函数不直接println!，而是写入传进来的`&mut dyn Write`，
+    这样运行器可以按主题捕获输出，并用catch_unwind把panic转换为失败状态。
+
+    用法：
+        rust-exercise list
+        rust-exercise run thread::map_reduce
+        rust-exercise run-all
+*/
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+/// 演示入口：把输出写进sink
+pub type DemoFn = fn(&mut dyn Write) -> io::Result<()>;
+
+/// 一个登记到运行器中的演示
+pub struct Demo {
+    /// 形如`thread::map_reduce`的名字，`run`子命令按它查找
+    pub name: &'static str,
+    /// 一句话说明，`list`子命令会打印出来
+    pub about: &'static str,
+    pub run: DemoFn,
+}
+
+/// 一次演示的运行结果
+pub struct Report {
+    pub name: &'static str,
+    /// 演示写出的全部内容（即使失败，也保留失败之前的输出）
+    pub output: Vec<u8>,
+    /// Err中是panic信息或io错误的描述
+    pub result: Result<(), String>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// 所有主题登记的演示，按名字排序
+///
+/// 主题列表由lib.rs中的`topics!`宏生成，新增主题时不需要修改这里
+pub fn registry() -> Vec<&'static Demo> {
+    let mut demos: Vec<&'static Demo> = crate::TOPICS.iter().flat_map(|group| group.iter()).collect();
+    demos.sort_by_key(|demo| demo.name);
+    demos
+}
+
+/// 按名字查找演示
+pub fn find(name: &str) -> Option<&'static Demo> {
+    registry().into_iter().find(|demo| demo.name == name)
+}
+
+/// 运行一个演示并捕获它的输出
+///
+/// 演示中的panic不会传播出来，而是记录在Report::result中
+pub fn run_captured(demo: &Demo) -> Report {
+    let mut output = Vec::new();
+    let result = match panic::catch_unwind(AssertUnwindSafe(|| (demo.run)(&mut output))) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(err)) => Err(format!("io error: {}", err)),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    };
+    Report { name: demo.name, output, result }
+}
+
+// panic的payload通常是&str或String
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+const USAGE: &str = "usage:
+    rust-exercise list              列出所有演示
+    rust-exercise run <name>        运行一个演示，例如：run thread::map_reduce
+    rust-exercise run-all           依次运行所有演示";
+
+/// 命令行入口，返回进程退出码
+///
+/// 0：成功；1：有演示失败；2：参数错误或找不到演示
+pub fn cli<I: IntoIterator<Item=String>>(args: I, out: &mut dyn Write) -> io::Result<i32> {
+    let args: Vec<String> = args.into_iter().collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["list"] => {
+            for demo in registry() {
+                writeln!(out, "{:<32} {}", demo.name, demo.about)?;
+            }
+            Ok(0)
+        }
+        ["run", name] => match find(name) {
+            Some(demo) => {
+                let report = run_captured(demo);
+                out.write_all(&report.output)?;
+                if let Err(reason) = &report.result {
+                    writeln!(out, "demo {} failed: {}", demo.name, reason)?;
+                    return Ok(1);
+                }
+                Ok(0)
+            }
+            None => {
+                writeln!(out, "unknown demo `{}`, try `list`", name)?;
+                Ok(2)
+            }
+        },
+        ["run-all"] => {
+            let reports: Vec<Report> = registry().into_iter().map(run_captured).collect();
+            for report in &reports {
+                writeln!(out, "===== {} =====", report.name)?;
+                out.write_all(&report.output)?;
+                match &report.result {
+                    Ok(()) => writeln!(out, "----- ok")?,
+                    Err(reason) => writeln!(out, "----- FAILED: {}", reason)?,
+                }
+            }
+            let failed = reports.iter().filter(|report| !report.is_ok()).count();
+            writeln!(out, "{} demos, {} passed, {} failed", reports.len(), reports.len() - failed, failed)?;
+            Ok(if failed == 0 { 0 } else { 1 })
+        }
+        _ => {
+            writeln!(out, "{}", USAGE)?;
+            Ok(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn boom(_: &mut dyn Write) -> io::Result<()> {
+        panic!("boom")
+    }
+
+    fn half_then_boom(out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "before")?;
+        panic!("after {}", 1)
+    }
+
+    #[test]
+    fn test_registry_names_are_unique() {
+        let names: Vec<&str> = registry().iter().map(|demo| demo.name).collect();
+        let mut deduped = names.clone();
+        deduped.dedup();
+        assert_eq!(names, deduped);
+        assert!(find("thread::map_reduce").is_some());
+        assert!(find("no::such_demo").is_none());
+    }
+
+    #[test]
+    fn test_every_topic_registers_a_demo() {
+        assert!(crate::TOPICS.iter().all(|group| !group.is_empty()));
+        assert!(find("variable_bindings").is_some());
+        assert!(find("traits::iterators").is_some());
+    }
+
+    #[test]
+    fn test_every_registered_demo_runs() {
+        for demo in registry() {
+            let report = run_captured(demo);
+            assert!(report.is_ok(), "{} failed: {:?}", demo.name, report.result);
+            assert!(!report.output.is_empty(), "{} wrote nothing", demo.name);
+        }
+    }
+
+    #[test]
+    fn test_panic_is_captured() {
+        let demo = Demo { name: "test::boom", about: "", run: boom };
+        let report = run_captured(&demo);
+        assert_eq!(report.result, Err("panicked: boom".to_string()));
+
+        // panic之前的输出仍然保留
+        let demo = Demo { name: "test::half", about: "", run: half_then_boom };
+        let report = run_captured(&demo);
+        assert_eq!(report.output, b"before\n");
+        assert_eq!(report.result, Err("panicked: after 1".to_string()));
+    }
+
+    #[test]
+    fn test_cli() {
+        let mut out = Vec::new();
+        assert_eq!(cli(args(&["list"]), &mut out).unwrap(), 0);
+        assert!(String::from_utf8(out).unwrap().contains("thread::map_reduce"));
+
+        let mut out = Vec::new();
+        assert_eq!(cli(args(&["run", "enum_linked_list"]), &mut out).unwrap(), 0);
+        assert_eq!(String::from_utf8(out).unwrap(), "3\n2,1,0,Nil\n");
+
+        let mut out = Vec::new();
+        assert_eq!(cli(args(&["run", "nope"]), &mut out).unwrap(), 2);
+        assert_eq!(cli(args(&[]), &mut out).unwrap(), 2);
+        assert_eq!(cli(args(&["run"]), &mut out).unwrap(), 2);
+
+        let mut out = Vec::new();
+        assert_eq!(cli(args(&["run-all"]), &mut out).unwrap(), 0);
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.ends_with(&format!("{} demos, {} passed, 0 failed\n", registry().len(), registry().len())));
+    }
+}
@@ -9,10 +9,10 @@
     编译器（通过借用检查）静态地保证了引用总是指向有效的对象。
     也就是说，当存在引用指向一个对象时，该对象`不能被销毁`。
 */
-mod mutability;
-mod freezing;
-mod aliasing;
-mod the_ref_pattern;
+pub mod mutability;
+pub mod freezing;
+pub mod aliasing;
+pub mod the_ref_pattern;
 
 #[cfg(test)]
 mod tests {
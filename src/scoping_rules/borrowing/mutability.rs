@@ -10,6 +10,7 @@
 mod tests {
     // `&'static str` 是一个对分配在`只读内存区`的字符串的引用
     #[derive(Copy, Clone)]
+    #[allow(dead_code)]
     struct Book {
         author: &'static str,
         title: &'static str,
@@ -17,11 +18,13 @@ mod tests {
     }
 
     // 此函数接受一个对 Book 类型的引用
+    #[allow(dead_code)]
     fn borrow_book(book: &Book) {
         println!("I immutably borrowed {} - {} - {} edition", book.author, book.title, book.year)
     }
 
     // 此函数接受一个对可变的 Book 类型的引用，它把年份 `year` 改为 2020 年
+    #[allow(dead_code)]
     fn new_edition(book: &mut Book) {
         book.year = 2020;
         println!("I mutably borrowed {} - {} - {} edition", book.author, book.title, book.year)
@@ -10,6 +10,7 @@ mod tests {
     struct Point(i32, i32);
 
     #[test]
+    #[allow(clippy::toplevel_ref_arg)]
     fn test_the_ref_pattern() {
         let c = 'A';
 
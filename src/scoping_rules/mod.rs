@@ -2,6 +2,47 @@
     作用域在所有权（ownership）、借用（borrow）和生命周期（lifetime）中起着重要作用。
     也就是说，作用域告诉编译器什么时候借用是合法的、什么时候资源可以释放、以及变量何时被创建或销毁。
 */
-mod raii;
-mod ownership_and_moves;
-mod borrowing;
\ No newline at end of file
+pub mod raii;
+pub mod ownership_and_moves;
+
+pub mod borrowing;
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "scoping_rules", about: "RAII的析构顺序、move与借用", run: demo },
+];
+
+// 离开作用域时记下自己的名字，用来观察析构的顺序
+struct Noisy<'a> {
+    name: &'static str,
+    dropped: &'a RefCell<Vec<&'static str>>,
+}
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.dropped.borrow_mut().push(self.name);
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // RAII：变量离开作用域时按声明的相反顺序析构
+    let dropped = RefCell::new(vec![]);
+    {
+        let _a = Noisy { name: "a", dropped: &dropped };
+        let _b = Noisy { name: "b", dropped: &dropped };
+    }
+    writeln!(out, "drop order: {:?}", dropped.borrow())?;
+
+    // 所有权：赋值把Box的所有权move给新变量，boxed不能再使用
+    let boxed = Box::new(1024);
+    let moved = boxed;
+    writeln!(out, "moved: {}", moved)?;
+
+    // 借用：同一时间可以有多个不可变借用
+    let text = String::from("borrowing");
+    let (r1, r2) = (&text, &text);
+    writeln!(out, "borrowed: {} {}", r1, r2.len())
+}
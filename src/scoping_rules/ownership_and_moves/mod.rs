@@ -1,4 +1,4 @@
-mod mutability;
+pub mod mutability;
 /*
     所有权和移动
     因为变量要负责释放它们拥有的资源，所以资源只能拥有`一个`所有者。
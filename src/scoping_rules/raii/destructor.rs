@@ -15,6 +15,7 @@ mod tests {
 
     #[test]
     fn test_destructor() {
+        #[allow(unused_variables)]
         let x = S;
         println!("this is just a mark.");
     }
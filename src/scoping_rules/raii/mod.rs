@@ -7,10 +7,11 @@
     这种行为避免了资源泄漏（resource leak）,
     程序员不用手动释放内存或者担心内存泄漏（memory leak）了。
 */
-mod destructor;
+pub mod destructor;
 
 mod tests {
     // 在堆上分配一个整型数据
+    #[allow(dead_code)]
     fn create_box() {
         let _box1 = Box::new(1024);
         // `_box1` 在这里(离开create_box函数的作用域时)被销毁，内存得到释放
@@ -0,0 +1,221 @@
+/*
+    快照测试snapshot
+
+    很多练习只是把结果println!出来，测试本身什么都不断言。
+    快照测试把演示写进`Write`的内容捕获成字符串，再与仓库中的`.snap`文件逐字比较：
+
+        - 内容一致：测试通过；
+        - 内容不一致：测试失败，并打印按行比较的diff；
+        - 设置环境变量 UPDATE_SNAPSHOTS=1 时，用本次输出覆盖`.snap`文件（新增快照也用这种方式）。
+
+    快照文件统一放在 <crate根目录>/snapshots/ 下，名字中的`::`会替换成`__`。
+*/
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::runner::DemoFn;
+
+/// 打开更新模式的环境变量
+pub const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// 快照比较成功时的情况
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// 与快照一致
+    Matched,
+    /// 更新模式下写入（或重写）了快照
+    Updated,
+}
+
+/// 快照比较失败的原因
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// 快照文件不存在
+    Missing { path: PathBuf },
+    /// 内容不一致，diff中`-`为快照内容，`+`为实际输出
+    Mismatch { path: PathBuf, diff: String },
+    Io(io::Error),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Missing { path } => write!(
+                f, "snapshot {} does not exist, rerun with {}=1 to create it", path.display(), UPDATE_ENV),
+            SnapshotError::Mismatch { path, diff } => write!(
+                f, "snapshot {} does not match (rerun with {}=1 to accept):\n{}", path.display(), UPDATE_ENV, diff),
+            SnapshotError::Io(err) => write!(f, "snapshot io error: {}", err),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(err: io::Error) -> Self {
+        SnapshotError::Io(err)
+    }
+}
+
+/// 仓库中存放快照的目录
+pub fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots")
+}
+
+/// 快照名对应的文件名
+pub fn file_name(name: &str) -> String {
+    format!("{}.snap", name.replace("::", "__"))
+}
+
+/// 是否处于更新模式
+pub fn update_mode() -> bool {
+    std::env::var_os(UPDATE_ENV).is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// 运行一个演示，返回它写出的全部内容
+pub fn capture(demo: DemoFn) -> String {
+    let mut out = Vec::new();
+    demo(&mut out).expect("writing to a Vec<u8> never fails");
+    String::from_utf8(out).expect("demo output is not valid UTF-8")
+}
+
+/// 在dir目录下比较名为name的快照
+pub fn check_in(dir: &Path, name: &str, actual: &str, update: bool) -> Result<Outcome, SnapshotError> {
+    let path = dir.join(file_name(name));
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => Some(expected),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    match expected {
+        Some(ref expected) if expected == actual => Ok(Outcome::Matched),
+        _ if update => {
+            fs::create_dir_all(dir)?;
+            fs::write(&path, actual)?;
+            Ok(Outcome::Updated)
+        }
+        None => Err(SnapshotError::Missing { path }),
+        Some(expected) => Err(SnapshotError::Mismatch { diff: diff(&expected, actual), path }),
+    }
+}
+
+/// 在仓库的snapshots目录下比较快照，是否更新由环境变量决定
+pub fn check(name: &str, actual: &str) -> Result<Outcome, SnapshotError> {
+    check_in(&snapshot_dir(), name, actual, update_mode())
+}
+
+/// 快照不一致时panic，panic信息中带有diff
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    if let Err(err) = check(name, actual) {
+        panic!("{}", err);
+    }
+}
+
+/// 捕获演示的输出，并与快照比较
+#[track_caller]
+pub fn assert_demo_snapshot(name: &str, demo: DemoFn) {
+    assert_snapshot(name, &capture(demo));
+}
+
+/// 按行比较两段文本
+///
+/// 基于最长公共子序列（LCS）：公共行以两个空格开头，
+/// 只在expected中的行以`- `开头，只在actual中的行以`+ `开头。
+/// 末尾换行符不同也会单独指出来。
+pub fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j]：old[i..]与new[j..]的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+
+    if expected.ends_with('\n') != actual.ends_with('\n') {
+        out.push_str(if actual.ends_with('\n') {
+            "+ <newline at end of output>\n"
+        } else {
+            "- <newline at end of output>\n"
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::TempDir;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc\n", "a\nb\nc\n"), "  a\n  b\n  c\n");
+        assert_eq!(diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n- b\n+ x\n  c\n");
+        assert_eq!(diff("a\nb\n", "a\nb\nc\n"), "  a\n  b\n+ c\n");
+        assert_eq!(diff("a\nb\n", "b\n"), "- a\n  b\n");
+        assert_eq!(diff("a\n", "a"), "  a\n- <newline at end of output>\n");
+    }
+
+    #[test]
+    fn test_check_in() {
+        let temp = TempDir::new("snapshot").unwrap();
+        // 用一个尚不存在的子目录，顺便验证更新模式会自动创建目录
+        let dir = temp.join("snapshots");
+
+        // 不存在的快照：非更新模式下报Missing，更新模式下创建
+        match check_in(&dir, "demo::one", "hello\n", false) {
+            Err(SnapshotError::Missing { path }) => assert!(path.ends_with("demo__one.snap")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(check_in(&dir, "demo::one", "hello\n", true).unwrap(), Outcome::Updated);
+        assert_eq!(check_in(&dir, "demo::one", "hello\n", false).unwrap(), Outcome::Matched);
+
+        // 内容不一致时返回diff，且不会改动快照
+        match check_in(&dir, "demo::one", "world\n", false) {
+            Err(err @ SnapshotError::Mismatch { .. }) => {
+                let text = err.to_string();
+                assert!(text.contains("- hello\n+ world\n"), "{}", text);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(fs::read_to_string(dir.join("demo__one.snap")).unwrap(), "hello\n");
+
+        // 更新模式下覆盖旧快照
+        assert_eq!(check_in(&dir, "demo::one", "world\n", true).unwrap(), Outcome::Updated);
+        assert_eq!(fs::read_to_string(dir.join("demo__one.snap")).unwrap(), "world\n");
+    }
+}
@@ -11,31 +11,54 @@
     Trait Object本质上是指针，它可以指向不同的类型：指向的具体类型不同，调用的方法也就不同。
 */
 
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-#[cfg(test)]
-mod tests {
-    //  假设有一个trait Bird，有另外两个类型都实现了这个trait，我们要设计一个函数，既可以接受 Duck 作为参数，也可以接受 Swan 作为参数 。
+//  假设有一个trait Bird，有另外两个类型都实现了这个trait，我们要设计一个函数，既可以接受 Duck 作为参数，也可以接受 Swan 作为参数 。
 
-    trait Bird {
-        fn fly(&self);
-    }
+trait Bird {
+    fn fly(&self) -> &'static str;
+}
 
-    // 两个实现类
-    struct Duck;
+// 两个实现类
+struct Duck;
 
-    impl Bird for Duck {
-        fn fly(&self) {
-            println!("Duck flies!")
-        }
+impl Bird for Duck {
+    fn fly(&self) -> &'static str {
+        "Duck flies!"
     }
+}
 
-    struct Swan;
+struct Swan;
 
-    impl Bird for Swan {
-        fn fly(&self) {
-            println!("Swan flies!")
-        }
+impl Bird for Swan {
+    fn fly(&self) -> &'static str {
+        "Swan flies!"
     }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "static_and_dynamic_dispatch", about: "泛型的静态分派与trait object的动态分派", run: demo },
+];
+
+// 静态分派：编译器为每个T生成一份
+fn fly_static<T: Bird>(bird: T) -> &'static str {
+    bird.fly()
+}
+
+// 动态分派：运行时通过虚函数表找到fly
+fn fly_dynamic(bird: Box<dyn Bird>) -> &'static str {
+    bird.fly()
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "static: {} {}", fly_static(Duck), fly_static(Swan))?;
+    writeln!(out, "dynamic: {} {}", fly_dynamic(Box::new(Duck)), fly_dynamic(Box::new(Swan)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_static_and_dynamic_dispatch_v1() {
@@ -45,15 +68,16 @@ mod tests {
             fn test(arg:Bird) {}
             fn test() -> Bird {}
 
-            Rust编译器是不允许直接使用 trait作为参数类型和返回类型的。这也是trait跟许多语言中的“interface” 的一个区别 。
+            Rust编译器是不允许直接使用 trait作为参数类型和返回类型的。
+            （见 ui/dispatch_unsized_param.rs 和 ui/dispatch_unsized_return.rs）这也是trait跟许多语言中的“interface” 的一个区别 。
             有两种选择。一种是利用泛型:
             fn test<T:Bird>(arg:T) {
-                arg.fly()
+                println!("{}", arg.fly())
             }
         */
 
         fn test<T: Bird>(arg: T) {
-            arg.fly();
+            println!("{}", arg.fly());
         }
 
         let duck = Duck {};
@@ -80,12 +104,12 @@ mod tests {
             根据不同需求，可以用不同的指针类型，如 Box/& /&mut 等
 
             fn test(arg:Box<dyn Bird>) {
-                arg.fly()
+                println!("{}", arg.fly())
             }
         */
 
         fn test(arg: Box<dyn Bird>) {
-            arg.fly()
+            println!("{}", arg.fly())
         }
 
         let duck = Box::new(Duck {});
@@ -102,4 +126,4 @@ mod tests {
             这就是“动态分派” 。
         */
     }
-}
\ No newline at end of file
+}
@@ -11,12 +11,14 @@
 
 #[cfg(test)]
 mod tests {
+    #[allow(dead_code)]
     struct Test1 {
         x: f32,
         y: f64,
     }
 
     // Test2 由 Test1组成
+    #[allow(dead_code)]
     struct Test2 {
         field1: Test1,
         field2: Test1,
@@ -34,6 +34,7 @@ mod tests {
         // 创建两个不同的HashSet对象
         // 可以利用vec!宏来快速创建(HashSet类型必须显式标注出来)
         let mut set1: HashSet<i32> = vec![1, 2, 3, 4, 5].into_iter().collect();
+        #[allow(unused_mut)]
         let mut set2: HashSet<i32> = vec![3, 4, 5, 6, 7, 8].into_iter().collect();
         println!("{:?}", set1);
 
@@ -8,15 +8,48 @@
         - 堆分配的指针（heap allocated pointers）: Box<i32>
 */
 
-mod box_stack_heap;
-mod vector;
-mod strings;
-mod string_literals_and_escapes;
-mod option;
-mod result;
-mod result_with_question_mark;
-mod panic;
-mod hashmap;
-mod alternate_or_custom_key_types;
-mod hashset;
-mod rc;
+pub mod box_stack_heap;
+pub mod vector;
+pub mod strings;
+pub mod string_literals_and_escapes;
+pub mod option;
+pub mod result;
+pub mod result_with_question_mark;
+pub mod panic;
+pub mod hashmap;
+pub mod alternate_or_custom_key_types;
+pub mod hashset;
+pub mod rc;
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::rc::Rc;
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "std_library_types", about: "Box、Vec、String、Option、Result、HashMap与Rc", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let boxed = Box::new(1024);
+    writeln!(out, "Box: {}", boxed)?;
+
+    let mut numbers: Vec<i32> = (1..=3).collect();
+    numbers.push(4);
+    writeln!(out, "Vec: {:?}", numbers)?;
+
+    let mut text = String::from("std");
+    text.push_str("_library_types");
+    writeln!(out, "String: {}", text)?;
+
+    writeln!(out, "Option: {:?} {:?}", numbers.first(), numbers.get(10))?;
+    writeln!(out, "Result: {:?} {:?}", result::checked::divide(1.0, 2.0), result::checked::sqrt(-1.0))?;
+
+    let mut ages = HashMap::new();
+    ages.insert("Michael.W", 18);
+    writeln!(out, "HashMap: {:?}", ages.get("Michael.W"))?;
+
+    let shared = Rc::new(text);
+    let other = Rc::clone(&shared);
+    writeln!(out, "Rc: {} strong_count={}", other, Rc::strong_count(&shared))
+}
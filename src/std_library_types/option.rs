@@ -33,6 +33,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_literal_unwrap)]
     fn test_option() {
         // 正常除法
         try_division(1024, 2);
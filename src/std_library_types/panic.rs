@@ -29,6 +29,8 @@ mod tests {
     }
 
     #[test]
+    // 本示例最终会panic，用should_panic标记
+    #[should_panic]
     fn test_panic() {
         // 首先在堆上创建一个对象
         let s = Box::new(S { name: "Michael.W" });
@@ -11,10 +11,12 @@
 */
 
 // 定义一个mod，用来进行数学运算
-mod checked {
+pub mod checked {
     // 自定义一种数学错误
     // 由于要让本mod外的代码调用，所以可见性设为pub
-    #[derive(Debug)]        // 用于错误原因打印
+    /// 自定义的数学运算错误
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]        // 用于错误原因打印
+    #[allow(clippy::enum_variant_names)]
     pub enum MichaelMathError {
         // 自定义三种数学错误类型：
         // 除数为0
@@ -26,10 +28,12 @@ mod checked {
     }
 
     // 出于便捷性，定义Result类型别名
+    /// 数学运算结果
     pub type MichaelResult = Result<f64, MichaelMathError>;
 
     // 定义运算逻辑
     // 返回值为自定义Result类型
+    /// 除法：divident / divisor
     pub fn divide(divident: f64, divisor: f64) -> MichaelResult {
         if divisor == 0.0 {
             // 如果除数为0
@@ -42,6 +46,7 @@ mod checked {
 
     // 定义开平方运算
     // 返回值为自定义Result类型
+    /// 开平方
     pub fn sqrt(num: f64) -> MichaelResult {
         if num < 0.0 {
             // 如果被开方数为负数
@@ -53,6 +58,7 @@ mod checked {
 
     // 定义自然对数运算(log e (N))
     // 返回值为自定义Result类型
+    /// 自然对数
     pub fn ln(num: f64) -> MichaelResult {
         if num < 0.0 {
             // 如果被开方数为负数
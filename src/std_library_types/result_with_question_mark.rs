@@ -10,15 +10,17 @@
 */
 
 // 继续用result.rs文件中的例子做对比
-mod checked {
-    #[derive(Debug)]
+pub mod checked {
+    /// 自定义的数学运算错误
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[allow(clippy::enum_variant_names)]
     pub enum MichaelMathError {
         MichaelDivisionByZero,
         MichaelNegativeSquareRoot,
         MichaelNegativeLogarithm,
     }
 
-    type MichaelResult = Result<f64, MichaelMathError>;
+    pub type MichaelResult = Result<f64, MichaelMathError>;
 
     pub fn div(divident: f64, divisor: f64) -> MichaelResult {
         if divisor == 0.0 {
@@ -63,7 +65,7 @@ mod checked {
     // 中间计算过程
     // 因为？运算符只能用在返回值为Result或Option的函数中
     // 所以要额外定义这样一个满足上述条件的中间函数
-    fn run_op(num1: f64, num2: f64) -> MichaelResult {
+    pub fn run_op(num1: f64, num2: f64) -> MichaelResult {
         let result_div = div(num1, num2)?;
         let result_ln = ln(result_div)?;
         sqrt(result_ln)
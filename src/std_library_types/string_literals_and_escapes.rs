@@ -12,6 +12,7 @@
 
     字面量中出现的字符串或字符定界符必须转义："\""（在字符串字面量中表示一个"）、'\''（字符'的表示）
 */
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -64,6 +65,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(invalid_from_utf8)]
     fn test_byte_string() {
         // 切记：&str 和 String 都必须是合法有效的 UTF-8 序列
         // 如果想要非 UTF-8 字符串，或者一个字节数组（字节数组中大部分为文本）
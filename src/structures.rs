@@ -1,6 +1,27 @@
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "structures", about: "结构体与结构体更新语法", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    let point = Point { x: 1.024, y: 2.048 };
+    // 用结构体更新语法，其余字段取自point
+    let point1 = Point { x: 4.096, ..point };
+    writeln!(out, "point: ({}, {})", point.x, point.y)?;
+    writeln!(out, "point1: ({}, {})", point1.x, point1.y)
+}
+
 #[cfg(test)]
 mod tests {
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct Point {
         x: f32,
         y: f32,
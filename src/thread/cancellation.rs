@@ -0,0 +1,282 @@
+/*
+    协作式取消与截止时间
+
+    线程一旦开始运行，外部无法（也不应该）强行终止它：被杀掉的线程可能正持有锁、写了一半数据。
+    Rust中通常采用`协作式`的取消：
+
+        - 调用者持有CancellationToken，需要停止时调用cancel()；
+        - 长时间运行的任务定期检查is_cancelled()，发现被取消后尽快整理好已有的部分结果并返回；
+        - 需要等待的任务用token.wait_timeout代替thread::sleep，取消时会被立即唤醒。
+
+    CancellationToken可以clone，所有克隆共享同一个状态（一个原子变量加一个Condvar）。
+
+    run_with_deadline(duration, job)把job放在当前线程执行，另起一个看门狗线程计时，
+    到时间还没结束就取消token。返回的Outcome带有状态Completed或TimedOut，以及job交回的（部分）结果。
+    job返回与截止时间到达几乎同时发生时，两个线程争抢同一个标志，先抢到的一方决定状态：
+    job先返回就是Completed，看门狗不会再取消token；看门狗先到，token被取消，状态为TimedOut。
+*/
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// token的状态
+const ACTIVE: u8 = 0;
+const CANCELLED: u8 = 1;
+const DEADLINE_EXCEEDED: u8 = 2;
+
+/// 取消的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    /// 调用了cancel()
+    Cancelled,
+    /// 超过了截止时间
+    DeadlineExceeded,
+}
+
+struct Inner {
+    state: AtomicU8,
+    // Condvar必须配合一个Mutex使用，这里的Mutex不保护任何数据
+    lock: Mutex<()>,
+    condvar: Condvar,
+}
+
+/// 可以clone的取消令牌，所有克隆共享同一个状态
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            inner: Arc::new(Inner { state: AtomicU8::new(ACTIVE), lock: Mutex::new(()), condvar: Condvar::new() }),
+        }
+    }
+
+    /// 取消，并唤醒所有正在wait的线程。重复取消没有效果
+    pub fn cancel(&self) {
+        self.cancel_with(CANCELLED);
+    }
+
+    // 只有第一次取消生效，原因以第一次为准
+    fn cancel_with(&self, reason: u8) {
+        if self.inner.state.compare_exchange(ACTIVE, reason, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            // 先拿到锁再通知：正在wait_timeout中检查状态的线程要么已经看到了新状态，要么已经进入等待
+            let _guard = self.inner.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.inner.condvar.notify_all();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.state.load(Ordering::SeqCst) != ACTIVE
+    }
+
+    /// 被取消的原因，尚未取消时为None
+    pub fn reason(&self) -> Option<CancelReason> {
+        match self.inner.state.load(Ordering::SeqCst) {
+            CANCELLED => Some(CancelReason::Cancelled),
+            DEADLINE_EXCEEDED => Some(CancelReason::DeadlineExceeded),
+            _ => None,
+        }
+    }
+
+    /// 最多等待timeout，期间被取消会立即返回。返回值表示是否已被取消
+    ///
+    /// 可以当作能被取消打断的thread::sleep使用
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut guard = self.inner.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Condvar可能被虚假唤醒，所以要循环检查
+        while !self.is_cancelled() {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            guard = self.inner.condvar.wait_timeout(guard, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner()).0;
+        }
+        self.is_cancelled()
+    }
+
+    /// 一直等到被取消
+    pub fn wait(&self) {
+        let mut guard = self.inner.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        while !self.is_cancelled() {
+            guard = self.inner.condvar.wait(guard).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        CancellationToken::new()
+    }
+}
+
+/// 可能被中途停止的计算最终的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// 正常完成
+    Completed,
+    /// 被cancel()停止
+    Cancelled,
+    /// 超过截止时间被停止
+    TimedOut,
+}
+
+impl Status {
+    /// 根据token的状态得出计算的状态
+    pub fn of(token: &CancellationToken) -> Status {
+        match token.reason() {
+            None => Status::Completed,
+            Some(CancelReason::Cancelled) => Status::Cancelled,
+            Some(CancelReason::DeadlineExceeded) => Status::TimedOut,
+        }
+    }
+}
+
+/// 计算的状态，以及它交回的（可能只是部分的）结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<T> {
+    pub status: Status,
+    pub value: T,
+}
+
+impl<T> Outcome<T> {
+    pub fn is_completed(&self) -> bool {
+        self.status == Status::Completed
+    }
+}
+
+/// 在当前线程执行job，超过timeout仍未结束时取消传给它的token
+///
+/// job应当定期检查token，被取消后尽快返回已有的部分结果。
+/// 截止时间在job返回之前到达时，状态为TimedOut，否则为Completed
+pub fn run_with_deadline<R, F>(timeout: Duration, job: F) -> Outcome<R>
+    where F: FnOnce(&CancellationToken) -> R,
+{
+    let token = CancellationToken::new();
+    // job返回或者看门狗超时，谁先置位谁决定结果
+    let settled = AtomicBool::new(false);
+    let (done, finished) = mpsc::channel::<()>();
+    let (value, fired) = thread::scope(|scope| {
+        let watchdog = {
+            let (token, settled) = (&token, &settled);
+            scope.spawn(move || {
+                // job结束（或panic）时done被drop，recv_timeout返回Disconnected
+                let timed_out = matches!(finished.recv_timeout(timeout), Err(RecvTimeoutError::Timeout));
+                let fired = timed_out && !settled.swap(true, Ordering::SeqCst);
+                if fired {
+                    token.cancel_with(DEADLINE_EXCEEDED);
+                }
+                fired
+            })
+        };
+        let value = job(&token);
+        // 先记下job已经返回，再唤醒看门狗
+        settled.store(true, Ordering::SeqCst);
+        drop(done);
+        (value, watchdog.join().expect("watchdog does not panic"))
+    });
+    Outcome { status: if fired { Status::TimedOut } else { Status::Completed }, value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_is_shared_by_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        assert_eq!(Status::of(&clone), Status::Completed);
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.reason(), Some(CancelReason::Cancelled));
+        // 已经取消后，截止时间不会改变原因
+        clone.cancel_with(DEADLINE_EXCEEDED);
+        assert_eq!(token.reason(), Some(CancelReason::Cancelled));
+    }
+
+    #[test]
+    fn test_cancel_wakes_waiters() {
+        let token = CancellationToken::new();
+        let started = Instant::now();
+        let waiters: Vec<_> = (0..3)
+            .map(|_| {
+                let token = token.clone();
+                thread::spawn(move || token.wait_timeout(Duration::from_secs(30)))
+            })
+            .collect();
+        let blocked = {
+            let token = token.clone();
+            thread::spawn(move || token.wait())
+        };
+        thread::sleep(Duration::from_millis(20));
+        token.cancel();
+        for waiter in waiters {
+            assert!(waiter.join().unwrap());
+        }
+        blocked.join().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_wait_timeout_without_cancel() {
+        let token = CancellationToken::new();
+        assert!(!token.wait_timeout(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_run_with_deadline_completes() {
+        let outcome = run_with_deadline(Duration::from_secs(10), |token| {
+            assert!(!token.is_cancelled());
+            42
+        });
+        assert_eq!(outcome, Outcome { status: Status::Completed, value: 42 });
+    }
+
+    #[test]
+    fn test_finished_job_is_never_timed_out() {
+        // job在截止时间前后返回，状态必须与token是否被取消一致；返回之后token不会再被取消
+        for micros in [0, 500, 1000, 1500, 2000] {
+            let outcome = run_with_deadline(Duration::from_millis(1), |token| {
+                let started = Instant::now();
+                while started.elapsed() < Duration::from_micros(micros) {
+                    std::hint::spin_loop();
+                }
+                token.clone()
+            });
+            let token = outcome.value;
+            thread::sleep(Duration::from_millis(3));
+            assert_eq!(outcome.status == Status::TimedOut, token.is_cancelled(), "{} micros", micros);
+        }
+    }
+
+    #[test]
+    fn test_run_with_deadline_times_out_with_partial_results() {
+        let outcome = run_with_deadline(Duration::from_millis(50), |token| {
+            let mut done = vec![];
+            // 每一步10ms，一共需要10秒，只能完成一小部分
+            for step in 0..1000 {
+                if token.wait_timeout(Duration::from_millis(10)) {
+                    break;
+                }
+                done.push(step);
+            }
+            done
+        });
+        assert_eq!(outcome.status, Status::TimedOut);
+        assert!(!outcome.is_completed());
+        assert!(!outcome.value.is_empty() && outcome.value.len() < 1000, "{} steps", outcome.value.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "job failed")]
+    fn test_run_with_deadline_propagates_panic() {
+        run_with_deadline(Duration::from_secs(10), |_| -> () { panic!("job failed") });
+    }
+}
@@ -0,0 +1,484 @@
+/*
+    通用的map-reduce引擎
+
+    testcase_map_reduce中的例子为每个数据段各开一个线程，数据段还必须是`&'static str`。
+    这里把其中的套路抽出来：
+
+        input --chunker--> 若干数据块 --map_fn（在worker线程中）--> 中间结果 --reduce_fn--> 最终结果
+
+        - chunker在调用者线程中把输入切成数据块，数据块只要求是Send；
+        - 固定数量的worker线程从同一个队列中领取数据块，数据块再多，线程数也不会超过workers；
+        - 中间结果按数据块的顺序交给reduce_fn，所以reduce_fn不需要满足交换律。
+
+    worker用std::thread::scope创建，因此map_fn和数据块都可以借用调用者的数据，不要求'static。
+
+    map_reduce_with可以通过Options选择worker领取数据块的方式（Schedule）：
+        - SharedQueue：所有worker共用一个队列，数据块按需懒惰地从chunker中取出；
+        - WorkStealing：先收集全部数据块，交给work_stealing调度器，适合各块耗时差别很大的输入。
+
+    map_fn可能失败时使用try_map_reduce：map_fn返回Result，数据块中的panic也会被捕获，
+    二者都记录为带数据块序号的SegmentFailure，最终以MapReduceError返回，而不是让整个程序panic。
+    Options::errors决定遇到第一个错误就停止（FailFast），还是处理完所有数据块、收集全部错误（CollectAll）。
+
+    需要能够中途停止时使用map_reduce_cancellable：token被取消后，worker不再领取新的数据块，
+    map_fn也会拿到token，可以在处理很长的数据块时检查它并提前放弃。
+    已经完成的数据块仍然按顺序reduce，作为部分结果连同状态（Completed/Cancelled/TimedOut）一起返回。
+*/
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::Ordering;
+
+use super::cancellation::{CancellationToken, Outcome, Status};
+use super::sync::{thread, AtomicBool, Mutex};
+use super::work_stealing;
+use crate::runner::panic_message;
+
+/// worker领取数据块的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    /// 共用一个加锁的队列
+    SharedQueue,
+    /// 每个worker一个队列，空闲时从其他worker那里窃取
+    WorkStealing,
+}
+
+/// try_map_reduce遇到错误时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// 出现第一个错误后不再处理新的数据块，只报告一个错误
+    FailFast,
+    /// 处理完所有数据块，报告全部错误
+    CollectAll,
+}
+
+/// map_reduce_with和try_map_reduce的选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// worker线程数，必须大于0
+    pub workers: usize,
+    pub schedule: Schedule,
+    /// 只对try_map_reduce有效
+    pub errors: ErrorMode,
+}
+
+impl Options {
+    /// workers个线程，使用共享队列，遇到错误立即停止
+    pub fn new(workers: usize) -> Options {
+        Options { workers, schedule: Schedule::SharedQueue, errors: ErrorMode::FailFast }
+    }
+
+    pub fn schedule(mut self, schedule: Schedule) -> Options {
+        self.schedule = schedule;
+        self
+    }
+
+    pub fn errors(mut self, errors: ErrorMode) -> Options {
+        self.errors = errors;
+        self
+    }
+}
+
+/// 一个数据块失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureKind<E> {
+    /// map_fn返回了Err
+    Failed(E),
+    /// map_fn panic了，其中是panic信息
+    Panicked(String),
+}
+
+/// 某个数据块（segment）的失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentFailure<E> {
+    /// 数据块在chunker输出中的序号
+    pub segment: usize,
+    pub kind: FailureKind<E>,
+}
+
+/// try_map_reduce的错误：按数据块序号排列的失败列表，FailFast模式下只有一项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapReduceError<E> {
+    pub failures: Vec<SegmentFailure<E>>,
+}
+
+impl<E: Display> Display for MapReduceError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, failure) in self.failures.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            match &failure.kind {
+                FailureKind::Failed(err) => write!(f, "segment {}: {}", failure.segment, err)?,
+                FailureKind::Panicked(message) => write!(f, "segment {} panicked: {}", failure.segment, message)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: Debug + Display> Error for MapReduceError<E> {}
+
+/// 用workers个线程执行一次map-reduce
+///
+/// 没有任何数据块时返回None；map_fn中的panic会在所有worker结束后原样传播给调用者。
+/// workers为0时panic
+pub fn map_reduce<I, C, K, T, M, R>(input: I, chunker: C, map_fn: M, reduce_fn: R, workers: usize) -> Option<T>
+    where C: FnOnce(I) -> K,
+          K: IntoIterator,
+          K::IntoIter: Send,
+          K::Item: Send,
+          T: Send,
+          M: Fn(K::Item) -> T + Sync,
+          R: FnMut(T, T) -> T,
+{
+    map_reduce_with(input, chunker, map_fn, reduce_fn, Options::new(workers))
+}
+
+/// 按options指定的线程数和调度方式执行一次map-reduce，其余与map_reduce相同
+pub fn map_reduce_with<I, C, K, T, M, R>(input: I, chunker: C, map_fn: M, reduce_fn: R, options: Options) -> Option<T>
+    where C: FnOnce(I) -> K,
+          K: IntoIterator,
+          K::IntoIter: Send,
+          K::Item: Send,
+          T: Send,
+          M: Fn(K::Item) -> T + Sync,
+          R: FnMut(T, T) -> T,
+{
+    let results = execute(chunker(input), options, |_, chunk| map_fn(chunk));
+    results.into_iter().map(|(_, value)| value).reduce(reduce_fn)
+}
+
+/// map_fn可能失败的map-reduce
+///
+/// 所有数据块都成功时返回Ok，没有任何数据块时为Ok(None)；否则返回MapReduceError，
+/// 其中的失败（包括map_fn中的panic）带有数据块的序号，数量取决于options.errors
+pub fn try_map_reduce<I, C, K, T, E, M, R>(input: I, chunker: C, map_fn: M, reduce_fn: R, options: Options)
+    -> Result<Option<T>, MapReduceError<E>>
+    where C: FnOnce(I) -> K,
+          K: IntoIterator,
+          K::IntoIter: Send,
+          K::Item: Send,
+          T: Send,
+          E: Send,
+          M: Fn(K::Item) -> Result<T, E> + Sync,
+          R: FnMut(T, T) -> T,
+{
+    // FailFast模式下出现错误后置位，之后领到的数据块直接跳过
+    let stop = AtomicBool::new(false);
+    let outcomes = execute(chunker(input), options, |_, chunk| {
+        if stop.load(Ordering::Relaxed) {
+            return None;
+        }
+        let outcome = match panic::catch_unwind(AssertUnwindSafe(|| map_fn(chunk))) {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(err)) => Err(FailureKind::Failed(err)),
+            Err(payload) => Err(FailureKind::Panicked(panic_message(payload.as_ref()))),
+        };
+        if outcome.is_err() && options.errors == ErrorMode::FailFast {
+            stop.store(true, Ordering::Relaxed);
+        }
+        Some(outcome)
+    });
+
+    let mut values = vec![];
+    let mut failures = vec![];
+    for (segment, outcome) in outcomes {
+        match outcome {
+            Some(Ok(value)) => values.push(value),
+            Some(Err(kind)) => failures.push(SegmentFailure { segment, kind }),
+            // 被跳过的数据块，只会在已有错误时出现
+            None => {}
+        }
+    }
+    if failures.is_empty() {
+        return Ok(values.into_iter().reduce(reduce_fn));
+    }
+    // FailFast时各worker可能同时遇到了错误，只报告序号最小的一个
+    if options.errors == ErrorMode::FailFast {
+        failures.truncate(1);
+    }
+    Err(MapReduceError { failures })
+}
+
+/// 被取消的map-reduce交回的部分结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partial<T> {
+    /// 已完成的数据块reduce的结果，一个都没有完成时为None
+    pub value: Option<T>,
+    /// 已完成的数据块的序号，从小到大
+    pub completed: Vec<usize>,
+}
+
+/// 可以通过token中途停止的map-reduce
+///
+/// map_fn返回None表示它发现token已被取消，放弃了这个数据块。
+/// 返回的状态取自token：没有被取消时为Completed，此时completed包含全部数据块
+pub fn map_reduce_cancellable<I, C, K, T, M, R>(input: I, chunker: C, map_fn: M, reduce_fn: R, options: Options,
+                                               token: &CancellationToken) -> Outcome<Partial<T>>
+    where C: FnOnce(I) -> K,
+          K: IntoIterator,
+          K::IntoIter: Send,
+          K::Item: Send,
+          T: Send,
+          M: Fn(K::Item, &CancellationToken) -> Option<T> + Sync,
+          R: FnMut(T, T) -> T,
+{
+    let results = execute(chunker(input), options, |_, chunk| {
+        // 取消后领到的数据块直接跳过
+        if token.is_cancelled() {
+            return None;
+        }
+        map_fn(chunk, token)
+    });
+
+    let mut completed = vec![];
+    let mut values = vec![];
+    for (segment, value) in results {
+        if let Some(value) = value {
+            completed.push(segment);
+            values.push(value);
+        }
+    }
+    let value = values.into_iter().reduce(reduce_fn);
+    Outcome { status: Status::of(token), value: Partial { value, completed } }
+}
+
+// 按options对每个数据块执行f(序号, 数据块)，返回按序号排好的结果
+//
+// f中的panic会在所有worker结束后原样传播给调用者
+fn execute<K, T, F>(chunks: K, options: Options, f: F) -> Vec<(usize, T)>
+    where K: IntoIterator,
+          K::IntoIter: Send,
+          K::Item: Send,
+          T: Send,
+          F: Fn(usize, K::Item) -> T + Sync,
+{
+    let workers = options.workers;
+    assert!(workers > 0, "map_reduce needs at least one worker");
+    if options.schedule == Schedule::WorkStealing {
+        let chunks: Vec<(usize, K::Item)> = chunks.into_iter().enumerate().collect();
+        // work_stealing::run已经按任务顺序返回结果
+        return work_stealing::run(chunks, workers, |(index, chunk)| (index, f(index, chunk)));
+    }
+
+    // 所有worker共享同一个数据块队列，谁先空闲谁就领取下一块
+    let queue = Mutex::new(chunks.into_iter().enumerate());
+    let next_chunk = || queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).next();
+
+    let mut results: Vec<(usize, T)> = thread::scope(|scope| {
+        let handlers: Vec<_> = (0..workers)
+            .map(|_| scope.spawn(|| {
+                let mut mapped = vec![];
+                // 先取出数据块、释放锁，再调用f，避免持锁计算
+                while let Some((index, chunk)) = next_chunk() {
+                    mapped.push((index, f(index, chunk)));
+                }
+                mapped
+            }))
+            .collect();
+
+        // 逐个join；有worker panic时，等其余worker结束后把原始的panic信息继续抛出
+        let mut results = vec![];
+        let mut panicked = None;
+        for handler in handlers {
+            match handler.join() {
+                Ok(mapped) => results.extend(mapped),
+                Err(payload) => panicked = panicked.or(Some(payload)),
+            }
+        }
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+        results
+    });
+
+    // 按数据块的原始顺序排列
+    results.sort_by_key(|&(index, _)| index);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+    use crate::thread::cancellation::run_with_deadline;
+
+    #[test]
+    fn test_reduce_keeps_chunk_order() {
+        let words = vec!["a", "b", "c", "d", "e", "f", "g"];
+        // 字符串拼接不满足交换律，结果仍然按原始顺序
+        let joined = map_reduce(words, |words| words, |w| w.to_uppercase(), |a, b| a + &b, 3);
+        assert_eq!(joined.as_deref(), Some("ABCDEFG"));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let sum = map_reduce(Vec::<u32>::new(), |v| v, |x| x, |a, b| a + b, 4);
+        assert_eq!(sum, None);
+    }
+
+    #[test]
+    fn test_worker_count_is_bounded() {
+        // 1000个数据块，只允许出现3个不同的线程
+        let threads = map_reduce(
+            0..1000,
+            |range| range,
+            |_| {
+                let mut set = HashSet::new();
+                set.insert(thread::current().id());
+                set
+            },
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+            3,
+        ).unwrap();
+        assert!(!threads.is_empty() && threads.len() <= 3, "{} threads", threads.len());
+    }
+
+    #[test]
+    fn test_borrowed_input() {
+        // 输入、数据块和map_fn都借用了本函数中的数据
+        let data: Vec<u64> = (1..=100).collect();
+        let factor = 2;
+        let sum = map_reduce(&data[..], |slice| slice.chunks(7), |chunk| chunk.iter().map(|x| x * factor).sum::<u64>(), |a, b| a + b, 4);
+        assert_eq!(sum, Some(5050 * 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "bad chunk 3")]
+    fn test_panic_is_propagated() {
+        map_reduce(0..10, |range| range, |i| if i == 3 { panic!("bad chunk {}", i) } else { i }, |a, b| a + b, 2);
+    }
+
+    #[test]
+    fn test_work_stealing_schedule() {
+        let options = Options::new(3).schedule(Schedule::WorkStealing);
+        let joined = map_reduce_with(vec!["a", "b", "c", "d"], |v| v, |w| w.to_string(), |a, b| a + &b, options);
+        assert_eq!(joined.as_deref(), Some("abcd"));
+        let data: Vec<u64> = (1..=1000).collect();
+        let sum = map_reduce_with(&data[..], |s| s.chunks(10), |c| c.iter().sum::<u64>(), |a, b| a + b, options);
+        assert_eq!(sum, Some(500500));
+    }
+
+    // 偶数成功，奇数失败，7会panic
+    fn check(x: u32) -> Result<u32, String> {
+        if x == 7 {
+            panic!("seven");
+        }
+        if x.is_multiple_of(2) { Ok(x) } else { Err(format!("{} is odd", x)) }
+    }
+
+    #[test]
+    fn test_try_map_reduce_ok() {
+        for schedule in [Schedule::SharedQueue, Schedule::WorkStealing].iter() {
+            let options = Options::new(3).schedule(*schedule);
+            let sum = try_map_reduce((0..10).map(|x| x * 2), |v| v, check, |a, b| a + b, options);
+            assert_eq!(sum, Ok(Some(90)));
+            let empty = try_map_reduce(Vec::new(), |v| v, check, |a, b| a + b, options);
+            assert_eq!(empty, Ok(None));
+        }
+    }
+
+    #[test]
+    fn test_try_map_reduce_collect_all() {
+        for schedule in [Schedule::SharedQueue, Schedule::WorkStealing].iter() {
+            let options = Options::new(4).schedule(*schedule).errors(ErrorMode::CollectAll);
+            let err = try_map_reduce(0..10, |v| v, check, |a, b| a + b, options).unwrap_err();
+            let segments: Vec<usize> = err.failures.iter().map(|f| f.segment).collect();
+            assert_eq!(segments, vec![1, 3, 5, 7, 9]);
+            assert_eq!(err.failures[0].kind, FailureKind::Failed("1 is odd".to_string()));
+            assert_eq!(err.failures[3].kind, FailureKind::Panicked("seven".to_string()));
+            assert!(err.to_string().contains("segment 7 panicked: seven"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_try_map_reduce_fail_fast() {
+        let processed = AtomicUsize::new(0);
+        let options = Options::new(1);
+        let err = try_map_reduce(0..1000, |v| v, |x| {
+            processed.fetch_add(1, Ordering::SeqCst);
+            check(x)
+        }, |a, b| a + b, options).unwrap_err();
+        assert_eq!(err.failures, vec![SegmentFailure { segment: 1, kind: FailureKind::Failed("1 is odd".to_string()) }]);
+        // 只有一个worker，出错之后的数据块都被跳过了
+        assert_eq!(processed.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_cancellable_completes() {
+        let token = CancellationToken::new();
+        let outcome = map_reduce_cancellable(0..10u32, |v| v, |x, _| Some(x), |a, b| a + b, Options::new(3), &token);
+        assert_eq!(outcome.status, Status::Completed);
+        assert_eq!(outcome.value, Partial { value: Some(45), completed: (0..10).collect() });
+    }
+
+    #[test]
+    fn test_cancel_stops_taking_chunks() {
+        let token = CancellationToken::new();
+        // 单个worker按顺序处理，第5个数据块处理完后取消
+        let outcome = map_reduce_cancellable(0..100u32, |v| v, |x, token| {
+            if x == 4 {
+                token.cancel();
+            }
+            Some(x)
+        }, |a, b| a + b, Options::new(1), &token);
+        assert_eq!(outcome.status, Status::Cancelled);
+        assert_eq!(outcome.value, Partial { value: Some(10), completed: vec![0, 1, 2, 3, 4] });
+    }
+
+    #[test]
+    fn test_deadline_returns_partial_results() {
+        for schedule in [Schedule::SharedQueue, Schedule::WorkStealing].iter() {
+            let options = Options::new(2).schedule(*schedule);
+            let outcome = run_with_deadline(Duration::from_millis(50), |token| {
+                map_reduce_cancellable(0..1000u64, |v| v, |x, token| {
+                    // 每个数据块需要10ms；被取消时放弃当前数据块
+                    if token.wait_timeout(Duration::from_millis(10)) { None } else { Some(x) }
+                }, |a, b| a + b, options, token)
+            });
+            assert_eq!(outcome.status, Status::TimedOut);
+            let partial = outcome.value;
+            assert_eq!(partial.status, Status::TimedOut);
+            let completed = &partial.value.completed;
+            assert!(!completed.is_empty() && completed.len() < 1000, "{} chunks", completed.len());
+            assert_eq!(partial.value.value, Some(completed.iter().map(|&i| i as u64).sum()));
+        }
+    }
+
+    #[cfg(model)]
+    #[test]
+    fn test_model_schedules_agree() {
+        // 两种调度方式在各种交错下都得到同样的、按顺序reduce的结果
+        for schedule in [Schedule::SharedQueue, Schedule::WorkStealing].iter() {
+            let options = Options::new(3).schedule(*schedule);
+            crate::thread::model::check(100, move || {
+                let joined = map_reduce_with(0..6u32, |v| v, |x| x.to_string(), |a, b| a + &b, options);
+                assert_eq!(joined.as_deref(), Some("012345"));
+            });
+        }
+    }
+
+    #[cfg(model)]
+    #[test]
+    fn test_model_fail_fast_reports_one_failure() {
+        crate::thread::model::check(100, || {
+            let options = Options::new(2);
+            let err = try_map_reduce(0..6, |v| v, check, |a, b| a + b, options).unwrap_err();
+            // 哪个奇数先失败取决于交错，但只会报告一个，并且确实是失败的数据块
+            assert_eq!(err.failures.len(), 1);
+            assert_eq!(err.failures[0].segment % 2, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one worker")]
+    fn test_zero_workers() {
+        map_reduce(0..10, |range| range, |i| i, |a, b| a + b, 0);
+    }
+}
@@ -4,7 +4,87 @@
     Rust 通过 spawn 函数提供了创建本地操作系统（native OS）线程的机制。
     该函数的参数是一个通过值捕获变量的闭包（moving closure）。
 */
-mod testcase_map_reduce;
+pub mod cancellation;
+pub mod map_reduce;
+pub mod model;
+pub mod par_slice;
+pub mod pipeline;
+pub mod scoped;
+pub mod sharded_map;
+pub mod streaming;
+pub mod testcase_map_reduce;
+pub mod thread_pool;
+pub mod work_stealing;
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+// 线程池、map-reduce与工作窃取调度器使用的线程和同步原语。
+// 平时（包括普通的cargo test）就是std；用`--cfg model`编译时换成model中的版本，由model::check的调度器控制：
+//     RUSTFLAGS="--cfg model" cargo test --lib thread::
+#[cfg(not(model))]
+mod sync {
+    pub use std::sync::atomic::{AtomicBool, AtomicUsize};
+    pub use std::sync::{mpsc, Condvar, Mutex, MutexGuard};
+    pub use std::thread;
+}
+
+#[cfg(model)]
+mod sync {
+    pub use super::model::sync::{AtomicBool, AtomicUsize, Condvar, Mutex, MutexGuard};
+    pub use super::model::{mpsc, thread};
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "thread::spawn", about: "创建多个线程并等待它们结束", run: demo_for_thread },
+    Demo { name: "thread::map_reduce", about: "用线程实现map-reduce，计算每一位数字的和", run: testcase_map_reduce::demo },
+    Demo { name: "thread::pipeline", about: "用有界channel连接的多级流水线处理成绩文件", run: pipeline::demo },
+    Demo { name: "thread::pool", about: "用线程池执行任务，而不是每个任务开一个线程", run: demo_for_thread_pool },
+    Demo { name: "thread::scoped", about: "用作用域线程并行处理借用的Vec与String", run: scoped::demo },
+    Demo { name: "thread::streaming", about: "逐批读取文件的流式map-reduce，并报告每秒处理的行数", run: streaming::demo },
+    Demo { name: "thread::word_count", about: "用同一个map-reduce引擎统计单词出现的次数", run: testcase_map_reduce::demo_word_count },
+];
+
+// 多线程的demo
+// 注：每个线程把要输出的内容作为返回值交回主线程，由主线程按顺序写出
+pub fn demo_for_thread(out: &mut dyn Write) -> io::Result<()> {
+    const THREAD_NUM: i32 = 10;
+    // 用于存放各个线程handler的Vec
+    let mut thread_handlers = vec![];
+
+    for i in 0..THREAD_NUM {
+        let handler = std::thread::spawn(
+            move || format!("THREAD {} is running", i)
+        );
+        //handler存入Vec
+        thread_handlers.push(handler);
+    }
+
+    for handler in thread_handlers {
+        // 等待线程结束。返回一个结果
+        let line = handler.join().expect("thread panicked");
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+// 线程池的demo：与demo_for_thread相同的10个任务，只用3个worker线程执行
+pub fn demo_for_thread_pool(out: &mut dyn Write) -> io::Result<()> {
+    const JOB_NUM: i32 = 10;
+    let pool = thread_pool::ThreadPool::new(3);
+
+    let handles: Vec<_> = (0..JOB_NUM)
+        .map(|i| pool.submit(move || format!("JOB {} is running", i)))
+        .collect();
+    for handle in handles {
+        let line = handle.join().expect("job panicked");
+        writeln!(out, "{}", line)?;
+    }
+
+    // 等待所有任务结束并回收worker线程
+    pool.shutdown().expect("no job panicked");
+    writeln!(out, "pool shut down")
+}
 
 #[cfg(test)]
 mod tests {
@@ -15,6 +95,15 @@ mod tests {
 
     const THREAD_NUM: i32 = 10;
 
+    #[test]
+    fn test_demo_for_thread() {
+        let mut out = Vec::new();
+        super::demo_for_thread(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 10);
+        assert_eq!(text.lines().next(), Some("THREAD 0 is running"));
+    }
+
     #[test]
     fn test_thread() {
         // 提供一个 vector 来存放所创建的子线程（children）
@@ -0,0 +1,506 @@
+/*
+    确定性的并发模型检查
+
+    多线程的bug往往只在某种特定的交错（interleaving）下出现：操作系统决定线程的先后，
+    测试跑一百次都通过，第一百零一次才失败，而且无法重现。
+
+    这里的做法（与loom、shuttle等库的思路相同，但要简单得多）：
+
+        - model中的线程都是真正的操作系统线程，但同一时刻只允许其中一个运行；
+        - 每个被“插桩”的操作（Mutex加锁、原子操作、channel收发、spawn、join）之前都是一个调度点，
+          由调度器用带种子的伪随机数决定接下来运行哪个线程；
+        - 同样的种子产生同样的调度，失败的执行可以用它的种子原样重放；
+        - 所有线程都被阻塞时报告死锁，步数超过上限时报告可能的活锁。
+
+        model::check(200, || {
+            let counter = Arc::new(model::sync::AtomicUsize::new(0));
+            let handle = { ... model::thread::spawn(...) ... };
+            ...
+            assert_eq!(counter.load(SeqCst), 2);
+        });
+
+    check用不同的种子执行闭包多次，某次失败时panic，信息中带着种子；Checker::replay只执行那一个种子。
+
+    sync、mpsc和thread中的类型在模型检查之外与std中的同名类型行为一致。
+    thread模块中的线程池、map-reduce和工作窃取调度器只有在用`--cfg model`编译时才换成它们（见thread::sync，
+    与loom的`cfg(loom)`相同），对它们的模型检查测试也只在这时运行；普通的cargo test测试的是std版本：
+
+        RUSTFLAGS="--cfg model" cargo test --lib thread::
+
+    限制：
+        - 随机调度只是抽样，不保证覆盖所有交错；
+        - 所有操作都按顺序一致性（SeqCst）执行，不模拟更弱的内存序；
+        - 失败的那次执行中的线程会一直停在阻塞状态，不会被回收。
+*/
+pub mod mpsc;
+pub mod sync;
+pub mod thread;
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use crate::runner::panic_message;
+
+// 线程阻塞时等待的对象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resource {
+    // Mutex、Condvar或channel，用它的地址区分
+    Object(usize),
+    // 等待某个线程结束
+    Thread(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Run {
+    Runnable,
+    Blocked(Resource),
+    Finished,
+}
+
+struct ThreadState {
+    run: Run,
+    // 线程panic的信息，被join观察到之后清除
+    panic: Option<String>,
+}
+
+struct State {
+    threads: Vec<ThreadState>,
+    // 当前允许运行的线程
+    active: usize,
+    // xorshift的状态
+    rng: u64,
+    steps: usize,
+    max_steps: usize,
+    failure: Option<String>,
+    // 所有线程都已结束，或者已经失败
+    done: bool,
+}
+
+impl State {
+    // 随机选出下一个运行的线程，没有可运行的线程时返回false
+    fn schedule(&mut self) -> bool {
+        let runnable: Vec<usize> = (0..self.threads.len()).filter(|&id| self.threads[id].run == Run::Runnable).collect();
+        if runnable.is_empty() {
+            return false;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.active = runnable[(self.rng % runnable.len() as u64) as usize];
+        true
+    }
+
+    fn wake(&mut self, resource: Resource, all: bool) {
+        for thread in &mut self.threads {
+            if thread.run == Run::Blocked(resource) {
+                thread.run = Run::Runnable;
+                if !all {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn deadlock(&self) -> String {
+        let blocked: Vec<usize> = (0..self.threads.len())
+            .filter(|&id| matches!(self.threads[id].run, Run::Blocked(_)))
+            .collect();
+        format!("deadlock: threads {:?} are blocked forever", blocked)
+    }
+}
+
+// 一次执行中所有线程共享的调度器
+struct Execution {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+thread_local! {
+    // 当前线程所属的执行以及它在其中的编号；不在模型检查中的线程为None
+    static CONTEXT: RefCell<Option<(Arc<Execution>, usize)>> = const { RefCell::new(None) };
+}
+
+fn context() -> Option<(Arc<Execution>, usize)> {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+// 当前线程是否处于模型检查之中
+fn active() -> bool {
+    CONTEXT.with(|context| context.borrow().is_some())
+}
+
+// 调度点：由调度器决定接下来运行哪个线程（可能仍是自己）
+fn switch() {
+    if let Some((execution, id)) = context() {
+        execution.switch(id);
+    }
+}
+
+// 阻塞在resource上，直到被wake
+fn block_on(resource: Resource) {
+    if let Some((execution, id)) = context() {
+        execution.prepare_block(id, resource);
+        execution.park(id);
+    }
+}
+
+// 先登记为阻塞，之后再park。二者之间可以释放别的资源（Condvar::wait释放锁）
+fn prepare_block(resource: Resource) {
+    if let Some((execution, id)) = context() {
+        execution.prepare_block(id, resource);
+    }
+}
+
+fn park() {
+    if let Some((execution, id)) = context() {
+        execution.park(id);
+    }
+}
+
+fn wake(resource: Resource, all: bool) {
+    if let Some((execution, _)) = context() {
+        execution.lock().wake(resource, all);
+    }
+}
+
+// 等到线程id结束，并把它的panic标记为已观察
+fn wait_for(id: usize) {
+    if let Some((execution, me)) = context() {
+        loop {
+            execution.switch(me);
+            if execution.lock().threads[id].run == Run::Finished {
+                break;
+            }
+            execution.prepare_block(me, Resource::Thread(id));
+            execution.park(me);
+        }
+        execution.lock().threads[id].panic = None;
+    }
+}
+
+// 在新的操作系统线程中运行模型线程id
+fn run_thread<T>(execution: Arc<Execution>, id: usize, f: impl FnOnce() -> T) -> T {
+    CONTEXT.with(|context| *context.borrow_mut() = Some((Arc::clone(&execution), id)));
+    execution.wait_turn(id, execution.lock());
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CONTEXT.with(|context| *context.borrow_mut() = None);
+    execution.finish(id, result.as_ref().err().map(|payload| panic_message(payload.as_ref())));
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+impl Execution {
+    fn new(seed: u64, max_steps: usize) -> Execution {
+        let state = State {
+            threads: vec![],
+            active: 0,
+            // xorshift的状态不能为0
+            rng: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+            steps: 0,
+            max_steps,
+            failure: None,
+            done: false,
+        };
+        Execution { state: Mutex::new(state), changed: Condvar::new() }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 登记一个新线程，返回它的编号
+    fn register(&self) -> usize {
+        let mut state = self.lock();
+        state.threads.push(ThreadState { run: Run::Runnable, panic: None });
+        state.threads.len() - 1
+    }
+
+    // 等到轮到id运行。执行失败后不会再轮到任何线程
+    fn wait_turn(&self, id: usize, mut state: MutexGuard<'_, State>) {
+        while state.failure.is_some() || state.active != id {
+            state = self.changed.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    fn fail(&self, state: &mut State, message: String) {
+        if state.failure.is_none() {
+            state.failure = Some(message);
+        }
+        state.done = true;
+        self.changed.notify_all();
+    }
+
+    fn switch(&self, id: usize) {
+        let mut state = self.lock();
+        state.steps += 1;
+        if state.steps > state.max_steps {
+            let message = format!("step limit of {} exceeded (livelock?)", state.max_steps);
+            self.fail(&mut state, message);
+        } else {
+            state.schedule();
+            self.changed.notify_all();
+        }
+        self.wait_turn(id, state);
+    }
+
+    fn prepare_block(&self, id: usize, resource: Resource) {
+        self.lock().threads[id].run = Run::Blocked(resource);
+    }
+
+    // 让出运行权，直到再次被调度
+    fn park(&self, id: usize) {
+        let mut state = self.lock();
+        if state.schedule() {
+            self.changed.notify_all();
+        } else {
+            let message = state.deadlock();
+            self.fail(&mut state, message);
+        }
+        self.wait_turn(id, state);
+    }
+
+    fn finish(&self, id: usize, panic: Option<String>) {
+        let mut state = self.lock();
+        state.threads[id].run = Run::Finished;
+        state.wake(Resource::Thread(id), true);
+        // 0号线程执行的是被检查的闭包，它的panic（例如断言失败）就是失败
+        if id == 0 {
+            if let Some(message) = &panic {
+                let message = format!("thread 0 panicked: {}", message);
+                self.fail(&mut state, message);
+                return;
+            }
+        }
+        state.threads[id].panic = panic;
+        if state.threads.iter().all(|thread| thread.run == Run::Finished) {
+            state.done = true;
+            self.changed.notify_all();
+        } else if state.schedule() {
+            self.changed.notify_all();
+        } else {
+            let message = state.deadlock();
+            self.fail(&mut state, message);
+        }
+    }
+}
+
+// 用种子seed执行一次f
+fn execute(seed: u64, max_steps: usize, f: Arc<dyn Fn() + Send + Sync>) -> Result<(), String> {
+    let execution = Arc::new(Execution::new(seed, max_steps));
+    let id = execution.register();
+    let main = Arc::clone(&execution);
+    std::thread::Builder::new()
+        .name("model-main".to_string())
+        .spawn(move || run_thread(main, id, move || f()))
+        .expect("failed to spawn model thread");
+
+    let mut state = execution.lock();
+    while !state.done {
+        state = execution.changed.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+    if let Some(failure) = state.failure.take() {
+        return Err(failure);
+    }
+    // 没有被join的线程中的panic
+    match state.threads.iter().enumerate().find_map(|(id, thread)| thread.panic.as_ref().map(|message| (id, message))) {
+        Some((id, message)) => Err(format!("thread {} panicked: {}", id, message)),
+        None => Ok(()),
+    }
+}
+
+/// 失败的执行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// 重放这次执行用的种子
+    pub seed: u64,
+    pub message: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "schedule with seed {} failed: {}", self.seed, self.message)
+    }
+}
+
+impl Error for Failure {}
+
+/// 模型检查的配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checker {
+    iterations: usize,
+    seed: u64,
+    max_steps: usize,
+}
+
+impl Checker {
+    /// 执行iterations次，种子从0开始
+    pub fn new(iterations: usize) -> Checker {
+        Checker { iterations, seed: 0, max_steps: 100_000 }
+    }
+
+    /// 第一次执行的种子，之后每次加一
+    pub fn seed(mut self, seed: u64) -> Checker {
+        self.seed = seed;
+        self
+    }
+
+    /// 每次执行最多的调度步数，超过时认为发生了活锁
+    pub fn max_steps(mut self, max_steps: usize) -> Checker {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// 依次用每个种子执行f，返回第一次失败
+    pub fn run<F>(&self, f: F) -> Result<(), Failure>
+        where F: Fn() + Send + Sync + 'static,
+    {
+        let f: Arc<dyn Fn() + Send + Sync> = Arc::new(f);
+        for seed in self.seed..self.seed + self.iterations as u64 {
+            execute(seed, self.max_steps, Arc::clone(&f)).map_err(|message| Failure { seed, message })?;
+        }
+        Ok(())
+    }
+
+    /// 只用种子seed执行一次f，重现check报告的失败
+    pub fn replay<F>(seed: u64, f: F) -> Result<(), Failure>
+        where F: Fn() + Send + Sync + 'static,
+    {
+        Checker::new(1).seed(seed).run(f)
+    }
+}
+
+/// 用iterations个不同的调度执行f，任何一次失败都会panic，信息中带有失败的种子
+pub fn check<F>(iterations: usize, f: F)
+    where F: Fn() + Send + Sync + 'static,
+{
+    if let Err(failure) = Checker::new(iterations).run(f) {
+        panic!("{} (replay it with Checker::replay({}, ..))", failure, failure.seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sync::{AtomicUsize, Condvar, Mutex};
+    use super::*;
+    use std::sync::atomic::Ordering::SeqCst;
+
+    // 非原子的“读-改-写”：两个线程同时加一时可能丢失一次更新
+    fn racy_increment() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    let value = counter.load(SeqCst);
+                    counter.store(value + 1, SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.load(SeqCst), 2, "lost update");
+    }
+
+    #[test]
+    fn test_finds_lost_update_and_replays_it() {
+        let failure = Checker::new(100).run(racy_increment).unwrap_err();
+        assert!(failure.message.contains("lost update"), "{}", failure);
+        // 同一个种子得到同样的结果
+        assert_eq!(Checker::replay(failure.seed, racy_increment), Err(failure.clone()));
+        assert_eq!(Checker::replay(failure.seed, racy_increment), Err(failure));
+    }
+
+    #[test]
+    fn test_correct_code_passes() {
+        check(100, || {
+            let counter = Arc::new(AtomicUsize::new(0));
+            let total = Arc::new(Mutex::new(0));
+            let handles: Vec<_> = (0..3)
+                .map(|i| {
+                    let (counter, total) = (Arc::clone(&counter), Arc::clone(&total));
+                    thread::spawn(move || {
+                        counter.fetch_add(1, SeqCst);
+                        *total.lock().unwrap() += i;
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            assert_eq!(counter.load(SeqCst), 3);
+            assert_eq!(*total.lock().unwrap(), 3);
+        });
+    }
+
+    #[test]
+    fn test_detects_lock_order_deadlock() {
+        let failure = Checker::new(200).run(|| {
+            let a = Arc::new(Mutex::new(()));
+            let b = Arc::new(Mutex::new(()));
+            let (a2, b2) = (Arc::clone(&a), Arc::clone(&b));
+            let handle = thread::spawn(move || {
+                let _b = b2.lock().unwrap();
+                let _a = a2.lock().unwrap();
+            });
+            {
+                let _a = a.lock().unwrap();
+                let _b = b.lock().unwrap();
+            }
+            handle.join().unwrap();
+        }).unwrap_err();
+        assert!(failure.message.starts_with("deadlock"), "{}", failure);
+    }
+
+    #[test]
+    fn test_condvar_and_channel() {
+        check(100, || {
+            let pair = Arc::new((Mutex::new(false), Condvar::new()));
+            let (sender, receiver) = mpsc::channel();
+            let waiter = {
+                let pair = Arc::clone(&pair);
+                thread::spawn(move || {
+                    let (ready, condvar) = &*pair;
+                    let mut ready = ready.lock().unwrap();
+                    while !*ready {
+                        ready = condvar.wait(ready).unwrap();
+                    }
+                    sender.send(42).unwrap();
+                })
+            };
+            *pair.0.lock().unwrap() = true;
+            pair.1.notify_all();
+            assert_eq!(receiver.recv(), Ok(42));
+            // 唯一的发送端随线程结束被drop
+            assert!(receiver.recv().is_err());
+            waiter.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn test_unjoined_panic_is_reported() {
+        let failure = Checker::new(1).run(|| {
+            thread::spawn(|| panic!("background failure"));
+        }).unwrap_err();
+        assert_eq!(failure.message, "thread 1 panicked: background failure");
+    }
+
+    #[test]
+    fn test_primitives_work_outside_model() {
+        // 不在模型检查中时，与std中的类型行为相同
+        let mutex = Arc::new(Mutex::new(0));
+        let (sender, receiver) = mpsc::channel();
+        let handle = {
+            let mutex = Arc::clone(&mutex);
+            thread::spawn(move || {
+                *mutex.lock().unwrap() += 1;
+                sender.send(()).unwrap();
+            })
+        };
+        receiver.recv().unwrap();
+        handle.join().unwrap();
+        assert_eq!(*mutex.lock().unwrap(), 1);
+    }
+}
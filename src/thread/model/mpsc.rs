@@ -0,0 +1,138 @@
+/*
+    插桩的多生产者、单消费者channel
+
+    只实现了线程池用到的部分：无界的channel()、send、recv和try_recv，错误类型与std相同。
+    recv在队列为空时阻塞：模型中登记为阻塞，交给调度器；不在模型检查中时等待内部的std Condvar。
+*/
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+pub use std::sync::mpsc::{RecvError, SendError, TryRecvError};
+
+use super::Resource;
+
+struct Channel<T> {
+    state: Mutex<State<T>>,
+    available: Condvar,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver: bool,
+}
+
+impl<T> Channel<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn resource(&self) -> Resource {
+        Resource::Object(self as *const Self as usize)
+    }
+
+    // 有新数据或者发送端全部断开
+    fn notify(&self) {
+        self.available.notify_all();
+        super::wake(self.resource(), true);
+    }
+}
+
+/// 创建一个无界的channel
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel {
+        state: Mutex::new(State { queue: VecDeque::new(), senders: 1, receiver: true }),
+        available: Condvar::new(),
+    });
+    (Sender { channel: Arc::clone(&channel) }, Receiver { channel })
+}
+
+/// 发送端
+pub struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// 接收端已经drop时返回Err，其中是没有发出去的值
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        super::switch();
+        let mut state = self.channel.lock();
+        if !state.receiver {
+            return Err(SendError(value));
+        }
+        state.queue.push_back(value);
+        drop(state);
+        self.channel.notify();
+        Ok(())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.channel.lock().senders += 1;
+        Sender { channel: Arc::clone(&self.channel) }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.channel.lock();
+        state.senders -= 1;
+        let disconnected = state.senders == 0;
+        drop(state);
+        if disconnected {
+            self.channel.notify();
+        }
+    }
+}
+
+/// 接收端
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// 阻塞直到收到数据；队列为空并且所有发送端都已drop时返回Err
+    pub fn recv(&self) -> Result<T, RecvError> {
+        if super::active() {
+            loop {
+                super::switch();
+                match self.try_take() {
+                    Ok(value) => return Ok(value),
+                    Err(TryRecvError::Disconnected) => return Err(RecvError),
+                    Err(TryRecvError::Empty) => super::block_on(self.channel.resource()),
+                }
+            }
+        }
+        let mut state = self.channel.lock();
+        loop {
+            if let Some(value) = state.queue.pop_front() {
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = self.channel.available.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        super::switch();
+        self.try_take()
+    }
+
+    fn try_take(&self) -> Result<T, TryRecvError> {
+        let mut state = self.channel.lock();
+        match state.queue.pop_front() {
+            Some(value) => Ok(value),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.channel.lock().receiver = false;
+    }
+}
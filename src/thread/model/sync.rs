@@ -0,0 +1,185 @@
+/*
+    插桩的Mutex、Condvar与原子类型
+
+    每次加锁和原子操作之前都是一个调度点。锁被别的线程持有时，
+    模型中的线程登记为阻塞，由调度器换别的线程运行，解锁时再把它唤醒。
+    不在模型检查中时，直接使用内部的std类型。
+*/
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::Ordering;
+use std::sync::{self, LockResult, PoisonError, TryLockError};
+
+use super::Resource;
+
+/// 可以被模型检查的Mutex
+#[derive(Debug, Default)]
+pub struct Mutex<T: ?Sized> {
+    inner: sync::Mutex<T>,
+}
+
+impl<T> Mutex<T> {
+    pub fn new(value: T) -> Mutex<T> {
+        Mutex { inner: sync::Mutex::new(value) }
+    }
+
+    pub fn into_inner(self) -> LockResult<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    pub fn lock(&self) -> LockResult<MutexGuard<'_, T>> {
+        if !super::active() {
+            return wrap(self, self.inner.lock());
+        }
+        loop {
+            super::switch();
+            match self.inner.try_lock() {
+                Ok(guard) => return Ok(MutexGuard { mutex: self, inner: Some(guard) }),
+                Err(TryLockError::Poisoned(err)) => return wrap(self, Err(err)),
+                Err(TryLockError::WouldBlock) => super::block_on(self.resource()),
+            }
+        }
+    }
+
+    fn resource(&self) -> Resource {
+        Resource::Object(self as *const Self as *const () as usize)
+    }
+}
+
+fn wrap<'a, T: ?Sized>(mutex: &'a Mutex<T>, result: LockResult<sync::MutexGuard<'a, T>>) -> LockResult<MutexGuard<'a, T>> {
+    match result {
+        Ok(guard) => Ok(MutexGuard { mutex, inner: Some(guard) }),
+        Err(err) => Err(PoisonError::new(MutexGuard { mutex, inner: Some(err.into_inner()) })),
+    }
+}
+
+/// Mutex::lock返回的guard，drop时解锁并唤醒等待这把锁的线程
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+    // 只有Condvar::wait会提前取走
+    inner: Option<sync::MutexGuard<'a, T>>,
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner.as_deref().expect("guard is held")
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner.as_deref_mut().expect("guard is held")
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.inner = None;
+        super::wake(self.mutex.resource(), true);
+    }
+}
+
+/// 可以被模型检查的Condvar，模型中不会出现虚假唤醒
+#[derive(Debug, Default)]
+pub struct Condvar {
+    inner: sync::Condvar,
+}
+
+impl Condvar {
+    pub fn new() -> Condvar {
+        Condvar { inner: sync::Condvar::new() }
+    }
+
+    pub fn wait<'a, T>(&self, mut guard: MutexGuard<'a, T>) -> LockResult<MutexGuard<'a, T>> {
+        let mutex = guard.mutex;
+        if !super::active() {
+            let inner = guard.inner.take().expect("guard is held");
+            return wrap(mutex, self.inner.wait(inner));
+        }
+        // 先登记为阻塞再解锁：同一时刻只有一个线程在运行，二者之间不会有别的线程插进来，
+        // 因此不会错过解锁之后的notify
+        super::prepare_block(self.resource());
+        drop(guard);
+        super::park();
+        mutex.lock()
+    }
+
+    pub fn notify_one(&self) {
+        if super::active() {
+            super::wake(self.resource(), false);
+        } else {
+            self.inner.notify_one();
+        }
+    }
+
+    pub fn notify_all(&self) {
+        if super::active() {
+            super::wake(self.resource(), true);
+        } else {
+            self.inner.notify_all();
+        }
+    }
+
+    fn resource(&self) -> Resource {
+        Resource::Object(self as *const Self as usize)
+    }
+}
+
+macro_rules! atomic {
+    ($name:ident, $value:ty) => {
+        /// 每次操作之前都是一个调度点的原子类型，模型中所有操作都按SeqCst执行
+        #[derive(Debug, Default)]
+        pub struct $name {
+            inner: sync::atomic::$name,
+        }
+
+        impl $name {
+            pub const fn new(value: $value) -> $name {
+                $name { inner: sync::atomic::$name::new(value) }
+            }
+
+            pub fn load(&self, order: Ordering) -> $value {
+                super::switch();
+                self.inner.load(order)
+            }
+
+            pub fn store(&self, value: $value, order: Ordering) {
+                super::switch();
+                self.inner.store(value, order)
+            }
+
+            pub fn swap(&self, value: $value, order: Ordering) -> $value {
+                super::switch();
+                self.inner.swap(value, order)
+            }
+
+            pub fn compare_exchange(&self, current: $value, new: $value, success: Ordering, failure: Ordering)
+                -> Result<$value, $value> {
+                super::switch();
+                self.inner.compare_exchange(current, new, success, failure)
+            }
+
+            pub fn into_inner(self) -> $value {
+                self.inner.into_inner()
+            }
+        }
+    };
+}
+
+atomic!(AtomicBool, bool);
+atomic!(AtomicUsize, usize);
+
+impl AtomicUsize {
+    pub fn fetch_add(&self, value: usize, order: Ordering) -> usize {
+        super::switch();
+        self.inner.fetch_add(value, order)
+    }
+
+    pub fn fetch_sub(&self, value: usize, order: Ordering) -> usize {
+        super::switch();
+        self.inner.fetch_sub(value, order)
+    }
+}
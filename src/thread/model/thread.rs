@@ -0,0 +1,131 @@
+/*
+    插桩的线程：spawn、Builder与作用域线程scope
+
+    在模型检查中创建的线程会登记到调度器里，第一次被调度到才开始运行；
+    join时如果线程还没结束，就登记为阻塞，等它结束时被唤醒。
+    不在模型检查中时，直接使用std::thread。
+*/
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+
+pub use std::thread::{current, sleep, Result, ThreadId};
+
+/// 创建线程，失败时panic
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+    where F: FnOnce() -> T + Send + 'static,
+          T: Send + 'static,
+{
+    Builder::new().spawn(f).expect("failed to spawn thread")
+}
+
+/// 与std::thread::Builder相同，只支持设置线程名
+#[derive(Debug)]
+pub struct Builder {
+    inner: thread::Builder,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder { inner: thread::Builder::new() }
+    }
+
+    pub fn name(self, name: String) -> Builder {
+        Builder { inner: self.inner.name(name) }
+    }
+
+    pub fn spawn<F, T>(self, f: F) -> io::Result<JoinHandle<T>>
+        where F: FnOnce() -> T + Send + 'static,
+              T: Send + 'static,
+    {
+        match super::context() {
+            None => Ok(JoinHandle { inner: self.inner.spawn(f)?, id: None }),
+            Some((execution, _)) => {
+                let id = execution.register();
+                let inner = self.inner.spawn(move || super::run_thread(execution, id, f))?;
+                // 新线程可能先于创建者运行
+                super::switch();
+                Ok(JoinHandle { inner, id: Some(id) })
+            }
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// spawn返回的句柄
+#[derive(Debug)]
+pub struct JoinHandle<T> {
+    inner: thread::JoinHandle<T>,
+    // 模型中的线程编号
+    id: Option<usize>,
+}
+
+impl<T> JoinHandle<T> {
+    pub fn join(self) -> Result<T> {
+        if let Some(id) = self.id {
+            super::wait_for(id);
+        }
+        self.inner.join()
+    }
+}
+
+/// 与std::thread::scope相同：作用域中的线程可以借用外面的数据，返回前全部结束
+pub fn scope<'env, F, T>(f: F) -> T
+    where F: for<'scope> FnOnce(&Scope<'scope, 'env>) -> T,
+{
+    thread::scope(|inner| {
+        let scope = Scope { inner, spawned: Mutex::new(vec![]) };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        // std在离开作用域时的隐式join不经过调度器，模型中必须先等这些线程结束
+        for id in scope.spawned.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()) {
+            super::wait_for(id);
+        }
+        result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+    })
+}
+
+/// scope中的作用域
+pub struct Scope<'scope, 'env: 'scope> {
+    inner: &'scope thread::Scope<'scope, 'env>,
+    // 在模型中创建的线程
+    spawned: Mutex<Vec<usize>>,
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    pub fn spawn<F, T>(&self, f: F) -> ScopedJoinHandle<'scope, T>
+        where F: FnOnce() -> T + Send + 'scope,
+              T: Send + 'scope,
+    {
+        match super::context() {
+            None => ScopedJoinHandle { inner: self.inner.spawn(f), id: None },
+            Some((execution, _)) => {
+                let id = execution.register();
+                self.spawned.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(id);
+                let inner = self.inner.spawn(move || super::run_thread(execution, id, f));
+                super::switch();
+                ScopedJoinHandle { inner, id: Some(id) }
+            }
+        }
+    }
+}
+
+/// Scope::spawn返回的句柄
+pub struct ScopedJoinHandle<'scope, T> {
+    inner: thread::ScopedJoinHandle<'scope, T>,
+    id: Option<usize>,
+}
+
+impl<T> ScopedJoinHandle<'_, T> {
+    pub fn join(self) -> Result<T> {
+        if let Some(id) = self.id {
+            super::wait_for(id);
+        }
+        self.inner.join()
+    }
+}
@@ -0,0 +1,392 @@
+/*
+    切片的并行扩展方法ParallelSliceExt
+
+        let squares = data.par_map(|x| x * x);
+        let total: u64 = data.par_sum();
+        data.par_sort();
+
+    为[T]实现的扩展trait（Vec<T>通过Deref自动获得这些方法），用法与顺序的迭代器对应：
+
+        par_map / par_filter / par_for_each / par_reduce / par_sum / par_sort
+
+    - 任务在一个全局的ThreadPool中执行，线程数等于available_parallelism，借用数据通过ThreadPool::scope实现；
+    - 自适应切分（与rayon的思路相同）：一开始整个切片只是一个区间，每个worker从共享队列中取一个区间，
+      每次处理其中MIN_CHUNK个元素；处理之前如果发现有worker闲着，就把剩下部分的后一半切出去放进队列。
+      所以区间只在有人闲着时才继续切分：负载均匀时切分次数很少，某些元素特别慢时，
+      其余的元素会被不断切给空闲的worker；
+    - 输入不超过MIN_CHUNK个元素时直接在调用者线程中顺序执行，不值得为它派发任务；
+    - 输出的顺序与输入一致，par_reduce因此只要求操作满足结合律，不要求交换律；
+    - 在线程池的worker中（例如嵌套调用par_map）会改为顺序执行，避免所有worker都在等待而死锁。
+
+    par_sort需要事先确定的段长（静态切分）：先并行地排好每一段，再每轮把相邻的两段合并。合并用的是标准库的稳定排序：
+    它能识别已经有序的连续段（run），对两段有序数据排序只需线性时间，因此整体是稳定排序。
+*/
+use std::iter::Sum;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread;
+
+use super::thread_pool::ThreadPool;
+
+// par_sort中每个线程大约分到几段
+const SPLITS_PER_THREAD: usize = 4;
+// 自适应切分时每次处理的元素个数，也是par_sort每段至少的元素个数
+const MIN_CHUNK: usize = 64;
+
+// 所有并行扩展方法共用的线程池，第一次使用时创建
+fn pool() -> &'static ThreadPool {
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| ThreadPool::new(thread::available_parallelism().map_or(4, |n| n.get())))
+}
+
+// par_sort的段长：静态切分成大约`线程数 * SPLITS_PER_THREAD`段
+fn sort_run_len(len: usize) -> usize {
+    len.div_ceil(pool().size() * SPLITS_PER_THREAD).max(MIN_CHUNK)
+}
+
+// 是否应该在本线程中顺序执行
+fn sequential(len: usize, chunk_len: usize) -> bool {
+    len <= chunk_len || ThreadPool::is_worker_thread()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // f在锁外执行，锁不会因为f panic而中毒
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// 自适应切分的共享状态
+struct Splitter {
+    queue: Mutex<Queue>,
+    changed: Condvar,
+    // 正在等待区间的worker数，处理中的worker不加锁地读取它
+    idle: AtomicUsize,
+}
+
+struct Queue {
+    ranges: Vec<Range<usize>>,
+    // 正在处理区间的worker数
+    busy: usize,
+}
+
+impl Splitter {
+    // 取一个区间；队列为空并且没有worker还在处理（不会再切出新区间）时返回None
+    fn take(&self) -> Option<Range<usize>> {
+        let mut queue = lock(&self.queue);
+        loop {
+            if let Some(range) = queue.ranges.pop() {
+                queue.busy += 1;
+                return Some(range);
+            }
+            if queue.busy == 0 {
+                return None;
+            }
+            self.idle.fetch_add(1, Ordering::SeqCst);
+            queue = self.changed.wait(queue).unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.idle.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    fn give(&self, range: Range<usize>) {
+        lock(&self.queue).ranges.push(range);
+        self.changed.notify_one();
+    }
+
+    fn finish(&self) {
+        let mut queue = lock(&self.queue);
+        if thread::panicking() {
+            // f panic了，结果已经不完整，剩下的区间不必再处理，panic会由scope传播
+            queue.ranges.clear();
+        }
+        queue.busy -= 1;
+        if queue.busy == 0 && queue.ranges.is_empty() {
+            // 所有工作都已完成，叫醒等待的worker让它们退出
+            self.changed.notify_all();
+        }
+    }
+}
+
+// 区间处理完（包括f panic）时调用finish，否则其余的worker会一直等下去
+struct Finish<'a>(&'a Splitter);
+
+impl Drop for Finish<'_> {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
+
+// 用自适应切分在pool中并行地对data的各段执行f，按段的顺序返回结果
+fn map_adaptive<'a, T, R, F>(pool: &ThreadPool, data: &'a [T], f: F) -> Vec<R>
+    where T: Sync, R: Send, F: Fn(&'a [T]) -> R + Sync,
+{
+    if sequential(data.len(), MIN_CHUNK) {
+        return vec![f(data)];
+    }
+    let splitter = Splitter {
+        queue: Mutex::new(Queue { ranges: std::iter::once(0..data.len()).collect(), busy: 0 }),
+        changed: Condvar::new(),
+        idle: AtomicUsize::new(0),
+    };
+    // 每一段的起点与结果
+    let results = Mutex::new(Vec::new());
+    let worker = || {
+        let mut local = vec![];
+        while let Some(Range { start, mut end }) = splitter.take() {
+            let _finish = Finish(&splitter);
+            let mut pos = start;
+            while pos < end {
+                // 有人闲着，并且剩下的部分还够分成两段：把后一半切出去
+                if end - pos >= 2 * MIN_CHUNK && splitter.idle.load(Ordering::SeqCst) > 0 {
+                    let mid = pos + (end - pos) / 2;
+                    splitter.give(mid..end);
+                    end = mid;
+                }
+                let piece = (pos + MIN_CHUNK).min(end);
+                local.push((pos, f(&data[pos..piece])));
+                pos = piece;
+            }
+        }
+        lock(&results).append(&mut local);
+    };
+    pool.scope(|scope| {
+        for _ in 0..pool.size() {
+            let worker = &worker;
+            scope.execute(worker);
+        }
+    });
+    let mut results = results.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+    results.sort_by_key(|&(start, _)| start);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+// 按chunk_len切成互不重叠的可变块，并行地对每块执行f
+fn for_chunks_mut<T, F>(data: &mut [T], chunk_len: usize, f: F)
+    where T: Send, F: Fn(&mut [T]) + Sync,
+{
+    if sequential(data.len(), chunk_len) {
+        data.chunks_mut(chunk_len).for_each(f);
+        return;
+    }
+    pool().scope(|scope| {
+        for chunk in data.chunks_mut(chunk_len) {
+            let f = &f;
+            scope.execute(move || f(chunk));
+        }
+    });
+}
+
+/// 切片的并行版本的常用迭代操作，结果与对应的顺序操作相同
+pub trait ParallelSliceExt<T> {
+    /// 对每个元素执行f，按原来的顺序收集结果
+    fn par_map<R, F>(&self, f: F) -> Vec<R>
+        where T: Sync, R: Send, F: Fn(&T) -> R + Sync;
+
+    /// 按原来的顺序收集满足条件的元素
+    fn par_filter<F>(&self, f: F) -> Vec<&T>
+        where T: Sync, F: Fn(&T) -> bool + Sync;
+
+    /// 对每个元素执行f，执行顺序不确定
+    fn par_for_each<F>(&self, f: F)
+        where T: Sync, F: Fn(&T) + Sync;
+
+    /// 用满足结合律的f归约所有元素，切片为空时返回None
+    fn par_reduce<F>(&self, f: F) -> Option<T>
+        where T: Clone + Send + Sync, F: Fn(T, T) -> T + Sync;
+
+    /// 所有元素的和
+    fn par_sum<'a, S>(&'a self) -> S
+        where T: Sync + 'a, S: Sum<&'a T> + Sum<S> + Send;
+
+    /// 稳定排序
+    fn par_sort(&mut self)
+        where T: Ord + Send;
+}
+
+impl<T> ParallelSliceExt<T> for [T] {
+    fn par_map<R, F>(&self, f: F) -> Vec<R>
+        where T: Sync, R: Send, F: Fn(&T) -> R + Sync,
+    {
+        map_adaptive(pool(), self, |chunk| chunk.iter().map(&f).collect::<Vec<R>>())
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn par_filter<F>(&self, f: F) -> Vec<&T>
+        where T: Sync, F: Fn(&T) -> bool + Sync,
+    {
+        map_adaptive(pool(), self, |chunk| chunk.iter().filter(|x| f(x)).collect::<Vec<&T>>())
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn par_for_each<F>(&self, f: F)
+        where T: Sync, F: Fn(&T) + Sync,
+    {
+        map_adaptive(pool(), self, |chunk| chunk.iter().for_each(&f));
+    }
+
+    fn par_reduce<F>(&self, f: F) -> Option<T>
+        where T: Clone + Send + Sync, F: Fn(T, T) -> T + Sync,
+    {
+        map_adaptive(pool(), self, |chunk| chunk.iter().cloned().reduce(&f))
+            .into_iter()
+            .flatten()
+            .reduce(&f)
+    }
+
+    fn par_sum<'a, S>(&'a self) -> S
+        where T: Sync + 'a, S: Sum<&'a T> + Sum<S> + Send,
+    {
+        map_adaptive(pool(), self, |chunk| chunk.iter().sum::<S>()).into_iter().sum()
+    }
+
+    fn par_sort(&mut self)
+        where T: Ord + Send,
+    {
+        let mut run = sort_run_len(self.len());
+        for_chunks_mut(self, run, |chunk| chunk.sort());
+        // 每轮把相邻的两段有序数据合并成一段
+        while run < self.len() {
+            run *= 2;
+            for_chunks_mut(self, run, |chunk| chunk.sort());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    // 线性同余生成器，产生可重现的随机输入
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            self.0 >> 33
+        }
+
+        fn vec(&mut self, len: usize, bound: u64) -> Vec<u64> {
+            (0..len).map(|_| self.next() % bound).collect()
+        }
+    }
+
+    // 各种长度的随机输入：空、短于一块、恰好若干块、很长
+    fn inputs() -> Vec<Vec<u64>> {
+        let mut rng = Lcg(2024);
+        [0, 1, 5, MIN_CHUNK - 1, MIN_CHUNK, MIN_CHUNK + 1, 1000, 4096, 10_007]
+            .iter()
+            .flat_map(|&len| vec![rng.vec(len, 100), rng.vec(len, u64::MAX >> 40)])
+            .collect()
+    }
+
+    #[test]
+    fn test_map_filter_match_sequential() {
+        for data in inputs() {
+            assert_eq!(data.par_map(|x| x * 3 + 1), data.iter().map(|x| x * 3 + 1).collect::<Vec<_>>());
+            assert_eq!(data.par_filter(|x| x % 3 == 0), data.iter().filter(|x| *x % 3 == 0).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_for_each_visits_every_element_once() {
+        for data in inputs() {
+            let sum = AtomicU64::new(0);
+            data.par_for_each(|x| {
+                sum.fetch_add(*x, Ordering::Relaxed);
+            });
+            assert_eq!(sum.into_inner(), data.iter().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn test_reduce_and_sum_match_sequential() {
+        for data in inputs() {
+            assert_eq!(data.par_sum::<u64>(), data.iter().sum::<u64>());
+            assert_eq!(data.par_reduce(|a, b| a.max(b)), data.iter().copied().max());
+            // 字符串拼接满足结合律但不满足交换律，验证了顺序
+            let strings: Vec<String> = data.iter().map(|x| (x % 10).to_string()).collect();
+            assert_eq!(strings.par_reduce(|a, b| a + &b), strings.iter().cloned().reduce(|a, b| a + &b));
+        }
+    }
+
+    #[test]
+    fn test_sort_matches_sequential() {
+        for mut data in inputs() {
+            let mut expected = data.clone();
+            expected.sort();
+            data.par_sort();
+            assert_eq!(data, expected);
+        }
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct Keyed(u64, usize);
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        // 只按键比较，第二个字段记录原始位置
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+        let mut data: Vec<Keyed> = Lcg(7).vec(5000, 10).into_iter().enumerate().map(|(i, k)| Keyed(k, i)).collect();
+        let mut expected = data.clone();
+        expected.sort();
+        data.par_sort();
+        assert!(data.iter().zip(&expected).all(|(a, b)| a.0 == b.0 && a.1 == b.1));
+    }
+
+    #[test]
+    fn test_skewed_range_is_split_for_idle_workers() {
+        // 整个切片一开始只是一个区间，前四分之一的元素更慢；
+        // 空闲的worker应该不断分到切出来的区间，而不是由拿到整个区间的worker独自做完
+        let pool = ThreadPool::new(4);
+        let data: Vec<usize> = (0..4096).collect();
+        let pieces = Mutex::new(vec![]);
+        let sums = map_adaptive(&pool, &data, |chunk| {
+            let millis = if chunk[0] < 1024 { 3 } else { 1 };
+            thread::sleep(std::time::Duration::from_millis(millis));
+            pieces.lock().unwrap().push(thread::current().id());
+            chunk.iter().sum::<usize>()
+        });
+        assert_eq!(sums.iter().sum::<usize>(), data.iter().sum::<usize>());
+
+        let pieces = pieces.into_inner().unwrap();
+        // 切分点不一定对齐MIN_CHUNK，段数可能略多
+        assert!(pieces.len() >= data.len() / MIN_CHUNK);
+        let threads: HashSet<_> = pieces.iter().collect();
+        let busiest = threads.iter().map(|&&id| pieces.iter().filter(|&&other| other == id).count()).max().unwrap();
+        assert!(threads.len() >= 3, "only {} workers got work", threads.len());
+        assert!(busiest * 2 < pieces.len(), "one worker did {} of {} pieces", busiest, pieces.len());
+    }
+
+    #[test]
+    fn test_nested_calls_do_not_deadlock() {
+        let rows: Vec<Vec<u64>> = (0..200).map(|i| (0..200).map(|j| i * j).collect()).collect();
+        let threads = Mutex::new(HashSet::new());
+        let sums = rows.par_map(|row| {
+            threads.lock().unwrap().insert(thread::current().id());
+            row.par_sum::<u64>()
+        });
+        assert_eq!(sums, rows.iter().map(|row| row.iter().sum::<u64>()).collect::<Vec<_>>());
+        assert!(threads.into_inner().unwrap().len() <= pool().size() + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot map 777")]
+    fn test_panic_is_propagated() {
+        let data: Vec<u32> = (0..10_000).collect();
+        data.par_map(|&x| if x == 777 { panic!("cannot map {}", x) } else { x });
+    }
+}
@@ -0,0 +1,446 @@
+/*
+    多级流水线Pipeline
+
+        source --> stage(f1, 2个线程) --> stage(f2, 4个线程) --> sink
+
+    - 相邻两级之间用有界的std::sync::mpsc::sync_channel连接。下游处理不过来时，
+      channel被填满，上游的send就会阻塞，这就是背压（backpressure）：
+      不管source有多大，同时在流水线中的元素个数都有上限；
+    - 每个stage可以有多个线程，它们共享上游channel的Receiver；
+    - source中的每个元素都带着序号，sink可以选择按序号恢复原始顺序（Order::Ordered），
+      也可以按到达顺序处理（Order::Unordered）；
+    - Ordered模式下，sink要等某个慢的元素，先到的元素只能暂存起来。sink不能因此停止recv
+      （它等的元素可能正卡在一个满了的channel后面），所以由source限制序号的窗口：
+      序号不小于“已交给sink的个数 + 窗口大小”的元素要等前面的元素交出去之后才产生。
+      窗口大小等于流水线本身能容纳的元素个数，暂存的元素不会超过它；
+    - source耗尽后，发送端依次被drop，各级的recv返回Err，线程逐级退出；
+      sink提前结束时，上游的send失败，线程同样会退出。
+
+    各级线程在构建时就启动了，所以闭包和数据都要求'static。
+*/
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use crate::file_io::read_lines::read_lines;
+use crate::fixture::TempDir;
+
+/// sink看到的元素顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// 与source中的顺序一致
+    Ordered,
+    /// 按处理完成的先后
+    Unordered,
+}
+
+// source可以产生的序号窗口
+struct Window {
+    state: Mutex<WindowState>,
+    changed: Condvar,
+}
+
+struct WindowState {
+    // 已经交给sink的元素个数
+    delivered: usize,
+    // 流水线能容纳的元素个数
+    size: usize,
+    // sink已经结束或者某一级panic，source不必再产生数据
+    closed: bool,
+}
+
+impl Window {
+    fn new(size: usize) -> Window {
+        Window { state: Mutex::new(WindowState { delivered: 0, size, closed: false }), changed: Condvar::new() }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WindowState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 等到序号seq进入窗口；窗口已关闭时返回false
+    fn acquire(&self, seq: usize) -> bool {
+        let mut state = self.lock();
+        while !state.closed && seq >= state.delivered + state.size {
+            state = self.changed.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        !state.closed
+    }
+
+    fn update(&self, f: impl FnOnce(&mut WindowState)) {
+        f(&mut self.lock());
+        self.changed.notify_all();
+    }
+}
+
+// drop时关闭窗口，让等在窗口上的source退出
+struct CloseOnDrop(Arc<Window>);
+
+impl Drop for CloseOnDrop {
+    fn drop(&mut self) {
+        self.0.update(|state| state.closed = true);
+    }
+}
+
+/// 已经启动、等待继续连接下一级或sink的流水线
+pub struct Pipeline<T> {
+    // 元素与它在source中的序号
+    receiver: Receiver<(usize, T)>,
+    // 各级channel的容量
+    capacity: usize,
+    threads: Vec<JoinHandle<()>>,
+    window: CloseOnDrop,
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    /// 在新线程中迭代source，送入容量为capacity的channel
+    ///
+    /// capacity为0时，channel退化为“会合”（rendezvous）通道，每次send都要等到对方recv
+    pub fn source<I>(source: I, capacity: usize) -> Pipeline<T>
+        where I: IntoIterator<Item=T>,
+              I::IntoIter: Send + 'static,
+    {
+        let mut source = source.into_iter();
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        // channel中的元素，加上source手上的一个
+        let window = Arc::new(Window::new(capacity + 1));
+        let handle = {
+            let window = Arc::clone(&window);
+            thread::Builder::new()
+                .name("pipeline-source".to_string())
+                .spawn(move || {
+                    // 先等序号进入窗口，再向迭代器要下一个元素
+                    for seq in 0.. {
+                        if !window.acquire(seq) {
+                            break;
+                        }
+                        let item = match source.next() {
+                            Some(item) => item,
+                            None => break,
+                        };
+                        // 下游已经全部退出，不必再继续产生数据
+                        if sender.send((seq, item)).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .expect("failed to spawn pipeline source")
+        };
+        Pipeline { receiver, capacity, threads: vec![handle], window: CloseOnDrop(window) }
+    }
+
+    /// 增加一级：用parallelism个线程对每个元素执行f
+    pub fn stage<U, F>(self, f: F, parallelism: usize) -> Pipeline<U>
+        where U: Send + 'static,
+              F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        assert!(parallelism > 0, "a pipeline stage needs at least one thread");
+        let Pipeline { receiver, capacity, mut threads, window } = self;
+        let stage_no = threads.len();
+        // 新的channel，加上每个线程手上的一个
+        window.0.update(|state| state.size += capacity + parallelism);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let f = Arc::new(f);
+        let (sender, next_receiver) = mpsc::sync_channel(capacity);
+
+        for worker in 0..parallelism {
+            let receiver = Arc::clone(&receiver);
+            let sender = sender.clone();
+            let f = Arc::clone(&f);
+            let window = Arc::clone(&window.0);
+            let handle = thread::Builder::new()
+                .name(format!("pipeline-stage-{}-{}", stage_no, worker))
+                .spawn(move || loop {
+                    // 锁只在recv期间持有
+                    let received = receiver.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+                    let (seq, item) = match received {
+                        Ok(item) => item,
+                        // 上游结束
+                        Err(_) => break,
+                    };
+                    let result = match panic::catch_unwind(AssertUnwindSafe(|| f(item))) {
+                        Ok(result) => result,
+                        // 这个元素永远到不了sink，关闭窗口让source停下，否则Ordered的sink会一直等它
+                        Err(payload) => {
+                            window.update(|state| state.closed = true);
+                            panic::resume_unwind(payload);
+                        }
+                    };
+                    if sender.send((seq, result)).is_err() {
+                        // 下游结束
+                        break;
+                    }
+                })
+                .expect("failed to spawn pipeline stage");
+            threads.push(handle);
+        }
+        // 只保留worker手中的发送端，所有worker退出后下游才会看到结束
+        drop(sender);
+        Pipeline { receiver: next_receiver, capacity, threads, window }
+    }
+
+    /// 把流水线的输出交给sink，等所有线程退出后返回sink的结果
+    ///
+    /// 某一级中的panic会在所有线程退出后传播给调用者
+    pub fn sink<R, S>(self, order: Order, sink: S) -> R
+        where S: FnOnce(&mut Output<T>) -> R,
+    {
+        let Pipeline { receiver, threads, window, .. } = self;
+        let mut output = Output { receiver, order, next_seq: 0, pending: BTreeMap::new(), window };
+        let result = sink(&mut output);
+        // 先drop接收端，让还在运行的上游线程（sink提前结束时）尽快退出
+        drop(output);
+
+        let mut panicked = None;
+        for handle in threads {
+            if let Err(payload) = handle.join() {
+                panicked = panicked.or(Some(payload));
+            }
+        }
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+        result
+    }
+
+    /// 把全部输出收集到Vec中
+    pub fn collect(self, order: Order) -> Vec<T> {
+        self.sink(order, |output| output.collect())
+    }
+}
+
+/// sink中使用的输出迭代器
+pub struct Output<T> {
+    receiver: Receiver<(usize, T)>,
+    order: Order,
+    // Ordered模式下，下一个应该交出的序号
+    next_seq: usize,
+    // Ordered模式下，提前到达、还不能交出的元素
+    pending: BTreeMap<usize, T>,
+    // 交出元素后推进窗口；drop时关闭
+    window: CloseOnDrop,
+}
+
+impl<T> Output<T> {
+    /// Ordered模式下暂存的、等待前面的元素到达的元素个数
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl<T> Iterator for Output<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.order == Order::Unordered {
+            let (_, item) = self.receiver.recv().ok()?;
+            self.window.0.update(|state| state.delivered += 1);
+            return Some(item);
+        }
+        loop {
+            if let Some(item) = self.pending.remove(&self.next_seq) {
+                self.next_seq += 1;
+                let delivered = self.next_seq;
+                self.window.0.update(|state| state.delivered = delivered);
+                return Some(item);
+            }
+            match self.receiver.recv() {
+                Ok((seq, item)) => {
+                    self.pending.insert(seq, item);
+                }
+                // 上游已结束。正常情况下pending已经为空；某一级panic时会缺少元素，
+                // 此时按序号交出剩余的元素，panic会在sink返回后传播
+                Err(_) => {
+                    let (&seq, _) = self.pending.iter().next()?;
+                    self.next_seq = seq;
+                }
+            }
+        }
+    }
+}
+
+// 演示用的成绩单，每行“姓名,分数”，其中混有格式错误的行
+const SCORES: &str = "alice,90
+bob,72
+carol,85
+bob,x
+alice,78
+
+dave,101
+carol,93
+bob,64
+alice,88";
+
+// 解析一行成绩，格式错误时返回Err
+fn parse_score(line: &str) -> Result<(String, u32), String> {
+    let mut fields = line.split(',');
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some(name), Some(score), None) if !name.trim().is_empty() => {
+            let score: u32 = score.trim().parse().map_err(|_| format!("bad score in {:?}", line))?;
+            if score > 100 {
+                return Err(format!("score out of range in {:?}", line));
+            }
+            Ok((name.trim().to_string(), score))
+        }
+        _ => Err(format!("malformed line {:?}", line)),
+    }
+}
+
+// 分数对应的等级
+fn grade(score: u32) -> char {
+    match score {
+        90..=100 => 'A',
+        80..=89 => 'B',
+        70..=79 => 'C',
+        _ => 'D',
+    }
+}
+
+/// 演示：read_lines读取成绩文件 -> 并行解析 -> 并行评级 -> 汇总每个人的成绩
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let dir = TempDir::new("pipeline")?;
+    let path = dir.write("scores.csv", SCORES)?;
+    let lines = read_lines(&path)?;
+
+    // 每个人的(分数列表, 等级列表)，以及无法解析的行
+    type Summary = (BTreeMap<String, (Vec<u32>, String)>, Vec<String>);
+    let (students, errors): Summary = Pipeline::source(lines, 4)
+        // 读取错误也作为解析错误向下传递
+        .stage(|line: io::Result<String>| line.map_err(|err| err.to_string()).and_then(|line| parse_score(&line)), 2)
+        .stage(|parsed| parsed.map(|(name, score)| (name, score, grade(score))), 2)
+        // 按文件中的顺序汇总，保证等级的顺序与成绩的顺序一致
+        .sink(Order::Ordered, |output| {
+            let mut students: BTreeMap<String, (Vec<u32>, String)> = BTreeMap::new();
+            let mut errors = vec![];
+            for record in output {
+                match record {
+                    Ok((name, score, grade)) => {
+                        let entry = students.entry(name).or_default();
+                        entry.0.push(score);
+                        entry.1.push(grade);
+                    }
+                    Err(err) => errors.push(err),
+                }
+            }
+            (students, errors)
+        });
+
+    for (name, (scores, grades)) in &students {
+        let average = scores.iter().sum::<u32>() as f64 / scores.len() as f64;
+        writeln!(out, "{:<6} scores={:?} grades={} average={:.1}", name, scores, grades, average)?;
+    }
+    for err in errors {
+        writeln!(out, "skipped: {}", err)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_ordered_output() {
+        let output = Pipeline::source(0..200u64, 4)
+            // 让不同元素的处理时间不同，打乱完成顺序
+            .stage(|x| {
+                thread::sleep(Duration::from_micros((x % 5) * 100));
+                x * 2
+            }, 4)
+            .stage(|x| x + 1, 3)
+            .collect(Order::Ordered);
+        assert_eq!(output, (0..200).map(|x| x * 2 + 1).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unordered_output() {
+        let output: HashSet<u32> = Pipeline::source(0..100u32, 2)
+            .stage(|x| x * x, 4)
+            .sink(Order::Unordered, |output| output.collect());
+        assert_eq!(output, (0..100).map(|x| x * x).collect());
+    }
+
+    #[test]
+    fn test_backpressure_bounds_items_in_flight() {
+        // 已经由source产生、但sink还没有处理的元素个数
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let source = {
+            let in_flight = Arc::clone(&in_flight);
+            (0..1000).inspect(move |_| {
+                in_flight.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let count = Pipeline::source(source, 2)
+            .stage(|x| x, 2)
+            .sink(Order::Unordered, |output| {
+                let mut count = 0;
+                for _ in output {
+                    let current = in_flight.fetch_sub(1, Ordering::SeqCst);
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    // sink处理得很慢，上游必须等待
+                    thread::sleep(Duration::from_micros(20));
+                    count += 1;
+                }
+                count
+            });
+        assert_eq!(count, 1000);
+        // 两个channel各2个 + 每个stage线程手上1个 + source手上1个，再留一点余量
+        let max = max_in_flight.load(Ordering::SeqCst);
+        assert!(max <= 10, "{} items in flight", max);
+    }
+
+    #[test]
+    fn test_slow_item_bounds_reorder_buffer() {
+        // 第0个元素很慢，后面的元素都会先到；sink必须暂存它们，但不能无限地暂存
+        let high_water = Arc::new(AtomicUsize::new(0));
+        let output = Pipeline::source(0..500u32, 2)
+            .stage(|x| {
+                if x == 0 {
+                    thread::sleep(Duration::from_millis(100));
+                }
+                x
+            }, 4)
+            .stage(|x| x, 2)
+            .sink(Order::Ordered, |output| {
+                let mut items = vec![];
+                while let Some(item) = output.next() {
+                    high_water.fetch_max(output.pending(), Ordering::SeqCst);
+                    items.push(item);
+                }
+                items
+            });
+        assert_eq!(output, (0..500).collect::<Vec<_>>());
+        // 窗口：三个channel各2个 + 6个stage线程 + source的1个
+        let high_water = high_water.load(Ordering::SeqCst);
+        assert!(high_water > 0 && high_water < 13, "{} items pending", high_water);
+    }
+
+    #[test]
+    fn test_sink_can_stop_early() {
+        // source是无限的，sink只取前10个；所有线程仍然能够退出
+        let first: Vec<u64> = Pipeline::source(0u64.., 1)
+            .stage(|x| x * 10, 2)
+            .sink(Order::Ordered, |output| output.take(10).collect());
+        assert_eq!(first, (0..10).map(|x| x * 10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_demo() {
+        crate::snapshot::assert_demo_snapshot("thread::pipeline", demo);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot handle 13")]
+    fn test_stage_panic_is_propagated() {
+        Pipeline::source(0..50, 2)
+            .stage(|x: i32| if x == 13 { panic!("cannot handle {}", x) } else { x }, 2)
+            .collect(Order::Ordered);
+    }
+}
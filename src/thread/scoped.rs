@@ -0,0 +1,147 @@
+/*
+    作用域线程：借用调用者的数据
+
+    thread::spawn要求闭包是'static的：新线程可能比创建它的函数活得更久，
+    所以最初的map-reduce例子只能处理`&'static str`。
+
+    std::thread::scope创建一个作用域，其中spawn的线程保证在scope返回之前全部被join，
+    因此这些线程可以放心地借用作用域外面的局部变量：
+
+        let mut data = vec![1, 2, 3, 4];
+        let (left, right) = data.split_at_mut(2);
+        std::thread::scope(|s| {
+            s.spawn(|| left.iter_mut().for_each(|x| *x *= 10));
+            s.spawn(|| right.iter_mut().for_each(|x| *x += 1));
+        });
+        // 到这里两个线程都已结束，data又可以正常使用
+
+    借用规则在线程之间同样成立：多个线程可以同时持有只读引用（&[T]，要求T: Sync），
+    或者各自持有互不重叠的可变引用（chunks_mut/split_at_mut得到的&mut [T]，要求T: Send）。
+
+    par_chunks_map与par_chunks_mut把切分和派发包装起来，底层是map_reduce引擎，线程数不超过workers。
+*/
+use std::io::{self, Write};
+
+use super::map_reduce::map_reduce;
+
+/// 把data按chunk_size切块，用workers个线程对每块执行f，按块的顺序返回结果
+///
+/// data可以是调用者拥有的Vec或String中借出的切片。chunk_size或workers为0时panic
+pub fn par_chunks_map<T, R, F>(data: &[T], chunk_size: usize, workers: usize, f: F) -> Vec<R>
+    where T: Sync, R: Send, F: Fn(&[T]) -> R + Sync,
+{
+    assert!(chunk_size > 0, "chunk_size must be at least 1");
+    map_reduce(data, |data| data.chunks(chunk_size), |chunk| vec![f(chunk)], concat, workers)
+        .unwrap_or_default()
+}
+
+/// 把data按chunk_size切成互不重叠的可变块，用workers个线程对每块执行f(块的序号, 块)
+///
+/// chunk_size或workers为0时panic
+pub fn par_chunks_mut<T, F>(data: &mut [T], chunk_size: usize, workers: usize, f: F)
+    where T: Send, F: Fn(usize, &mut [T]) + Sync,
+{
+    assert!(chunk_size > 0, "chunk_size must be at least 1");
+    map_reduce(data, |data| data.chunks_mut(chunk_size).enumerate(), |(index, chunk)| f(index, chunk), |_, _| (), workers);
+}
+
+// 按顺序拼接两段结果
+fn concat<R>(mut left: Vec<R>, right: Vec<R>) -> Vec<R> {
+    left.extend(right);
+    left
+}
+
+/// 演示：处理调用者拥有的String与Vec，而不是'static数据
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // 运行时才生成的数据，不可能是'static的
+    let text: String = (1..=6).map(|i| format!("line {} has {} words\n", i, "word ".repeat(i))).collect();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // 只读借用：每2行一块，统计单词数
+    let counts = par_chunks_map(&lines, 2, 3, |chunk| chunk.iter().map(|line| line.split_whitespace().count()).sum::<usize>());
+    writeln!(out, "words per 2 lines: {:?}", counts)?;
+
+    // 可变借用：各线程改写互不重叠的块
+    let mut numbers: Vec<u32> = (1..=10).collect();
+    par_chunks_mut(&mut numbers, 3, 3, |index, chunk| {
+        for x in chunk {
+            *x *= 10u32.pow(index as u32);
+        }
+    });
+    writeln!(out, "scaled by chunk: {:?}", numbers)?;
+
+    // 直接使用std::thread::scope：两个线程分别修改前后两半
+    let (left, right) = numbers.split_at_mut(5);
+    std::thread::scope(|s| {
+        s.spawn(|| left.reverse());
+        s.spawn(|| right.iter_mut().for_each(|x| *x = 0));
+    });
+    writeln!(out, "after scope: {:?}", numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_chunks_map_matches_sequential() {
+        let data: Vec<u64> = (0..1000).map(|i| i * 7 % 13).collect();
+        for chunk_size in [1, 3, 64, 1000, 5000].iter() {
+            for workers in 1..=4 {
+                let parallel = par_chunks_map(&data, *chunk_size, workers, |chunk| chunk.iter().sum::<u64>());
+                let sequential: Vec<u64> = data.chunks(*chunk_size).map(|chunk| chunk.iter().sum()).collect();
+                assert_eq!(parallel, sequential);
+            }
+        }
+        assert!(par_chunks_map(&[] as &[u8], 4, 2, |chunk| chunk.len()).is_empty());
+    }
+
+    #[test]
+    fn test_borrowed_string() {
+        // 调用者拥有的String，worker借用其中的字节
+        let text = String::from("hello scoped threads, hello borrowed data");
+        let uppercase = par_chunks_map(text.as_bytes(), 5, 3, |chunk| chunk.to_ascii_uppercase());
+        assert_eq!(uppercase.concat(), text.to_uppercase().into_bytes());
+        // 线程都已结束，text仍然可以使用
+        assert_eq!(text.len(), 41);
+    }
+
+    #[test]
+    fn test_disjoint_chunks_mut() {
+        let mut data = vec![0usize; 1003];
+        let threads = Mutex::new(HashSet::new());
+        par_chunks_mut(&mut data, 10, 4, |index, chunk| {
+            threads.lock().unwrap().insert(std::thread::current().id());
+            for (offset, x) in chunk.iter_mut().enumerate() {
+                *x = index * 10 + offset;
+            }
+        });
+        // 每个元素恰好被写了一次，写入的就是它自己的下标
+        assert_eq!(data, (0..1003).collect::<Vec<_>>());
+        assert!(threads.into_inner().unwrap().len() <= 4);
+    }
+
+    #[test]
+    fn test_split_at_mut_in_scope() {
+        let mut data: Vec<i32> = (0..8).collect();
+        let (left, right) = data.split_at_mut(4);
+        std::thread::scope(|s| {
+            s.spawn(|| left.iter_mut().for_each(|x| *x = -*x));
+            s.spawn(|| right.iter_mut().for_each(|x| *x *= 100));
+        });
+        assert_eq!(data, vec![0, -1, -2, -3, 400, 500, 600, 700]);
+    }
+
+    #[test]
+    fn test_demo() {
+        crate::snapshot::assert_demo_snapshot("thread::scoped", demo);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk_size must be at least 1")]
+    fn test_zero_chunk_size() {
+        par_chunks_mut(&mut [1, 2, 3], 0, 2, |_, _| {});
+    }
+}
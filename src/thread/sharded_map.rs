@@ -0,0 +1,233 @@
+/*
+    分片的并发散列表ShardedMap
+
+    多个线程一起往一个表里汇总数据，最直接的写法是Mutex<HashMap<K, V>>：
+    每次更新都要拿同一把锁，线程越多，花在等锁上的时间就越多。
+
+    ShardedMap把数据分到N个分片（shard）里，每个分片是一个RwLock<HashMap<K, V>>：
+
+        shard = hash(key) % N
+
+    - 不同分片上的读写互不干扰，同一分片上的读可以并发；
+    - upsert在分片的写锁下把Entry交给闭包，“没有就插入、有就修改”是一个原子操作；
+    - len等跨分片的统计不是原子的，并发修改时只是一个近似值；
+    - snapshot按分片的顺序同时拿到所有分片的读锁，在它存活期间整个表不会被修改，
+      因此看到的是某一时刻一致的内容。持有snapshot的线程不能再修改这个表，否则会死锁。
+*/
+use std::borrow::Borrow;
+use std::collections::hash_map::{Entry, HashMap, RandomState};
+use std::hash::{BuildHasher, Hash};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+
+// 默认每个CPU对应的分片数
+const SHARDS_PER_THREAD: usize = 4;
+
+/// 按键的散列值分片、每个分片一把RwLock的并发散列表
+pub struct ShardedMap<K, V> {
+    shards: Vec<RwLock<HashMap<K, V>>>,
+    // 选择分片用的散列函数，与分片内部HashMap的无关
+    hasher: RandomState,
+}
+
+impl<K: Hash + Eq, V> ShardedMap<K, V> {
+    /// 分片数为available_parallelism的若干倍
+    pub fn new() -> ShardedMap<K, V> {
+        let threads = thread::available_parallelism().map_or(4, |n| n.get());
+        ShardedMap::with_shards(threads * SHARDS_PER_THREAD)
+    }
+
+    /// 指定分片数，为0时panic
+    pub fn with_shards(shards: usize) -> ShardedMap<K, V> {
+        assert!(shards > 0, "ShardedMap needs at least one shard");
+        ShardedMap { shards: (0..shards).map(|_| RwLock::new(HashMap::new())).collect(), hasher: RandomState::new() }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard<Q: Hash + ?Sized>(&self, key: &Q) -> &RwLock<HashMap<K, V>> {
+        let index = self.hasher.hash_one(key) % self.shards.len() as u64;
+        &self.shards[index as usize]
+    }
+
+    /// 在key所在分片的写锁下，把key对应的Entry交给f，返回f的结果，
+    /// 例如计数：`map.upsert(word, |entry| *entry.or_insert(0) += 1)`
+    pub fn upsert<R, F>(&self, key: K, f: F) -> R
+        where F: FnOnce(Entry<'_, K, V>) -> R,
+    {
+        f(write(self.shard(&key)).entry(key))
+    }
+
+    /// 插入键值对，返回原来的值
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        write(self.shard(&key)).insert(key, value)
+    }
+
+    /// 返回key对应的值的克隆：分片的锁在返回前就已释放，不能把引用交出去
+    pub fn get_cloned<Q>(&self, key: &Q) -> Option<V>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized, V: Clone,
+    {
+        read(self.shard(key)).get(key).cloned()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized,
+    {
+        read(self.shard(key)).contains_key(key)
+    }
+
+    /// 删除key，返回它原来的值
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized,
+    {
+        write(self.shard(key)).remove(key)
+    }
+
+    /// 元素个数。逐个分片统计，并发修改时只是近似值
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| read(shard).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| read(shard).is_empty())
+    }
+
+    /// 同时持有所有分片的读锁，得到一个一致的快照
+    pub fn snapshot(&self) -> Snapshot<'_, K, V> {
+        // 所有需要多把锁的地方都按分片的顺序加锁，不会互相死锁
+        Snapshot { shards: self.shards.iter().map(read).collect() }
+    }
+
+    /// 取出所有数据，合并成一个HashMap
+    pub fn into_inner(self) -> HashMap<K, V> {
+        self.shards
+            .into_iter()
+            .flat_map(|shard| shard.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()))
+            .collect()
+    }
+}
+
+impl<K: Hash + Eq, V> Default for ShardedMap<K, V> {
+    fn default() -> Self {
+        ShardedMap::new()
+    }
+}
+
+// 分片的锁只在HashMap的方法调用期间持有，即使中毒，HashMap本身也是完好的
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// ShardedMap在某一时刻的一致视图，存活期间整个表不会被修改
+pub struct Snapshot<'a, K, V> {
+    shards: Vec<RwLockReadGuard<'a, HashMap<K, V>>>,
+}
+
+impl<'a, K, V> Snapshot<'a, K, V> {
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.is_empty())
+    }
+
+    /// 遍历快照中的所有键值对，顺序不确定
+    pub fn iter(&self) -> impl Iterator<Item=(&K, &V)> {
+        self.shards.iter().flat_map(|shard| shard.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_basic_operations() {
+        let map = ShardedMap::with_shards(3);
+        assert!(map.is_empty());
+        assert_eq!(map.shard_count(), 3);
+        assert_eq!(map.insert("a".to_string(), 1), None);
+        assert_eq!(map.insert("a".to_string(), 2), Some(1));
+        map.upsert("b".to_string(), |entry| *entry.or_insert(10) += 5);
+        map.upsert("b".to_string(), |entry| *entry.or_insert(10) += 5);
+        // 通过&str查找String键
+        assert_eq!(map.get_cloned("a"), Some(2));
+        assert_eq!(map.get_cloned("b"), Some(20));
+        assert!(map.contains_key("b"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.remove("a"), Some(2));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(map.get_cloned("a"), None);
+        assert_eq!(map.into_inner(), vec![("b".to_string(), 20)].into_iter().collect());
+    }
+
+    #[test]
+    fn test_parallel_word_count_matches_sequential() {
+        let text: Vec<String> = (0..20_000).map(|i| format!("w{}", i * 7919 % 997)).collect();
+        let map = ShardedMap::new();
+        thread::scope(|scope| {
+            for part in text.chunks(2000) {
+                let map = &map;
+                scope.spawn(move || {
+                    for word in part {
+                        map.upsert(word.clone(), |entry| *entry.or_insert(0usize) += 1);
+                    }
+                });
+            }
+        });
+        let mut expected: HashMap<String, usize> = HashMap::new();
+        for word in &text {
+            *expected.entry(word.clone()).or_insert(0) += 1;
+        }
+        assert_eq!(map.snapshot().len(), expected.len());
+        assert_eq!(map.into_inner(), expected);
+    }
+
+    #[test]
+    fn test_snapshot_is_consistent() {
+        // 写线程按固定顺序循环地给各个键加一，任一时刻前面的键不少于后面的键，且最多多1。
+        // 键分布在不同的分片里，只有同时拿住所有分片的锁，才能保证每次都看到这个关系
+        const KEYS: usize = 16;
+        let map = ShardedMap::with_shards(8);
+        for key in 0..KEYS {
+            map.insert(key, 0u64);
+        }
+        let stop = AtomicBool::new(false);
+        let inconsistent = thread::scope(|scope| {
+            scope.spawn(|| {
+                while !stop.load(Ordering::Relaxed) {
+                    for key in 0..KEYS {
+                        map.upsert(key, |entry| *entry.or_insert(0) += 1);
+                    }
+                }
+            });
+            let inconsistent = (0..200).find_map(|_| {
+                let snapshot = map.snapshot();
+                let mut counts = vec![0; KEYS];
+                for (&key, &count) in snapshot.iter() {
+                    counts[key] = count;
+                }
+                let ok = counts.windows(2).all(|pair| pair[0] >= pair[1]) && counts[0] - counts[KEYS - 1] <= 1;
+                if ok { None } else { Some(counts) }
+            });
+            // 先让写线程停下再断言，否则断言失败时scope会一直等待它
+            stop.store(true, Ordering::Relaxed);
+            inconsistent
+        });
+        assert_eq!(inconsistent, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one shard")]
+    fn test_zero_shards() {
+        ShardedMap::<u8, u8>::with_shards(0);
+    }
+}
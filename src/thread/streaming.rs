@@ -0,0 +1,396 @@
+/*
+    流式的map-reduce：逐批读取大文件
+
+    testcase_map_reduce处理的是内存中的字符串，map_reduce的chunker也要求输入已经在手里。
+    文件有好几个GB时，不可能先把它整个读进一个String。
+
+    file_io::read_lines用BufReader逐行读取，这里在它的基础上：
+
+        读取线程 --批次（batch_lines行）--> worker线程执行map_fn --> 调用者线程按批次顺序reduce
+
+        - 读取线程每读一个批次之前要先拿到一个“许可”，调用者每折叠完一个批次就归还一个许可，
+          所以任何时刻内存中（排队中、处理中、等待折叠的）批次最多in_flight个，内存占用与文件大小无关；
+        - 批次按序号折叠，reduce_fn与map_reduce一样不需要满足交换律；
+        - 读取出错（包括不是合法的UTF-8）时停止，返回该错误；map_fn中的panic在所有线程结束后传播给调用者。
+
+    word_frequency和digit_sum是两个现成的任务，Report中带有处理的行数、耗时和每秒处理的行数。
+*/
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::testcase_map_reduce::{count_words, merge_counts};
+use crate::file_io::read_lines::read_lines;
+use crate::fixture::TempDir;
+
+/// 流式处理的参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streaming {
+    pub workers: usize,
+    /// 每个批次的行数
+    pub batch_lines: usize,
+    /// 同时存在于内存中的批次数上限
+    pub in_flight: usize,
+}
+
+impl Streaming {
+    /// workers个worker，每批1024行，最多workers * 2个批次
+    pub fn new(workers: usize) -> Streaming {
+        Streaming { workers, batch_lines: 1024, in_flight: workers * 2 }
+    }
+
+    pub fn batch_lines(mut self, batch_lines: usize) -> Streaming {
+        self.batch_lines = batch_lines;
+        self
+    }
+
+    pub fn in_flight(mut self, in_flight: usize) -> Streaming {
+        self.in_flight = in_flight;
+        self
+    }
+}
+
+/// 交给map_fn的一批连续的行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    /// 第一行的行号，从1开始
+    pub first_line: usize,
+    pub lines: Vec<String>,
+}
+
+/// 一次流式处理的结果与统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report<T> {
+    pub value: T,
+    /// 读取的行数
+    pub lines: usize,
+    pub elapsed: Duration,
+}
+
+impl<T> Report<T> {
+    /// 每秒处理的行数
+    pub fn lines_per_sec(&self) -> f64 {
+        self.lines as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Report<U> {
+        Report { value: f(self.value), lines: self.lines, elapsed: self.elapsed }
+    }
+}
+
+impl<T> Display for Report<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lines in {:.2?} ({:.0} lines/sec)", self.lines, self.elapsed, self.lines_per_sec())
+    }
+}
+
+/// 对lines逐批执行map-reduce。没有任何行时value为None
+///
+/// options中的workers、batch_lines或in_flight为0时panic
+pub fn stream_lines<I, T, M, R>(lines: I, options: Streaming, map_fn: M, mut reduce_fn: R) -> io::Result<Report<Option<T>>>
+    where I: Iterator<Item=io::Result<String>> + Send,
+          T: Send,
+          M: Fn(&Batch) -> T + Sync,
+          R: FnMut(T, T) -> T,
+{
+    let Streaming { workers, batch_lines, in_flight } = options;
+    assert!(workers > 0 && batch_lines > 0 && in_flight > 0, "streaming options must all be at least 1");
+    let start = Instant::now();
+
+    // 批次的数量受许可限制，因此这个channel永远不会满，读取线程不会阻塞在send上
+    let (batch_sender, batch_receiver) = mpsc::sync_channel::<(usize, Batch)>(in_flight);
+    let batch_receiver = Mutex::new(batch_receiver);
+    let (result_sender, result_receiver) = mpsc::channel::<(usize, thread::Result<T>)>();
+    let (permit_sender, permit_receiver) = mpsc::sync_channel::<()>(in_flight);
+    for _ in 0..in_flight {
+        permit_sender.send(()).expect("the permit channel has room for every permit");
+    }
+
+    let (value, read) = thread::scope(|scope| {
+        let reader = scope.spawn(move || -> io::Result<usize> {
+            let mut lines = lines;
+            let mut count = 0;
+            // 调用者提前结束时，许可的发送端被drop，recv返回Err
+            for seq in 0.. {
+                if permit_receiver.recv().is_err() {
+                    break;
+                }
+                let batch = lines.by_ref().take(batch_lines).collect::<io::Result<Vec<String>>>()?;
+                if batch.is_empty() {
+                    break;
+                }
+                let first_line = count + 1;
+                count += batch.len();
+                if batch_sender.send((seq, Batch { first_line, lines: batch })).is_err() {
+                    break;
+                }
+            }
+            Ok(count)
+        });
+
+        for _ in 0..workers {
+            let (batch_receiver, map_fn, result_sender) = (&batch_receiver, &map_fn, result_sender.clone());
+            scope.spawn(move || loop {
+                // 锁只在recv期间持有
+                let received = batch_receiver.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+                let (seq, batch) = match received {
+                    Ok(item) => item,
+                    Err(_) => break,
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| map_fn(&batch)));
+                if result_sender.send((seq, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(result_sender);
+
+        // 在调用者线程中按批次的顺序折叠
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        let mut value = None;
+        for (seq, result) in result_receiver.iter() {
+            let result = match result {
+                Ok(result) => result,
+                Err(payload) => {
+                    // 让读取线程和worker尽快退出，再把panic传播出去
+                    drop(permit_sender);
+                    drop(result_receiver);
+                    reader.join().ok();
+                    panic::resume_unwind(payload);
+                }
+            };
+            pending.insert(seq, result);
+            while let Some(result) = pending.remove(&next) {
+                value = Some(match value.take() {
+                    None => result,
+                    Some(value) => reduce_fn(value, result),
+                });
+                next += 1;
+                // 读取线程可能已经结束，许可没人要了
+                let _ = permit_sender.send(());
+            }
+        }
+        (value, reader.join())
+    });
+
+    let lines = read.unwrap_or_else(|payload| panic::resume_unwind(payload))?;
+    Ok(Report { value, lines, elapsed: start.elapsed() })
+}
+
+/// 对文件path逐批执行map-reduce
+pub fn stream_file<P, T, M, R>(path: P, options: Streaming, map_fn: M, reduce_fn: R) -> io::Result<Report<Option<T>>>
+    where P: AsRef<Path>,
+          T: Send,
+          M: Fn(&Batch) -> T + Sync,
+          R: FnMut(T, T) -> T,
+{
+    stream_lines(read_lines(path)?, options, map_fn, reduce_fn)
+}
+
+/// 统计文件中每个单词出现的次数，规则与testcase_map_reduce::word_count相同
+pub fn word_frequency<P: AsRef<Path>>(path: P, options: Streaming) -> io::Result<Report<HashMap<String, usize>>> {
+    let report = stream_file(path, options, |batch| {
+        let mut counts = HashMap::new();
+        for line in &batch.lines {
+            count_words(line, &mut counts);
+        }
+        counts
+    }, merge_counts)?;
+    Ok(report.map(Option::unwrap_or_default))
+}
+
+/// digit_sum的错误
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    /// 第line行第column个字符不是数字（行、列都从1开始）
+    InvalidDigit { line: usize, column: usize, character: char },
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "read failed: {}", err),
+            StreamError::InvalidDigit { line, column, character } =>
+                write!(f, "invalid digit {:?} at line {}, column {}", character, line, column),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(err) => Some(err),
+            StreamError::InvalidDigit { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        StreamError::Io(err)
+    }
+}
+
+/// 文件中每一位数字的和，空白符被忽略，遇到其他字符时返回文件中第一个这样的字符
+pub fn digit_sum<P: AsRef<Path>>(path: P, options: Streaming) -> Result<Report<u64>, StreamError> {
+    let report = stream_file(path, options, |batch| {
+        let mut sum = 0u64;
+        for (number, line) in (batch.first_line..).zip(&batch.lines) {
+            for (column, character) in (1..).zip(line.chars()) {
+                match character.to_digit(10) {
+                    Some(digit) => sum += u64::from(digit),
+                    None if character.is_whitespace() => {}
+                    None => return Err(StreamError::InvalidDigit { line: number, column, character }),
+                }
+            }
+        }
+        Ok(sum)
+    }, |a, b| Ok(a? + b?))?;
+    let Report { value, lines, elapsed } = report;
+    Ok(Report { value: value.unwrap_or(Ok(0))?, lines, elapsed })
+}
+
+/// 演示：生成一个文件，用流式map-reduce统计单词并计算数字之和
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    use super::testcase_map_reduce::{DATA, TEXT};
+
+    let dir = TempDir::new("streaming")?;
+    // 把演示数据重复多次，得到一个几万行的文件
+    let text = dir.write("text.txt", format!("{}\n", TEXT).repeat(10_000))?;
+    let digits = dir.write("digits.txt", format!("{}\n", DATA).repeat(2_000))?;
+    let options = Streaming::new(4).batch_lines(512);
+
+    let words = word_frequency(&text, options)?;
+    let mut top: Vec<(&String, &usize)> = words.value.iter().collect();
+    top.sort_by(|(w1, c1), (w2, c2)| c2.cmp(c1).then_with(|| w1.cmp(w2)));
+    writeln!(out, "top words: {:?}", &top[..3])?;
+    writeln!(out, "word frequency: {}", words)?;
+
+    match digit_sum(&digits, options) {
+        Ok(report) => {
+            writeln!(out, "digit sum: {}", report.value)?;
+            writeln!(out, "digit sum: {}", report)
+        }
+        Err(err) => writeln!(out, "digit sum failed: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::TempDir;
+    use crate::thread::testcase_map_reduce::{word_count, DATA, TEXT};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn lines(text: &str) -> impl Iterator<Item=io::Result<String>> + Send + '_ {
+        text.lines().map(|line| Ok(line.to_string()))
+    }
+
+    #[test]
+    fn test_batches_are_folded_in_order() {
+        let text: String = (1..=1000).map(|i| format!("{}\n", i)).collect();
+        for &(workers, batch_lines) in &[(1, 1), (3, 7), (4, 1000), (2, 5000)] {
+            let options = Streaming::new(workers).batch_lines(batch_lines);
+            let report = stream_lines(lines(&text), options, |batch| {
+                // 每个批次交回它的第一行行号与行数，检查批次的顺序和行号
+                assert_eq!(batch.lines[0], batch.first_line.to_string());
+                vec![(batch.first_line, batch.lines.len())]
+            }, |mut a, b| {
+                a.extend(b);
+                a
+            }).unwrap();
+            assert_eq!(report.lines, 1000);
+            let batches = report.value.unwrap();
+            assert_eq!(batches.iter().map(|&(_, len)| len).sum::<usize>(), 1000);
+            assert!(batches.windows(2).all(|pair| pair[0].0 + pair[0].1 == pair[1].0));
+        }
+        let empty = stream_lines(lines(""), Streaming::new(2), |batch| batch.lines.len(), |a, b| a + b).unwrap();
+        assert_eq!((empty.value, empty.lines), (None, 0));
+    }
+
+    #[test]
+    fn test_memory_is_bounded_by_in_flight() {
+        // 已经读入、还没有折叠的行数不能超过in_flight个批次
+        let read = AtomicUsize::new(0);
+        let folded = AtomicUsize::new(0);
+        let max_outstanding = AtomicUsize::new(0);
+        let source = (0..20_000).map(|i| {
+            let outstanding = read.fetch_add(1, Ordering::SeqCst) + 1 - folded.load(Ordering::SeqCst);
+            max_outstanding.fetch_max(outstanding, Ordering::SeqCst);
+            Ok(i.to_string())
+        });
+        let options = Streaming::new(2).batch_lines(100).in_flight(3);
+        let report = stream_lines(source, options, |batch| batch.lines.len(), |a, b| {
+            folded.store(a + b, Ordering::SeqCst);
+            a + b
+        }).unwrap();
+        assert_eq!(report.value, Some(20_000));
+        let max = max_outstanding.load(Ordering::SeqCst);
+        // 第一个批次不经过reduce，多留一个批次的余量
+        assert!(max <= 4 * 100, "{} lines outstanding", max);
+    }
+
+    #[test]
+    fn test_word_frequency_matches_in_memory_count() {
+        let dir = TempDir::new("streaming-words").unwrap();
+        let path = dir.write("text.txt", format!("{}\n", TEXT).repeat(50)).unwrap();
+        let report = word_frequency(&path, Streaming::new(3).batch_lines(4)).unwrap();
+        let expected: HashMap<String, usize> = word_count(TEXT, 1).into_iter().map(|(w, c)| (w, c * 50)).collect();
+        assert_eq!(report.value, expected);
+        assert_eq!(report.lines, TEXT.lines().count() * 50);
+        assert!(report.lines_per_sec() > 0.0);
+    }
+
+    #[test]
+    fn test_digit_sum() {
+        let dir = TempDir::new("streaming-digits").unwrap();
+        let path = dir.write("digits.txt", DATA).unwrap();
+        let report = digit_sum(&path, Streaming::new(2).batch_lines(3)).unwrap();
+        assert_eq!(report.value, 1342);
+
+        let path = dir.write("bad.txt", "123\n456\n78x9\n0?\n").unwrap();
+        let err = digit_sum(&path, Streaming::new(2).batch_lines(1)).unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit 'x' at line 3, column 3");
+    }
+
+    #[test]
+    fn test_read_errors_are_returned() {
+        let dir = TempDir::new("streaming-errors").unwrap();
+        let path = dir.write("latin1.txt", "ok\n").unwrap();
+        std::fs::write(&path, b"ok\n\xff\xfe\n").unwrap();
+        let err = word_frequency(&path, Streaming::new(2).batch_lines(1)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(word_frequency(dir.join("missing.txt"), Streaming::new(1)).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_demo_reports_throughput() {
+        let mut out = Vec::new();
+        demo(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("top words: [(\"the\", 40000), (\"dog\", 30000), (\"fox\", 30000)]\n"), "{}", text);
+        assert!(text.contains("digit sum: 2684000\n"), "{}", text);
+        assert_eq!(text.matches("lines/sec").count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "bad batch 3")]
+    fn test_map_panic_is_propagated() {
+        let text: String = (0..100).map(|i| format!("{}\n", i)).collect();
+        stream_lines(lines(&text), Streaming::new(2).batch_lines(10), |batch| {
+            if batch.first_line == 31 {
+                panic!("bad batch {}", batch.first_line / 10);
+            }
+            batch.lines.len()
+        }, |a, b| a + b).unwrap();
+    }
+}
@@ -18,14 +18,22 @@
 
     注意:虽然我们在线程之间传递了引用，但 Rust 理解我们是在传递`只读`的引用，因此不会发生数据竞争等不安全的事情。
     另外，因为我们把数据块 move 到了线程中，Rust 会保证数据存活至线程退出，因此不会产生`悬挂指针`。
+
+    最初的写法为每个数据段各开一个线程，现在分段、派发和汇总都交给了通用的引擎
+    thread::map_reduce::map_reduce，这里的数字求和与单词计数只是它的两个使用者。
+
+    数据中混入非数字字符时，digit_sum返回带有数据段序号、字节偏移和该字符的错误，
+    而不是在worker线程中expect失败、panic。
 */
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn test_testcase_map_reduce() {
-        // 将要处理的数据
-        let data = "86967897737416471853297327050364959
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+
+use super::map_reduce::{map_reduce, try_map_reduce, MapReduceError, Options};
+
+/// 演示用的数据：每个用空白符隔开的块是一个数据段
+pub const DATA: &str = "86967897737416471853297327050364959
 11861322575564723963297542624962850
 70856234701860851907960690014725639
 38397966707106094172783238747669219
@@ -33,84 +41,179 @@ mod tests {
 58495327135744041048897885734297812
 69920216438980873548808413720956532
 16278424637452589860345374828574668";
-        // 我们会通过线程实现 map-reduce 算法，从而计算每一位的和
-        // 每个用空白符隔开的块都会分配给单独的线程来处理
 
-        // 创建一个Vec，用于储存将要创建的子线程
-        let mut thread_handlers = vec![];
-
-        /************************************************************************
+/// 单词计数演示用的文本
+pub const TEXT: &str = "the quick brown fox jumps over the lazy dog
+The dog barks, the fox runs.
+A quick dog; a lazy fox!";
+
+/// 演示中使用的worker数
+pub const WORKERS: usize = 4;
+
+/// 数据段中出现了不是数字的字符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDigit {
+    /// 该字符在整个输入中的字节偏移
+    pub offset: usize,
+    pub character: char,
+}
+
+impl fmt::Display for InvalidDigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid digit {:?} at byte {}", self.character, self.offset)
+    }
+}
+
+/// 用workers个线程计算data中每一位数字的和，遇到第一个非数字字符就停止
+pub fn digit_sum(data: &str, workers: usize) -> Result<u32, MapReduceError<InvalidDigit>> {
+    digit_sum_with(data, Options::new(workers))
+}
+
+/// 按options计算data中每一位数字的和
+///
+/// 出错时，MapReduceError中带有数据段的序号以及非数字字符和它的字节偏移
+pub fn digit_sum_with(data: &str, options: Options) -> Result<u32, MapReduceError<InvalidDigit>> {
+    let sum = try_map_reduce(
+        data,
+        /********************************************************************
          * "Map" 阶段
          *
-         * 把数据分段，并进行初始化处理
-         ************************************************************************/
-
-        // 把数据分段，每段将会单独计算
-        // 每段都是完整数据的一个引用（&str）
-        let chunked_data = data.split_whitespace();
-
-        // 对分段的数据进行迭代。
-        // enumerate方法会把当前的迭代计数与被迭代的元素以元组 (index, element)的形式返回。
-        // 接着立即使用 “解构赋值” 将该元组解构成两个变量 `i` 和 `data_segment`。
-        for (i, data_segment) in chunked_data.enumerate() {
-            println!("data segment {} is \"{}\"", i, data_segment);
-
-            // 每一段数据都用单独的线程处理
-            //
-            // std::thread::spawn方法返回新线程的句柄（handle）
-            // 我们必须拥有句柄，才能获取线程的返回值。
-
-            /*
-                 move || -> u32' 语法表示该闭包：
-                     * 没有参数（'||'）
-                     * 会获取所捕获变量的所有权（'move'）
-                     * 返回无符号 32 位整数（'-> u32'）
-            */
-            let handler = std::thread::spawn(
-                move || -> u32{
-                    // 计算该数据段的每一位的和
-                    let result = data_segment
-                        // 对该段中的字符进行迭代,即将&str转成chars（可以理解为一个char的迭代器）
-                        .chars()
-                        // 把字符转成十进制的数字
-                        .map(|ch| ch.to_digit(10).expect("should be a digit"))
-                        // 对返回的数字类型的迭代器求和
-                        .sum();
-
-                    // println! 会锁住标准输出，这样各线程打印的内容不会交错在一起。
-                    // 即 println！是线程安全的
-                    println!("processed segment {}, result={}", i, result);
-                    result
-                }
-            );
-
-            thread_handlers.push(handler);
-        }
-
-        /*************************************************************************
+         * 把数据分段，每段都是完整数据的一个引用（&str），
+         * 同时算出它在整个输入中的起始位置，用于报告出错的字节偏移
+         ********************************************************************/
+        |data| data.split_whitespace().map(move |segment| (segment.as_ptr() as usize - data.as_ptr() as usize, segment)),
+        // 计算该数据段的每一位的和（在worker线程中执行）
+        |(start, segment)| segment
+            // 对该段中的字符（及其字节位置）进行迭代
+            .char_indices()
+            // 把字符转成十进制的数字；不是数字时返回错误，而不是expect导致线程panic
+            .map(|(i, ch)| ch.to_digit(10).ok_or(InvalidDigit { offset: start + i, character: ch }))
+            // Result的迭代器可以直接求和：遇到第一个Err就停止并返回它
+            .sum::<Result<u32, InvalidDigit>>(),
+        /********************************************************************
          * "Reduce" 阶段
          *
-         * 收集中间结果，得出最终结果
-         ************************************************************************/
-
-        // 把每个线程的计算结果收入一个新的Vec中
-        let mut thread_sums = vec![];
-        for handler in thread_handlers {
-            // 收集每个子线程的返回值
-            let handler_result = handler.join().unwrap();
-            thread_sums.push(handler_result);
+         * 把所有中间结果加起来，得到最终结果
+         ********************************************************************/
+        |a, b| a + b,
+        options,
+    )?;
+    Ok(sum.unwrap_or(0))
+}
+
+/// 用workers个线程统计text中每个单词出现的次数
+///
+/// 按行分块；单词不区分大小写，并去掉两端的标点
+pub fn word_count(text: &str, workers: usize) -> HashMap<String, usize> {
+    map_reduce(
+        text,
+        |text| text.lines(),
+        |line| {
+            let mut counts = HashMap::new();
+            count_words(line, &mut counts);
+            counts
+        },
+        merge_counts,
+        workers,
+    ).unwrap_or_default()
+}
+
+/// 把line中的单词计入counts：不区分大小写，并去掉两端的标点
+pub fn count_words(line: &str, counts: &mut HashMap<String, usize>) {
+    for word in line.split_whitespace() {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        if !word.is_empty() {
+            *counts.entry(word).or_insert(0) += 1;
         }
+    }
+}
 
-        // 把所有中间结果加起来，得到最终结果
+/// 合并两张计数表：把较小的一张并入较大的一张
+pub fn merge_counts(a: HashMap<String, usize>, b: HashMap<String, usize>) -> HashMap<String, usize> {
+    let (mut big, small) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    for (word, count) in small {
+        *big.entry(word).or_insert(0) += count;
+    }
+    big
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // enumerate方法会把当前的迭代计数与被迭代的元素以元组 (index, element)的形式返回。
+    // 接着立即使用 “解构赋值” 将该元组解构成两个变量 `i` 和 `data_segment`。
+    for (i, data_segment) in DATA.split_whitespace().enumerate() {
+        writeln!(out, "data segment {} is \"{}\"", i, data_segment)?;
+    }
+    writeln!(out, "processing {} segments with {} workers", DATA.split_whitespace().count(), WORKERS)?;
 
-        // 可以使用"turbofish"写法:`::<>` 来为sum()提供类型提示
-        let final_sum_1 = thread_sums.iter().sum::<u32>();
+    // 打印结果
+    match digit_sum(DATA, WORKERS) {
+        Ok(sum) => writeln!(out, "Final sum result: {}", sum),
+        Err(err) => writeln!(out, "map-reduce failed: {}", err),
+    }
+}
+
+pub fn demo_word_count(out: &mut dyn Write) -> io::Result<()> {
+    let counts = word_count(TEXT, WORKERS);
+    // HashMap的遍历顺序不固定，按次数从多到少、再按单词排序后输出
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|(w1, c1), (w2, c2)| c2.cmp(c1).then_with(|| w1.cmp(w2)));
+    for (word, count) in counts {
+        writeln!(out, "{:<6} {}", word, count)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::assert_demo_snapshot;
 
-        // 或者不使用"turbofish"写法，直接显式指定final_sum的类型
-        let final_sum_2: u32 = thread_sums.iter().sum();
-        assert_eq!(final_sum_1, final_sum_2);
+    #[test]
+    fn test_testcase_map_reduce() {
+        let mut out = Vec::new();
+        super::demo(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        print!("{}", text);
+        assert!(text.ends_with("Final sum result: 1342\n"));
+    }
 
-        // 打印结果
-        println!("Final sum result: {}", final_sum_1);
+    #[test]
+    fn test_digit_sum_is_independent_of_workers() {
+        let sequential: u32 = DATA.chars().filter_map(|ch| ch.to_digit(10)).sum();
+        for workers in 1..=10 {
+            assert_eq!(digit_sum(DATA, workers), Ok(sequential));
+        }
+        assert_eq!(digit_sum("", 2), Ok(0));
+    }
+
+    #[test]
+    fn test_digit_sum_reports_bad_characters() {
+        use crate::thread::map_reduce::{ErrorMode, FailureKind, SegmentFailure};
+
+        let data = "123 45x6 789 1?2";
+        let err = digit_sum(data, 1).unwrap_err();
+        let bad_x = InvalidDigit { offset: 6, character: 'x' };
+        assert_eq!(err.failures, vec![SegmentFailure { segment: 1, kind: FailureKind::Failed(bad_x) }]);
+        assert_eq!(err.to_string(), "segment 1: invalid digit 'x' at byte 6");
+
+        let options = Options::new(3).errors(ErrorMode::CollectAll);
+        let err = digit_sum_with(data, options).unwrap_err();
+        let bad_question = InvalidDigit { offset: 14, character: '?' };
+        assert_eq!(err.failures, vec![
+            SegmentFailure { segment: 1, kind: FailureKind::Failed(bad_x) },
+            SegmentFailure { segment: 3, kind: FailureKind::Failed(bad_question) },
+        ]);
+    }
+
+    #[test]
+    fn test_word_count() {
+        let counts = word_count(TEXT, 3);
+        assert_eq!(counts["the"], 4);
+        assert_eq!(counts["fox"], 3);
+        assert_eq!(counts["a"], 2);
+        assert_eq!(counts.get("dog;"), None);
+        assert_eq!(counts.values().sum::<usize>(), TEXT.split_whitespace().count());
+        assert!(word_count("", 2).is_empty());
+        assert_demo_snapshot("thread::word_count", demo_word_count);
     }
-}
\ No newline at end of file
+}
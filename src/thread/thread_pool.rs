@@ -0,0 +1,435 @@
+/*
+    线程池ThreadPool
+
+    demo_for_thread为每个任务创建一个新的操作系统线程，再逐个join。
+    线程的创建和销毁都有开销，任务多的时候更好的做法是：
+
+        预先创建固定数量的worker线程，任务（闭包）通过channel排队，空闲的worker取出来执行。
+
+    - execute：提交一个不关心结果的任务；
+    - submit：提交任务并返回JobHandle，join()拿到任务的返回值，用法与thread::JoinHandle相同；
+    - 任务中的panic会被catch_unwind捕获，worker线程继续工作：
+        submit的任务，panic由对应JobHandle::join返回；
+        execute的任务，panic被记录下来，由shutdown()返回给调用者（drop时打印到stderr）；
+    - shutdown或drop时先关闭队列，等已经排队的任务全部执行完，再回收worker线程；
+    - scope：与std::thread::scope类似，作用域中提交的任务可以借用调用者的数据，
+      scope返回之前会等待这些任务全部结束，任务中的panic在等待之后传播给调用者。
+*/
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use super::sync::mpsc::{self, Receiver, Sender};
+use super::sync::thread::{self, JoinHandle};
+use super::sync::{Condvar, Mutex, MutexGuard};
+use crate::runner::panic_message;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+thread_local! {
+    // 当前线程是否是某个线程池的worker
+    static IS_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// 固定大小的线程池
+pub struct ThreadPool {
+    workers: Vec<JoinHandle<()>>,
+    // shutdown时先把它drop掉，worker的recv随之返回Err而退出
+    sender: Option<Sender<Job>>,
+    // execute提交的任务中发生的panic
+    panics: Arc<Mutex<Vec<String>>>,
+}
+
+impl ThreadPool {
+    /// 创建有size个worker的线程池，size为0时panic
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0, "ThreadPool size must be at least 1");
+        let (sender, receiver) = mpsc::channel::<Job>();
+        // 多个worker共享同一个Receiver，同一时刻只有一个worker在等待新任务
+        let receiver = Arc::new(Mutex::new(receiver));
+        let panics = Arc::new(Mutex::new(vec![]));
+
+        let workers = (0..size)
+            .map(|id| {
+                let receiver = Arc::clone(&receiver);
+                let panics = Arc::clone(&panics);
+                thread::Builder::new()
+                    .name(format!("pool-worker-{}", id))
+                    .spawn(move || worker_loop(&receiver, &panics))
+                    .expect("failed to spawn pool worker")
+            })
+            .collect();
+        ThreadPool { workers, sender: Some(sender), panics }
+    }
+
+    /// worker线程的数量
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// 当前线程是否是（任意一个）线程池的worker
+    ///
+    /// worker线程中调用scope并等待，可能占住所有worker而死锁，调用者可以据此改为在本线程中执行
+    pub fn is_worker_thread() -> bool {
+        IS_WORKER.with(|flag| flag.get())
+    }
+
+    /// 提交一个任务，不关心它的结果
+    pub fn execute<F>(&self, job: F) where F: FnOnce() + Send + 'static {
+        self.send(Box::new(job));
+    }
+
+    fn send(&self, job: Job) {
+        self.sender.as_ref()
+            .expect("sender is only taken during shutdown")
+            .send(job)
+            .expect("all pool workers have exited");
+    }
+
+    /// 提交一个任务，返回可以取回结果的句柄
+    pub fn submit<F, T>(&self, job: F) -> JobHandle<T>
+        where F: FnOnce() -> T + Send + 'static,
+              T: Send + 'static,
+    {
+        let (result_sender, result_receiver) = mpsc::channel();
+        self.execute(move || {
+            // 在这里捕获panic，交给JobHandle，而不是记入线程池的panics
+            let result = panic::catch_unwind(AssertUnwindSafe(job));
+            // 调用者可能已经丢弃了JobHandle，此时结果没人要，忽略发送错误即可
+            let _ = result_sender.send(result);
+        });
+        JobHandle { receiver: result_receiver }
+    }
+
+    /// 创建一个作用域，其中提交的任务可以借用生命周期为'scope的数据
+    ///
+    /// 返回之前（包括f panic时）等待作用域中的任务全部结束；任务panic时，
+    /// 第一个panic在所有任务结束后传播给调用者。不要在本线程池的worker中调用，否则可能死锁
+    pub fn scope<'scope, F, R>(&self, f: F) -> R
+        where F: FnOnce(&Scope<'_, 'scope>) -> R,
+    {
+        let scope = Scope {
+            pool: self,
+            state: Arc::new(ScopeState { pending: Mutex::new(0), done: Condvar::new(), panic: Mutex::new(None) }),
+            _scope: PhantomData,
+        };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        // 无论f是否panic，都必须等任务结束：它们还借用着'scope的数据
+        scope.state.wait();
+        let result = result.unwrap_or_else(|payload| panic::resume_unwind(payload));
+        if let Some(payload) = lock(&scope.state.panic).take() {
+            panic::resume_unwind(payload);
+        }
+        result
+    }
+
+    /// 到目前为止execute的任务中发生的panic（取出后清空）
+    pub fn take_panics(&self) -> Vec<String> {
+        std::mem::take(&mut *self.panics.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    /// 关闭线程池：等所有已提交的任务执行完毕，回收worker线程
+    ///
+    /// execute的任务中如果发生过panic，返回Err，其中是每个panic的信息
+    pub fn shutdown(mut self) -> Result<(), JobPanics> {
+        self.join_workers();
+        let panics = self.take_panics();
+        if panics.is_empty() { Ok(()) } else { Err(JobPanics(panics)) }
+    }
+
+    fn join_workers(&mut self) {
+        // drop发送端之后，队列中剩余的任务仍会被取完，之后recv返回Err，worker退出
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            // worker_loop本身不会panic（任务的panic已被捕获），这里的Err只可能来自内部错误
+            if let Err(payload) = worker.join() {
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+}
+
+// 每个worker线程反复从队列中取出任务执行，直到队列关闭
+fn worker_loop(receiver: &Mutex<Receiver<Job>>, panics: &Mutex<Vec<String>>) {
+    IS_WORKER.with(|flag| flag.set(true));
+    loop {
+        // 锁只在recv期间持有，语句结束时MutexGuard就被drop，执行任务时不占用锁
+        let job = receiver.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv();
+        match job {
+            Ok(job) => {
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                    let name = thread::current().name().unwrap_or("pool-worker").to_string();
+                    panics.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .push(format!("{}: {}", name, panic_message(payload.as_ref())));
+                }
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.join_workers();
+        // drop无法返回错误，至少不要让panic悄无声息地消失
+        for message in self.take_panics() {
+            eprintln!("ThreadPool: job panicked: {}", message);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // 任务在锁外执行，这些锁不会因为任务panic而中毒
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// ThreadPool::scope中的作用域
+pub struct Scope<'pool, 'scope> {
+    pool: &'pool ThreadPool,
+    state: Arc<ScopeState>,
+    // 让'scope成为不变（invariant）的生命周期，与std::thread::Scope相同：
+    // 否则作用域可以被当成更短的'scope使用，任务就能借用f中很快被释放的局部变量
+    _scope: PhantomData<&'scope mut &'scope ()>,
+}
+
+struct ScopeState {
+    // 已提交、尚未结束的任务数
+    pending: Mutex<usize>,
+    done: Condvar,
+    // 第一个panic的payload
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+}
+
+impl ScopeState {
+    fn wait(&self) {
+        let mut pending = lock(&self.pending);
+        while *pending > 0 {
+            pending = self.done.wait(pending).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+impl<'pool, 'scope> Scope<'pool, 'scope> {
+    /// 在线程池中执行一个可以借用'scope数据的任务
+    pub fn execute<F>(&self, job: F) where F: FnOnce() + Send + 'scope {
+        *lock(&self.state.pending) += 1;
+        let state = Arc::clone(&self.state);
+        let job: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                lock(&state.panic).get_or_insert(payload);
+            }
+            // 到这里job（连同它借用的数据）已经用完
+            let mut pending = lock(&state.pending);
+            *pending -= 1;
+            if *pending == 0 {
+                state.done.notify_all();
+            }
+        });
+        // SAFETY: ThreadPool::scope返回之前会等待pending归零，也就是等这个任务执行完毕，
+        // 而'scope比scope调用活得更久，所以任务借用的数据在它运行期间一直有效
+        let job = unsafe { mem::transmute::<Box<dyn FnOnce() + Send + 'scope>, Job>(job) };
+        self.pool.send(job);
+    }
+}
+
+/// submit返回的句柄
+pub struct JobHandle<T> {
+    receiver: Receiver<thread::Result<T>>,
+}
+
+impl<T> JobHandle<T> {
+    /// 阻塞等待任务结束；任务panic时返回Err，其中是panic的payload，与thread::JoinHandle::join一致
+    pub fn join(self) -> thread::Result<T> {
+        self.receiver.recv().unwrap_or_else(|_| Err(Box::new("job was dropped before it finished")))
+    }
+
+    /// 任务已结束时返回结果，否则把句柄原样交还
+    pub fn try_join(self) -> Result<thread::Result<T>, JobHandle<T>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Ok(result),
+            Err(mpsc::TryRecvError::Empty) => Err(self),
+            Err(mpsc::TryRecvError::Disconnected) => Ok(Err(Box::new("job was dropped before it finished"))),
+        }
+    }
+}
+
+/// execute提交的任务中发生的panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobPanics(pub Vec<String>);
+
+impl Display for JobPanics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} job(s) panicked: {}", self.0.len(), self.0.join("; "))
+    }
+}
+
+impl std::error::Error for JobPanics {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    #[test]
+    fn test_submit_returns_results() {
+        let pool = ThreadPool::new(3);
+        assert_eq!(pool.size(), 3);
+        let handles: Vec<JobHandle<u64>> = (0..20u64).map(|i| pool.submit(move || i * i)).collect();
+        let results: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results, (0..20u64).map(|i| i * i).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_only_pool_threads_are_used() {
+        let pool = ThreadPool::new(2);
+        let names: HashSet<String> = (0..50)
+            .map(|_| pool.submit(|| thread::current().name().unwrap().to_string()))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect();
+        assert!(names.iter().all(|name| name.starts_with("pool-worker-")));
+        assert!(names.len() <= 2);
+    }
+
+    #[test]
+    fn test_drop_waits_for_queued_jobs() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        {
+            let pool = ThreadPool::new(2);
+            for _ in 0..100 {
+                let counter = Arc::clone(&counter);
+                pool.execute(move || {
+                    thread::sleep(Duration::from_micros(50));
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 100);
+    }
+
+    #[test]
+    fn test_submit_panic_goes_to_handle() {
+        let pool = ThreadPool::new(1);
+        let bad = pool.submit(|| -> u32 { panic!("bad job {}", 7) });
+        let good = pool.submit(|| 42);
+        let payload = bad.join().unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "bad job 7");
+        // worker没有因为panic退出，后面的任务照常执行
+        assert_eq!(good.join().unwrap(), 42);
+        assert_eq!(pool.shutdown(), Ok(()));
+    }
+
+    #[test]
+    fn test_execute_panic_is_surfaced_by_shutdown() {
+        let pool = ThreadPool::new(2);
+        pool.execute(|| panic!("lost?"));
+        pool.execute(|| {});
+        let err = pool.shutdown().unwrap_err();
+        assert_eq!(err.0.len(), 1);
+        assert!(err.0[0].ends_with(": lost?"), "{}", err);
+        assert!(err.to_string().starts_with("1 job(s) panicked"));
+    }
+
+    #[test]
+    fn test_try_join() {
+        let pool = ThreadPool::new(1);
+        let (tx, rx) = mpsc::channel::<()>();
+        // 任务一直阻塞到收到信号
+        let handle = pool.submit(move || rx.recv().is_ok());
+        let handle = match handle.try_join() {
+            Err(handle) => handle,
+            Ok(_) => panic!("job finished too early"),
+        };
+        tx.send(()).unwrap();
+        assert!(handle.join().unwrap());
+    }
+
+    #[test]
+    fn test_scope_borrows_local_data() {
+        let pool = ThreadPool::new(3);
+        let mut data = vec![1u64; 100];
+        let total = AtomicUsize::new(0);
+        pool.scope(|scope| {
+            for chunk in data.chunks_mut(7) {
+                let total = &total;
+                scope.execute(move || {
+                    chunk.iter_mut().for_each(|x| *x *= 2);
+                    total.fetch_add(chunk.len(), Ordering::SeqCst);
+                });
+            }
+        });
+        // scope返回时所有任务都已结束
+        assert_eq!(total.load(Ordering::SeqCst), 100);
+        assert_eq!(data, vec![2; 100]);
+        assert!(!ThreadPool::is_worker_thread());
+        assert!(pool.submit(ThreadPool::is_worker_thread).join().unwrap());
+    }
+
+    #[test]
+    fn test_scope_panic_waits_for_other_jobs() {
+        let pool = ThreadPool::new(2);
+        let finished = AtomicUsize::new(0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.scope(|scope| {
+                scope.execute(|| panic!("scoped job failed"));
+                for _ in 0..10 {
+                    scope.execute(|| {
+                        thread::sleep(Duration::from_millis(1));
+                        finished.fetch_add(1, Ordering::SeqCst);
+                    });
+                }
+            })
+        }));
+        assert_eq!(panic_message(result.unwrap_err().as_ref()), "scoped job failed");
+        assert_eq!(finished.load(Ordering::SeqCst), 10);
+        // scope中的panic不记入线程池的panics
+        assert_eq!(pool.shutdown(), Ok(()));
+    }
+
+    #[cfg(model)]
+    #[test]
+    fn test_model_submit_and_shutdown() {
+        // 在各种交错下：结果按提交顺序取回，shutdown等所有任务结束后worker全部退出
+        crate::thread::model::check(200, || {
+            let pool = ThreadPool::new(2);
+            let done = Arc::new(Mutex::new(0));
+            let handles: Vec<JobHandle<u32>> = (0..3).map(|i| pool.submit(move || i * 10)).collect();
+            for _ in 0..2 {
+                let done = Arc::clone(&done);
+                pool.execute(move || *done.lock().unwrap() += 1);
+            }
+            let results: Vec<u32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+            assert_eq!(results, vec![0, 10, 20]);
+            assert_eq!(pool.shutdown(), Ok(()));
+            assert_eq!(*done.lock().unwrap(), 2);
+        });
+    }
+
+    #[cfg(model)]
+    #[test]
+    fn test_model_scope_waits_for_jobs() {
+        crate::thread::model::check(200, || {
+            let pool = ThreadPool::new(2);
+            let mut data = vec![0; 4];
+            pool.scope(|scope| {
+                for (i, x) in data.iter_mut().enumerate() {
+                    scope.execute(move || *x = i + 1);
+                }
+            });
+            assert_eq!(data, vec![1, 2, 3, 4]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1")]
+    fn test_zero_size() {
+        ThreadPool::new(0);
+    }
+}
@@ -0,0 +1,210 @@
+/*
+    工作窃取（work stealing）调度
+
+    testcase_map_reduce中的数据段一样长，每个线程的工作量相同。
+    真实的输入往往是倾斜的：如果事先把任务平均分给各个线程，分到慢任务的线程会拖住整个join。
+
+    工作窃取的做法：
+        - 每个worker有自己的双端队列（deque），开始时任务按顺序分成连续的几段，每个worker一段；
+        - worker从自己队列的`队头`取任务；
+        - 自己的队列空了，就随机挑一个其他worker，从它队列的`队尾`偷走一半任务；
+        - 所有队列都空了（本调度器不会在运行中产生新任务），worker退出。
+
+    这里只用到了std的Mutex和原子类型（`--cfg model`时换成thread::model中可以被调度器控制的版本）。每个队列各有一把锁，
+    worker大部分时间只访问自己的队列，只有窃取时才会碰别人的锁。
+*/
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::Ordering;
+
+use super::sync::{thread, AtomicBool, AtomicUsize, Mutex, MutexGuard};
+
+/// 一次运行的统计信息
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// 成功窃取的次数
+    pub steals: usize,
+    /// 被窃取的任务总数
+    pub stolen_tasks: usize,
+}
+
+// 每个worker自己的伪随机数（xorshift），用于随机挑选被窃取的worker
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> XorShift {
+        // 种子不能为0
+        XorShift(seed | 1)
+    }
+
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+// 每次运行挑选被窃取者的顺序都不同
+#[cfg(not(model))]
+fn seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64)
+}
+
+// 模型检查中的调度只能取决于调度器的种子，否则失败的执行无法重放
+#[cfg(model)]
+fn seed() -> u64 {
+    0
+}
+
+fn lock<T>(deque: &Mutex<T>) -> MutexGuard<'_, T> {
+    // 任务在锁外执行，锁不会因为任务panic而中毒；即便中毒，队列本身也是完好的
+    deque.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 用workers个线程，以工作窃取的方式对每个任务执行f，按任务的原始顺序返回结果
+///
+/// f中的panic会让其余worker尽快停下，并在所有worker结束后传播给调用者。workers为0时panic
+pub fn run<T, R, F>(tasks: Vec<T>, workers: usize, f: F) -> Vec<R>
+    where T: Send, R: Send, F: Fn(T) -> R + Sync,
+{
+    run_with_stats(tasks, workers, f).0
+}
+
+/// 与run相同，另外返回窃取的统计信息
+pub fn run_with_stats<T, R, F>(tasks: Vec<T>, workers: usize, f: F) -> (Vec<R>, Stats)
+    where T: Send, R: Send, F: Fn(T) -> R + Sync,
+{
+    assert!(workers > 0, "work stealing needs at least one worker");
+    let total = tasks.len();
+    let workers = workers.min(total.max(1));
+
+    // 按顺序切成连续的workers段
+    let mut deques: Vec<VecDeque<(usize, T)>> = (0..workers).map(|_| VecDeque::new()).collect();
+    for (index, task) in tasks.into_iter().enumerate() {
+        deques[index * workers / total.max(1)].push_back((index, task));
+    }
+    let deques: Vec<Mutex<VecDeque<(usize, T)>>> = deques.into_iter().map(Mutex::new).collect();
+
+    let steals = AtomicUsize::new(0);
+    let stolen_tasks = AtomicUsize::new(0);
+    // 有任务panic时置位，其他worker看到后不再领取新任务
+    let aborted = AtomicBool::new(false);
+    let seed = seed();
+
+    let worker = |id: usize| {
+        let mut rng = XorShift::new(seed ^ (id as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let mut results = vec![];
+        while !aborted.load(Ordering::Relaxed) {
+            // 先看自己的队头；注意取出任务的语句结束时锁就释放了
+            let own = lock(&deques[id]).pop_front();
+            let (index, task) = match own.or_else(|| steal(&deques, id, &mut rng, &steals, &stolen_tasks)) {
+                Some(job) => job,
+                None => break,
+            };
+            match panic::catch_unwind(AssertUnwindSafe(|| f(task))) {
+                Ok(result) => results.push((index, result)),
+                Err(payload) => {
+                    aborted.store(true, Ordering::Relaxed);
+                    return Err(payload);
+                }
+            }
+        }
+        Ok(results)
+    };
+
+    let mut indexed = Vec::with_capacity(total);
+    let mut panicked = None;
+    thread::scope(|scope| {
+        let worker = &worker;
+        let handlers: Vec<_> = (0..workers).map(|id| scope.spawn(move || worker(id))).collect();
+        for handler in handlers {
+            match handler.join().expect("worker loop catches task panics") {
+                Ok(results) => indexed.extend(results),
+                // 只保留第一个panic
+                Err(payload) => {
+                    if panicked.is_none() {
+                        panicked = Some(payload);
+                    }
+                }
+            }
+        }
+    });
+    if let Some(payload) = panicked {
+        panic::resume_unwind(payload);
+    }
+
+    indexed.sort_by_key(|&(index, _)| index);
+    let stats = Stats { steals: steals.into_inner(), stolen_tasks: stolen_tasks.into_inner() };
+    (indexed.into_iter().map(|(_, result)| result).collect(), stats)
+}
+
+// 从随机的起点开始依次尝试其他worker，偷走第一个非空队列队尾的一半任务，
+// 返回其中的一个，其余放进自己的队列。所有队列都空时返回None
+fn steal<T>(deques: &[Mutex<VecDeque<(usize, T)>>], thief: usize, rng: &mut XorShift,
+            steals: &AtomicUsize, stolen_tasks: &AtomicUsize) -> Option<(usize, T)> {
+    let start = rng.next(deques.len());
+    for offset in 0..deques.len() {
+        let victim = (start + offset) % deques.len();
+        if victim == thief {
+            continue;
+        }
+        let mut loot = {
+            let mut victim = lock(&deques[victim]);
+            let count = victim.len().div_ceil(2);
+            let keep = victim.len() - count;
+            victim.split_off(keep)
+        };
+        if loot.is_empty() {
+            continue;
+        }
+        steals.fetch_add(1, Ordering::Relaxed);
+        stolen_tasks.fetch_add(loot.len(), Ordering::Relaxed);
+        // 偷来的任务保持原来的相对顺序：先执行最前面的一个，其余放进自己的队列
+        let first = loot.pop_front();
+        lock(&deques[thief]).extend(loot);
+        return first;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_results_keep_task_order() {
+        for workers in 1..=5 {
+            let results = run((0..100).collect(), workers, |x: u64| x * 3);
+            assert_eq!(results, (0..100).map(|x| x * 3).collect::<Vec<_>>());
+        }
+        assert!(run(Vec::<u8>::new(), 4, |x| x).is_empty());
+    }
+
+    #[test]
+    fn test_skewed_tasks_get_stolen() {
+        // 前4个任务都很慢，并且最初都分给了worker 0，其他worker必须去偷
+        let tasks: Vec<u64> = (0..16).map(|i| if i < 4 { 20 } else { 0 }).collect();
+        let (results, stats) = run_with_stats(tasks, 4, |millis| {
+            thread::sleep(Duration::from_millis(millis));
+            millis
+        });
+        assert_eq!(results.iter().sum::<u64>(), 80);
+        assert!(stats.steals > 0 && stats.stolen_tasks > 0, "{:?}", stats);
+    }
+
+    #[test]
+    fn test_borrowed_data() {
+        let words = ["alpha".to_string(), "beta".to_string(), "gamma".to_string()];
+        let lengths = run(words.iter().collect(), 2, |word: &String| word.len());
+        assert_eq!(lengths, vec![5, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "task 13 failed")]
+    fn test_panic_stops_and_propagates() {
+        run((0..1000).collect(), 4, |i: u32| if i == 13 { panic!("task {} failed", i) } else { i });
+    }
+}
@@ -23,26 +23,47 @@
 // 指向DST的指针理所当然也应该是一个 “胖指针”。
 // 它里面包含了两个成员 都是指向单元类型的`裸指针`。
 
+use std::mem;
+use std::io::{self, Write};
+use crate::runner::Demo;
+
 trait Bird {
-    fn fly(&self);
+    fn fly(&self) -> &'static str;
 }
 
 struct Duck;
 
 impl Bird for Duck {
-    fn fly(&self) {
-        println!("Duck flies!")
+    fn fly(&self) -> &'static str {
+        "Duck flies!"
     }
 }
 
 struct Swan;
 
 impl Bird for Swan {
-    fn fly(&self) {
-        println!("Swan flies!")
+    fn fly(&self) -> &'static str {
+        "Swan flies!"
     }
 }
 
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "trait_object", about: "trait object是带虚函数表指针的胖指针", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    // trait object比普通引用多一个指向虚函数表的指针
+    writeln!(out, "&Duck: {} words, &dyn Bird: {} words",
+             mem::size_of::<&Duck>() / mem::size_of::<usize>(),
+             mem::size_of::<&dyn Bird>() / mem::size_of::<usize>())?;
+
+    let birds: [&dyn Bird; 2] = [&Duck, &Swan];
+    for bird in birds.iter() {
+        writeln!(out, "{}", bird.fly())?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +85,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(function_casts_as_integer, clippy::size_of_ref)]
     fn test_trait_object() {
         use std::mem;
         // 尝试一下使用unsafe代码，如果把它里面的数值当成整数拿出来会是什么结果:
@@ -105,4 +127,4 @@ mod tests {
             如果一个类型实现了多个trait，那么不同的trait object指向的虚函数表也不一样 。
         */
     }
-}
\ No newline at end of file
+}
@@ -15,6 +15,7 @@ mod tests {
 
     // 包含资源的结构体，并实现了Clone trait
     #[derive(Debug, Clone)]
+    #[allow(dead_code)]
     struct Pair<'a>(Box<f64>, &'a str);
 
     #[test]
@@ -34,10 +34,12 @@ mod tests {
     }
 
     // `Test3`，不带附加属性的tuple struct
+    #[allow(dead_code)]
     struct Test3(i32);
 
     #[test]
     fn test_derive() {
+        #[allow(unused_variables)]
         let test3 = Test3(1024);
 
         // 报错，因为Test3没有实现Debug trait
@@ -26,10 +26,13 @@ mod tests {
         let test1 = Test { name: "test1" };
 
         {   // 作用域2
+            #[allow(unused_variables)]
             let test2 = Test { name: "test2" };
 
             {   // 作用域3
+                #[allow(unused_variables)]
                 let test3 = Test { name: "test3" };
+                #[allow(unused_variables)]
                 let test4 = Test { name: "test4" };
                 println!("Exiting Scope 3")
             }
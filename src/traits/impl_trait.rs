@@ -13,6 +13,7 @@ use std::vec;
 #[allow(dead_code)]
 // 该方法将两个Vec<i32>合并在一起，并且在其上面套了一层迭代器（Iterator）
 // 可以看一下返回值类型是有多复杂！
+#[allow(clippy::useless_conversion)]
 fn combine_vecs_explicit_return_type(
     v: Vec<i32>,
     u: Vec<i32>,
@@ -23,6 +24,7 @@ fn combine_vecs_explicit_return_type(
 #[allow(dead_code)]
 // 下面的函数跟combine_vecs_explicit_return_type相同
 // 看看其的简化程度
+#[allow(clippy::useless_conversion)]
 fn combine_vecs(
     v: Vec<i32>,
     u: Vec<i32>,
@@ -36,6 +38,7 @@ fn combine_vecs(
 // 但是现在完全可以静态地返回闭包了
 #[cfg(test)]
 mod tests {
+    #[allow(clippy::let_and_return)]
     fn make_adder_function(n: i32)
                            -> impl Fn(i32) -> i32 {
         // 创建一个闭包
@@ -54,6 +57,7 @@ mod tests {
     // 这样使得使用map和filter更加简单。
     // 由于闭包是匿名的，如果函数要返回一个满足Iterator约束的闭包，程序员无法显式地写出来的。
     // 但是使用impl Trait就可以轻松做到：
+    #[allow(clippy::ptr_arg)]
     fn double_positives<'a>(numbers: &'a Vec<i32>) -> impl Iterator<Item=i32> + 'a {
         numbers
             .iter()
@@ -87,6 +91,7 @@ mod tests {
 
     #[test]
     // 生命周期测试
+    #[allow(clippy::needless_late_init)]
     fn test_double_positives_lifetime() {
         // 会报错：error[E0597]: `v` does not live long enough
         // 为什么？因为iterator定义在v前面
@@ -33,6 +33,7 @@ mod tests {
         // 输出类型，即加法和的类型——Test3
         type Output = Test3;
         // 即实现 Test2+Test1=Test3 这样的运算
+        #[allow(unused_variables)]
         fn add(self, rhs: Test1) -> Self::Output {
             println!("Test2.Add() is invoked");
             Test3
@@ -45,6 +46,7 @@ mod tests {
         type Output = Test4;
 
         // Test1+Test2=Test4
+        #[allow(unused_variables)]
         fn add(self, rhs: Test2) -> Self::Output {
             println!("Test1.Add() is invoked");
             Test4
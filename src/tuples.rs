@@ -8,44 +8,65 @@ and returns a Rectangle with its lower left corner on the point,
 and a width and height corresponding to the f32.
 */
 
-#[cfg(test)]
-mod tests {
-    #[derive(Debug)]
-    struct Person<'a> {
-        name: &'a str,
-        age: u8,
-    }
+use std::io::{self, Write};
+use crate::runner::Demo;
 
-    #[derive(Debug)]
-    struct Point {
-        x: f32,
-        y: f32,
-    }
+#[derive(Debug)]
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub age: u8,
+}
 
-    // Structs can be reused as fields of another struct
-    #[derive(Debug)]
-    struct Rectangle {
-        top_left: Point,
-        bottom_right: Point,
-    }
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
 
+// Structs can be reused as fields of another struct
+/// 由左上角和右下角确定的矩形
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    pub top_left: Point,
+    pub bottom_right: Point,
+}
 
-    // Activity1_1
+
+// Activity1_1
 //fn rect_area(Rectangle { top_left: Point { x: x1, y: y1 }, bottom_right: Point { x: x2, y: y2 } }: Rectangle) -> f32 {
 //    (x2 - x1) * (y2 - y1)
 //}
 
-    // Activity1_2
-    fn rect_area(r: &Rectangle) -> f32 {
-        (r.bottom_right.x - r.top_left.x) * (r.bottom_right.y - r.top_left.y)
-    }
+// Activity1_2
+/// 计算矩形面积
+pub fn rect_area(r: &Rectangle) -> f32 {
+    (r.bottom_right.x - r.top_left.x) * (r.bottom_right.y - r.top_left.y)
+}
+
+// Activity2
+/// 以点p为一角，构造一个矩形
+pub fn square(p: Point, area: f32) -> Rectangle {
+    let width = area / 2f32;
+    let x_new = p.x + width;
+    Rectangle { top_left: p, bottom_right: Point { x: x_new, y: 2f32 } }
+}
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "tuples", about: "嵌套结构体：矩形面积与square", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let r = Rectangle { top_left: Point { x: 1.0, y: 1.0 }, bottom_right: Point { x: 3.0, y: 4.0 } };
+    // Activity1
+    writeln!(out, "{}", rect_area(&r))?;
 
     // Activity2
-    fn square(p: Point, area: f32) -> Rectangle {
-        let width = area / 2f32;
-        let x_new = p.x + width;
-        Rectangle { top_left: p, bottom_right: Point { x: x_new, y: 2f32 } }
-    }
+    writeln!(out, "{:?}", square(Point { x: 1.0, y: 2.0 }, 4.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Point, Rectangle, rect_area, square};
 
     #[test]
     fn test_tuples() {
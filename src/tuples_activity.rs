@@ -20,41 +20,61 @@ Transpose:
 ( 1.2 2.2 )
 */
 
-#[cfg(test)]
-mod tests{
-    use std::fmt::{Display, Formatter, Error};
+use std::fmt::{Display, Formatter, Error};
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+fn _reverse(pair: (i32, bool)) -> (bool, i32) {
+    // `let` can be used to bind the members of a tuple to variables
+    let (integer, boolean) = pair;
+
+    (boolean, integer)
+}
 
-    fn _reverse(pair: (i32, bool)) -> (bool, i32) {
-        // `let` can be used to bind the members of a tuple to variables
-        let (integer, boolean) = pair;
+/// 2x2矩阵，按行存储：(a11, a12, a21, a22)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix(pub f32, pub f32, pub f32, pub f32);
 
-        (boolean, integer)
+// Activity1
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "( {} {} )\n( {} {} )", self.0, self.1, self.2, self.3)
     }
+}
+
+// Activity2
+/// 返回矩阵的转置
+pub fn transpose(matrix: &Matrix) -> Matrix {
+    Matrix(matrix.0, matrix.2, matrix.1, matrix.3)
+}
 
-    #[derive(Debug)]
-    struct Matrix(f32, f32, f32, f32);
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "tuples_activity::matrix", about: "Matrix的Display与转置", run: demo },
+];
 
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
     // Activity1
-    impl Display for Matrix {
-        fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-            write!(f, "( {} {} )\n( {} {} )", self.0, self.1, self.2, self.3)
-        }
-    }
+    writeln!(out, "{:?}", matrix)?;
+    writeln!(out, "{}", matrix)?;
 
     // Activity2
-    fn transpose(matrix: &Matrix) -> Matrix {
-        Matrix { 0: matrix.0, 1: matrix.2, 2: matrix.1, 3: matrix.3 }
-    }
+    writeln!(out, "Matrix:\n{}", matrix)?;
+    writeln!(out, "Transpose:\n{}", transpose(&matrix))
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{Matrix, transpose};
 
     #[test]
     fn test_tuples_activity(){
-        let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
-        // Activity1
-        println!("{:?}", matrix);
-        println!("{}", matrix);
+        let mut out = Vec::new();
+        super::demo(&mut out).unwrap();
+        print!("{}", String::from_utf8(out).unwrap());
 
-        // Activity2
-        println!("Matrix:\n{}", matrix);
-        println!("Transpose:\n{}", transpose(&matrix));
+        let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
+        assert_eq!(transpose(&matrix), Matrix(1.1, 2.1, 1.2, 2.2));
+        assert_eq!(format!("{}", matrix), "( 1.1 1.2 )\n( 2.1 2.2 )");
     }
 }
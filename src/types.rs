@@ -1,5 +1,26 @@
 // 不显示类型转换产生的溢出警告。
-#![allow(overflowing_literals)]
+
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "types", about: "用as进行类型转换，以及字面量的大小", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let decimal = 65.4321_f32;
+    let integer = decimal as u8;
+    let character = integer as char;
+    writeln!(out, "Casting: {} -> {} -> {}", decimal, integer, character)?;
+
+    // 转换为较小的整数类型时，只保留低位
+    writeln!(out, "1000 as a u8 is : {}", 1000_i32 as u8)?;
+    writeln!(out, "  -1 as a u8 is : {}", -1_i8 as u8)?;
+    writeln!(out, " 128 as a i8 is : {}", 128_i32 as i8)?;
+
+    writeln!(out, "size of `1u8` in bytes: {}", std::mem::size_of_val(&1u8))?;
+    writeln!(out, "size of `1f64` in bytes: {}", std::mem::size_of_val(&1f64))
+}
 
 #[cfg(test)]
 mod tests {
@@ -7,6 +28,7 @@ mod tests {
 
     // 类型转换
     #[test]
+    #[allow(overflowing_literals, clippy::unnecessary_cast)]
     fn test_casting() {
         let decimal = 65.4321_f32;
         /*         错误！不提供隐式转换
@@ -89,4 +111,4 @@ mod tests {
         // 别名的主要用途是避免写出冗长的模板化代码（boilerplate code）。
         // 如 IoResult<T> 是 Result<T, IoError> 类型的别名。
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,22 @@
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "variable_bindings", about: "变量绑定与复制", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let an_integer = 1u32;
+    let a_boolean = true;
+
+    // 将 `an_integer` 复制到 `copied_integer`
+    let copied_integer = an_integer;
+
+    writeln!(out, "An integer: {:?}", copied_integer)?;
+    writeln!(out, "A boolean: {:?}", a_boolean)?;
+    writeln!(out, "Meet the unit value: {:?}", ())
+}
+
 #[cfg(test)]
 mod tests {
 //    use super::*;
@@ -20,6 +39,7 @@ mod tests {
         let _unused_variable = 3u32;
 
         // compiler warning
+        #[allow(unused_variables)]
         let noisy_unused_variable = 2u32;
     }
 }
@@ -1,3 +1,27 @@
+use std::io::{self, Write};
+use crate::runner::Demo;
+
+pub const DEMOS: &[Demo] = &[
+    Demo { name: "while_control", about: "用while循环写FizzBuzz", run: demo },
+];
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut n = 1;
+    while n <= 15 {
+        if n % 15 == 0 {
+            writeln!(out, "fizzbuzz")?;
+        } else if n % 3 == 0 {
+            writeln!(out, "fizz")?;
+        } else if n % 5 == 0 {
+            writeln!(out, "buzz")?;
+        } else {
+            writeln!(out, "{}", n)?;
+        }
+        n += 1;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -18,4 +42,4 @@ mod test {
             n += 1;
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,8 @@
+// src/static_and_dynamic_dispatch.rs：trait是DST类型，不能直接作为参数类型
+// error-code: E0277
+
+trait Bird {
+    fn fly(&self);
+}
+
+pub fn test(arg: dyn Bird) {}
@@ -0,0 +1,18 @@
+// src/static_and_dynamic_dispatch.rs：trait是DST类型，不能直接作为返回类型
+// error-code: E0746
+
+trait Bird {
+    fn fly(&self);
+}
+
+struct Duck;
+
+impl Bird for Duck {
+    fn fly(&self) {
+        println!("Duck flies!")
+    }
+}
+
+pub fn test() -> dyn Bird {
+    Duck
+}
@@ -0,0 +1,8 @@
+// src/lifetimes/explicit_annotation.rs：短生命周期不能强制转换成长生命周期
+// error-code: E0597
+
+pub fn failed_borrow<'a>() {
+    let x = 1024;
+    // x是在函数内部声明的，它的生命周期一定小于函数的生命周期'a
+    let y: &'a i32 = &x;
+}
@@ -0,0 +1,7 @@
+// src/lifetimes/functions.rs：`'a` 存活的时间必须比函数的长
+// error-code: E0515
+
+pub fn invalid_output<'a>() -> &'a String {
+    // String对象在离开函数invalid_output时会被释放掉，返回的引用将指向无效数据
+    &String::from("michael.w")
+}
@@ -0,0 +1,6 @@
+// src/lifetimes/functions.rs：没有输入引用时，返回的引用无法省略生命周期
+// error-code: E0106
+
+pub fn invalid_output() -> &String {
+    &String::from("michael.w")
+}
@@ -0,0 +1,7 @@
+// src/object_safe.rs 条件二：方法的返回值中用到了Self类型，例如Clone
+// error-code: E0038
+
+pub fn object_safe_v3() {
+    let mut x = 1024;
+    let p = &mut x as &mut dyn Clone;
+}
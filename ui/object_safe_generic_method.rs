@@ -0,0 +1,22 @@
+// src/object_safe.rs 条件四：trait中有泛型方法
+// error-code: E0038
+
+use std::fmt::Debug;
+
+trait Trait {
+    fn generic_func<T: Debug>(&self, a: T);
+}
+
+struct Foo4;
+
+impl Trait for Foo4 {
+    fn generic_func<T: Debug>(&self, a: T) {
+        println!("{:?}", a);
+    }
+}
+
+pub fn object_safe_v7() {
+    let foo4 = Foo4;
+    let p = &foo4 as &dyn Trait;
+    p.generic_func("michael.w");
+}
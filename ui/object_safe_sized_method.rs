@@ -0,0 +1,24 @@
+// src/object_safe.rs 条件一：方法带有 Self: Sized 约束时，不能通过trait object调用
+// error-message: the `foo2` method cannot be invoked on a trait object
+
+trait Foo1 {
+    fn foo1(&self);
+    fn foo2(&self) where Self: Sized;
+}
+
+impl Foo1 for i32 {
+    fn foo1(&self) {
+        println!("foo1() is invoked: {}", self);
+    }
+
+    fn foo2(&self) where Self: Sized {
+        println!("foo2() is invoked: {}", self);
+    }
+}
+
+pub fn object_safe_v2() {
+    let x = 1024;
+    let p = &x as &dyn Foo1;
+    p.foo1();
+    p.foo2();
+}
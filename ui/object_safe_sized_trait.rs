@@ -0,0 +1,20 @@
+// src/object_safe.rs 条件一：trait带有 Self: Sized 约束时，不能构造trait object
+// error-code: E0038
+
+trait Foo where Self: Sized {
+    fn foo(&self);
+}
+
+impl Foo for i32 {
+    fn foo(&self) {
+        println!("{}", self);
+    }
+}
+
+pub fn object_safe_v1() {
+    let x = 1024;
+    x.foo();
+    // the trait `Foo` is not dyn compatible
+    let p = &x as &dyn Foo;
+    p.foo();
+}
@@ -0,0 +1,20 @@
+// src/object_safe.rs 条件三：trait中有静态方法
+// error-code: E0038
+
+trait Double2 {
+    fn double_static(num: i32) -> i32;
+    fn double(&mut self);
+}
+
+struct Foo2(i32);
+
+impl Double2 for Foo2 {
+    fn double_static(num: i32) -> i32 { num * 2 }
+
+    fn double(&mut self) { self.0 *= 2 }
+}
+
+pub fn object_safe_v5() {
+    let mut foo2 = Foo2(1024);
+    let p = &mut foo2 as &mut dyn Double2;
+}